@@ -0,0 +1,94 @@
+use turbopack_binding::swc::core::{
+    common::{comments::Comments, Span},
+    ecma::{
+        ast::{CallExpr, Callee, Module},
+        visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith},
+    },
+};
+
+use crate::import_analyzer::ImportMap;
+
+/// Exports of packages which only create values when called, like the
+/// style function creators of styled-system and the icon factories of UI
+/// libraries. Unused results of their calls can be dropped.
+static PURE_EXPORTS: &[(&str, &[&str])] = &[
+    (
+        "styled-system",
+        &[
+            "system",
+            "compose",
+            "variant",
+            "createParser",
+            "createStyleFunction",
+        ],
+    ),
+    (
+        "@styled-system/core",
+        &["system", "compose", "createParser", "createStyleFunction"],
+    ),
+    ("@styled-system/variant", &["variant"]),
+    ("@chakra-ui/icon", &["createIcon"]),
+    ("@chakra-ui/icons", &["createIcon"]),
+    ("@chakra-ui/react", &["createIcon"]),
+    ("@mui/material", &["createSvgIcon"]),
+    ("@mui/material/utils", &["createSvgIcon"]),
+    ("@mui/icons-material/utils", &["createSvgIcon"]),
+];
+
+/// Annotates calls of [PURE_EXPORTS] with `/*#__PURE__*/`, so the modules
+/// defining icons or style functions can be tree shaken when they're unused.
+pub fn auto_pure<C>(comments: C) -> impl Fold
+where
+    C: Comments,
+{
+    as_folder(AutoPure {
+        imports: Default::default(),
+        comments,
+    })
+}
+
+struct AutoPure<C>
+where
+    C: Comments,
+{
+    imports: ImportMap,
+    comments: C,
+}
+
+impl<C> VisitMut for AutoPure<C>
+where
+    C: Comments,
+{
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, m: &mut Module) {
+        self.imports = ImportMap::analyze(m);
+
+        m.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        call.visit_mut_children_with(self);
+
+        let Callee::Expr(callee) = &call.callee else {
+            return;
+        };
+
+        let is_pure = PURE_EXPORTS.iter().any(|(module, exports)| {
+            exports
+                .iter()
+                .any(|export| self.imports.is_import(callee, module, export))
+        });
+        if !is_pure {
+            return;
+        }
+
+        let mut lo = call.span.lo;
+        if lo.is_dummy() {
+            lo = Span::dummy_with_cmt().lo;
+            call.span.lo = lo;
+        }
+
+        self.comments.add_pure_comment(lo);
+    }
+}
@@ -0,0 +1,143 @@
+// This transform gives `import.meta` the same meaning in every bundler:
+// - On the server, `import.meta.url` is replaced with the `file://` URL of the
+//   original source file, like webpack does by default for node targets. It's
+//   kept in browser code, which mustn't contain paths of the build machine, and
+//   in `new URL(..., import.meta.url)`, which bundlers detect as an asset or a
+//   worker reference.
+// - `import.meta.env` is an alias of `process.env`, so
+//   `import.meta.env.NEXT_PUBLIC_*` and `import.meta.env.NODE_ENV` are inlined
+//   the same way as their `process.env` counterparts.
+//
+// All other `import.meta` properties are left to the bundler.
+
+use std::path::Path;
+
+use turbopack_binding::swc::core::{
+    common::{FileName, SyntaxContext, DUMMY_SP},
+    ecma::{
+        ast::*,
+        visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith},
+    },
+};
+
+pub fn import_meta(
+    file_name: &FileName,
+    unresolved_ctxt: SyntaxContext,
+    is_server: bool,
+) -> impl Fold {
+    let url = match file_name {
+        FileName::Real(path) if is_server && path.is_absolute() => Some(file_url(path)),
+        _ => None,
+    };
+
+    as_folder(ImportMeta {
+        url,
+        unresolved_ctxt,
+    })
+}
+
+struct ImportMeta {
+    url: Option<String>,
+    unresolved_ctxt: SyntaxContext,
+}
+
+fn is_import_meta(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::MetaProp(MetaPropExpr {
+            kind: MetaPropKind::ImportMeta,
+            ..
+        })
+    )
+}
+
+fn is_import_meta_url(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if is_import_meta(obj) && &*prop.sym == "url"
+    )
+}
+
+impl VisitMut for ImportMeta {
+    noop_visit_mut_type!();
+
+    fn visit_mut_new_expr(&mut self, n: &mut NewExpr) {
+        // `new URL('./file', import.meta.url)` is resolved by the bundler.
+        if let Expr::Ident(callee) = &*n.callee {
+            if &*callee.sym == "URL" && callee.span.ctxt == self.unresolved_ctxt {
+                if let Some([input, base]) = n.args.as_deref_mut() {
+                    if base.spread.is_none() && is_import_meta_url(&base.expr) {
+                        input.visit_mut_with(self);
+                        return;
+                    }
+                }
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        if let Expr::Member(MemberExpr {
+            span,
+            obj,
+            prop: MemberProp::Ident(prop),
+        }) = e
+        {
+            if is_import_meta(obj) {
+                match &*prop.sym {
+                    "url" => {
+                        if let Some(url) = &self.url {
+                            *e = Expr::Lit(Lit::Str(Str {
+                                span: *span,
+                                value: url.clone().into(),
+                                raw: None,
+                            }));
+                        }
+                    }
+                    "env" => {
+                        *e = Expr::Member(MemberExpr {
+                            span: *span,
+                            obj: Box::new(Expr::Ident(Ident::new(
+                                "process".into(),
+                                DUMMY_SP.with_ctxt(self.unresolved_ctxt),
+                            ))),
+                            prop: MemberProp::Ident(Ident::new("env".into(), DUMMY_SP)),
+                        });
+                    }
+                    _ => {}
+                }
+                return;
+            }
+        }
+
+        e.visit_mut_children_with(self);
+    }
+}
+
+/// Formats an absolute path as a `file://` URL, the same way as Node.js'
+/// `url.pathToFileURL`.
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for c in path.chars() {
+        match c {
+            '%' => url.push_str("%25"),
+            ' ' => url.push_str("%20"),
+            '#' => url.push_str("%23"),
+            '?' => url.push_str("%3F"),
+            '\n' => url.push_str("%0A"),
+            '\r' => url.push_str("%0D"),
+            '\t' => url.push_str("%09"),
+            c => url.push(c),
+        }
+    }
+    url
+}
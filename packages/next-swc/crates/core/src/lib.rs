@@ -55,9 +55,11 @@ use turbopack_binding::swc::{
 
 pub mod amp_attributes;
 mod auto_cjs;
+pub mod auto_pure;
 pub mod cjs_optimizer;
 pub mod disallow_re_export_all_in_page;
 mod import_analyzer;
+pub mod import_meta;
 pub mod named_import_transform;
 pub mod next_ssg;
 pub mod optimize_barrel;
@@ -67,6 +69,7 @@ pub mod pure;
 pub mod react_server_components;
 pub mod server_actions;
 pub mod shake_exports;
+pub mod split_named_imports;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -317,6 +320,15 @@ where
             },
             None => Either::Right(noop()),
         },
+        import_meta::import_meta(
+            &file.name,
+            SyntaxContext::empty().apply_mark(unresolved_mark),
+            opts.is_server_compiler
+        ),
+        Optional::new(
+            auto_pure::auto_pure(comments.clone()),
+            !opts.is_development
+        ),
         pure::pure_magic(comments),
     )
 }
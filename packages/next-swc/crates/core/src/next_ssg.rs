@@ -267,8 +267,10 @@ impl Fold for Analyzer<'_> {
         s
     }
 
+    /// Only local exports reference bindings of the module. The specifiers of
+    /// re-exports refer to the exports of their source.
     fn fold_named_export(&mut self, mut n: NamedExport) -> NamedExport {
-        if n.src.is_some() {
+        if n.src.is_none() {
             n.specifiers = n.specifiers.fold_with(self);
         }
 
@@ -487,6 +489,7 @@ impl Fold for NextSsg {
                 for item in take(&mut items) {
                     if let ModuleItem::ModuleDecl(
                         ModuleDecl::ExportNamed(..)
+                        | ModuleDecl::ExportAll(..)
                         | ModuleDecl::ExportDecl(..)
                         | ModuleDecl::ExportDefaultDecl(..)
                         | ModuleDecl::ExportDefaultExpr(..),
@@ -199,8 +199,17 @@ impl<C: Comments> ServerActions<C> {
 
         let args_arg = private_ident!("args");
 
+        // The code replacing the action keeps its span, so that source maps
+        // still map breakpoints and stack frames in it to the original
+        // function.
+        let span = arrow
+            .as_ref()
+            .map(|a| a.span)
+            .or_else(|| function.as_ref().map(|f| f.span))
+            .unwrap_or(DUMMY_SP);
+
         let call = CallExpr {
-            span: DUMMY_SP,
+            span,
             callee: action_ident
                 .clone()
                 .make_member(quote_ident!("apply"))
@@ -264,7 +273,7 @@ impl<C: Comments> ServerActions<C> {
             }
 
             let new_arrow = ArrowExpr {
-                span: DUMMY_SP,
+                span,
                 params: vec![
                     // ...args
                     Pat::Rest(RestPat {
@@ -376,7 +385,7 @@ impl<C: Comments> ServerActions<C> {
             // ($ACTION = async () => {}, $ACTION.$$id = "..", ..,
             // $ACTION)
             let mut exprs = vec![Box::new(Expr::Assign(AssignExpr {
-                span: DUMMY_SP,
+                span,
                 left: PatOrExpr::Pat(Box::new(Pat::Ident(ident.clone().into()))),
                 op: op!("="),
                 right: Box::new(Expr::Arrow(new_arrow)),
@@ -391,11 +400,8 @@ impl<C: Comments> ServerActions<C> {
             exprs.push(Box::new(Expr::Ident(ident.clone())));
 
             let new_paren = ParenExpr {
-                span: DUMMY_SP,
-                expr: Box::new(Expr::Seq(SeqExpr {
-                    span: DUMMY_SP,
-                    exprs,
-                })),
+                span,
+                expr: Box::new(Expr::Seq(SeqExpr { span, exprs })),
             };
 
             return (Some(Box::new(new_paren)), None);
@@ -437,11 +443,11 @@ impl<C: Comments> ServerActions<C> {
                     },
                 ],
                 decorators: vec![],
-                span: f.span,
+                span,
                 body: Some(BlockStmt {
                     span: DUMMY_SP,
                     stmts: vec![Stmt::Return(ReturnStmt {
-                        span: DUMMY_SP,
+                        span,
                         arg: Some(call.into()),
                     })],
                 }),
@@ -535,7 +541,7 @@ impl<C: Comments> ServerActions<C> {
                 // ($ACTION = async function () {}, $ACTION.$$id = "..", ..,
                 // $ACTION)
                 let mut exprs = vec![Box::new(Expr::Assign(AssignExpr {
-                    span: DUMMY_SP,
+                    span,
                     left: PatOrExpr::Pat(Box::new(Pat::Ident(ident.clone().into()))),
                     op: op!("="),
                     right: Box::new(Expr::Fn(FnExpr {
@@ -551,11 +557,8 @@ impl<C: Comments> ServerActions<C> {
                 exprs.push(Box::new(Expr::Ident(ident.clone())));
 
                 let new_paren = ParenExpr {
-                    span: DUMMY_SP,
-                    expr: Box::new(Expr::Seq(SeqExpr {
-                        span: DUMMY_SP,
-                        exprs,
-                    })),
+                    span,
+                    expr: Box::new(Expr::Seq(SeqExpr { span, exprs })),
                 };
 
                 return (Some(Box::new(new_paren)), None);
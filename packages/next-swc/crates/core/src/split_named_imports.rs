@@ -0,0 +1,93 @@
+use serde::Deserialize;
+use turbopack_binding::swc::core::{
+    common::DUMMY_SP,
+    ecma::{ast::*, visit::Fold},
+};
+
+/// The query appended to the request of every split import, naming the export
+/// it imports.
+pub const BARREL_EXPORT_QUERY: &str = "__next_barrel_export__";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    pub packages: Vec<String>,
+}
+
+/// Splits the named imports of the given packages into one import per name,
+/// e.g. `import { a, b as c } from 'pkg'` into
+/// `import { a } from 'pkg?__next_barrel_export__=a'` and
+/// `import { b as c } from 'pkg?__next_barrel_export__=b'`, so that each of
+/// them can be resolved to the module behind the package's barrel file.
+///
+/// This is the Turbopack counterpart of [crate::named_import_transform], which
+/// combines the names into a single `__barrel_optimize__` request for
+/// webpack's barrel loader.
+pub fn split_named_imports(config: Config) -> impl Fold {
+    SplitNamedImports {
+        packages: config.packages,
+    }
+}
+
+#[derive(Debug, Default)]
+struct SplitNamedImports {
+    packages: Vec<String>,
+}
+
+impl SplitNamedImports {
+    fn split(&self, decl: &ImportDecl) -> Option<Vec<ImportDecl>> {
+        if decl.type_only || !self.packages.iter().any(|p| decl.src.value == *p) {
+            return None;
+        }
+
+        let mut decls = vec![];
+        for specifier in &decl.specifiers {
+            // Default and namespace imports need the whole barrel.
+            let ImportSpecifier::Named(named) = specifier else {
+                return None;
+            };
+            if named.is_type_only {
+                continue;
+            }
+            let name = match &named.imported {
+                Some(ModuleExportName::Ident(ident)) => &ident.sym,
+                // Export names which aren't identifiers can't be passed through
+                // the query as is.
+                Some(ModuleExportName::Str(_)) => return None,
+                None => &named.local.sym,
+            };
+            decls.push(ImportDecl {
+                span: DUMMY_SP,
+                specifiers: vec![specifier.clone()],
+                src: Box::new(Str {
+                    span: DUMMY_SP,
+                    value: format!("{}?{}={}", decl.src.value, BARREL_EXPORT_QUERY, name).into(),
+                    raw: None,
+                }),
+                ..decl.clone()
+            });
+        }
+
+        // Keep side-effect imports (`import 'pkg'`) as they are.
+        (!decls.is_empty()).then_some(decls)
+    }
+}
+
+impl Fold for SplitNamedImports {
+    fn fold_module_items(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let mut new_items = Vec::with_capacity(items.len());
+        for item in items {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) = &item {
+                if let Some(decls) = self.split(decl) {
+                    new_items.extend(
+                        decls
+                            .into_iter()
+                            .map(|decl| ModuleItem::ModuleDecl(ModuleDecl::Import(decl))),
+                    );
+                    continue;
+                }
+            }
+            new_items.push(item);
+        }
+        new_items
+    }
+}
@@ -2,7 +2,9 @@ use std::{env::current_dir, path::PathBuf};
 
 use next_swc::{
     amp_attributes::amp_attributes,
+    auto_pure::auto_pure,
     cjs_optimizer::cjs_optimizer,
+    import_meta::import_meta,
     named_import_transform::named_import_transform,
     next_ssg::next_ssg,
     optimize_barrel::optimize_barrel,
@@ -14,6 +16,7 @@ use next_swc::{
         server_actions, {self},
     },
     shake_exports::{shake_exports, Config as ShakeExportsConfig},
+    split_named_imports::split_named_imports,
 };
 use next_transform_dynamic::{next_dynamic, NextDynamicMode};
 use next_transform_font::{next_font_loaders, Config as FontLoaderConfig};
@@ -496,6 +499,32 @@ fn named_import_transform_fixture(input: PathBuf) {
     );
 }
 
+#[fixture("tests/fixture/split-named-imports/**/input.js")]
+fn split_named_imports_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, false),
+                split_named_imports(json(
+                    r#"
+                    {
+                        "packages": ["foo", "@scope/bar"]
+                    }
+                    "#
+                ))
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
 #[fixture("tests/fixture/optimize-barrel/normal/**/input.js")]
 fn optimize_barrel_fixture(input: PathBuf) {
     let output = input.parent().unwrap().join("output.js");
@@ -596,3 +625,75 @@ fn pure(input: PathBuf) {
         Default::default(),
     );
 }
+
+#[fixture("tests/fixture/auto-pure/**/input.js")]
+fn auto_pure_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, false),
+                auto_pure(tr.comments.clone())
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/import-meta/server/**/input.js")]
+fn import_meta_server_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, false),
+                import_meta(
+                    &FileName::Real(PathBuf::from("/some-project/src/some file.js")),
+                    unresolved_ctxt,
+                    true
+                )
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/import-meta/client/**/input.js")]
+fn import_meta_client_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, false),
+                import_meta(
+                    &FileName::Real(PathBuf::from("/some-project/src/some file.js")),
+                    unresolved_ctxt,
+                    false
+                )
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
@@ -0,0 +1,10 @@
+import { createIcon } from '@chakra-ui/icons'
+import * as utils from '@mui/material/utils'
+
+export const CheckIcon = createIcon({
+  displayName: 'CheckIcon',
+  viewBox: '0 0 24 24',
+  path: <path d="M9 16.2 4.8 12l-1.4 1.4L9 19 21 7l-1.4-1.4L9 16.2z" />,
+})
+
+export const CloseIcon = utils.createSvgIcon(<path d="M19 6.41 17.59 5 12 10.59" />, 'Close')
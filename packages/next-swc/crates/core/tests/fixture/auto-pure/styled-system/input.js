@@ -0,0 +1,11 @@
+import { compose, space, system, variant } from 'styled-system'
+
+export const textStyles = system({
+  fontSize: { property: 'fontSize', scale: 'fontSizes' },
+})
+
+export const buttonVariants = variant({
+  variants: { primary: { color: 'white' } },
+})
+
+export const layout = compose(space, textStyles)
@@ -0,0 +1,15 @@
+import { compose, space, system, variant } from 'styled-system';
+export const textStyles = /*#__PURE__*/ system({
+    fontSize: {
+        property: 'fontSize',
+        scale: 'fontSizes'
+    }
+});
+export const buttonVariants = /*#__PURE__*/ variant({
+    variants: {
+        primary: {
+            color: 'white'
+        }
+    }
+});
+export const layout = /*#__PURE__*/ compose(space, textStyles);
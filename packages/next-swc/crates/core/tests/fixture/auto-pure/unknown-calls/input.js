@@ -0,0 +1,10 @@
+import { createIcon } from './icons'
+import { system } from 'styled-system'
+
+export const Icon = createIcon({ displayName: 'Icon' })
+
+function setup(system) {
+  return system({ color: true })
+}
+
+export const theme = setup(system)
@@ -0,0 +1,5 @@
+const worker = new Worker(new URL('./worker.js', import.meta.url))
+const image = new URL('./image.png', import.meta.url).href
+const base = new URL(import.meta.url)
+
+export { worker, image, base }
@@ -0,0 +1,5 @@
+const url = import.meta.url
+const env = import.meta.env
+const mode = import.meta.env.NODE_ENV
+
+export { url, env, mode }
@@ -0,0 +1,4 @@
+const url = import.meta.url;
+const env = process.env;
+const mode = process.env.NODE_ENV;
+export { url, env, mode };
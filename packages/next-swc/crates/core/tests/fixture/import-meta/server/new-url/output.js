@@ -0,0 +1,4 @@
+const worker = new Worker(new URL('./worker.js', import.meta.url));
+const image = new URL('./image.png', import.meta.url).href;
+const base = new URL("file:///some-project/src/some%20file.js");
+export { worker, image, base };
@@ -0,0 +1,6 @@
+const url = import.meta.url
+const env = import.meta.env
+const mode = import.meta.env.NODE_ENV
+const hot = import.meta.webpackHot
+
+export { url, env, mode, hot }
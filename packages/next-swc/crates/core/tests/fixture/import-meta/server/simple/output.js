@@ -0,0 +1,5 @@
+const url = "file:///some-project/src/some%20file.js";
+const env = process.env;
+const mode = process.env.NODE_ENV;
+const hot = import.meta.webpackHot;
+export { url, env, mode, hot };
@@ -0,0 +1,6 @@
+import { A, B as C } from 'foo'
+import { D } from '@scope/bar'
+import E, { F } from 'foo'
+import * as G from 'foo'
+import 'foo'
+import { H } from 'baz'
@@ -0,0 +1,7 @@
+import { A } from "foo?__next_barrel_export__=A";
+import { B as C } from "foo?__next_barrel_export__=B";
+import { D } from "@scope/bar?__next_barrel_export__=D";
+import E, { F } from 'foo';
+import * as G from 'foo';
+import 'foo';
+import { H } from 'baz';
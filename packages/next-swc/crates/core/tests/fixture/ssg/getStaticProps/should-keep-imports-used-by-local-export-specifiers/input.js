@@ -0,0 +1,12 @@
+import { getPosts, formatDate } from '../lib/posts'
+
+export async function getStaticProps() {
+  const posts = await getPosts()
+  return { props: { posts, updatedAt: formatDate(new Date()) } }
+}
+
+export { formatDate }
+
+export default function Blog({ posts }) {
+  return <ul>{posts.length}</ul>
+}
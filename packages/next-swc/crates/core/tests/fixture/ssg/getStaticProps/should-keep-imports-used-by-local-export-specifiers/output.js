@@ -0,0 +1,6 @@
+import { formatDate } from '../lib/posts';
+export var __N_SSG = true;
+export { formatDate };
+export default function Blog({ posts }) {
+    return __jsx("ul", null, posts.length);
+}
@@ -0,0 +1,2 @@
+export { getStaticProps } from '../lib/data'
+export * from '../components/page'
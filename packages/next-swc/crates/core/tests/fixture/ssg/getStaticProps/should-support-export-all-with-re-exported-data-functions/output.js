@@ -0,0 +1,2 @@
+export var __N_SSG = true;
+export * from '../components/page';
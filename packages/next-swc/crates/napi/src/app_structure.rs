@@ -6,9 +6,12 @@ use napi::{
     threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode},
     JsFunction,
 };
-use next_core::app_structure::{
-    find_app_dir, get_entrypoints as get_entrypoints_impl, Components, Entrypoint, Entrypoints,
-    LoaderTree, MetadataItem, MetadataWithAltItem,
+use next_core::{
+    app_structure::{
+        find_app_dir, get_entrypoints as get_entrypoints_impl, Components, Entrypoint, Entrypoints,
+        LoaderTree, MetadataItem, MetadataWithAltItem,
+    },
+    mode::NextMode,
 };
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{ReadRef, Vc};
@@ -328,6 +331,7 @@ async fn get_value(
     root_dir: String,
     project_dir: String,
     page_extensions: Vec<String>,
+    mode: NextMode,
     watching: bool,
 ) -> Result<Vc<OptionEntrypointsForJs>> {
     let page_extensions = Vc::cell(page_extensions);
@@ -342,7 +346,7 @@ async fn get_value(
     let app_dir = find_app_dir(project_path);
 
     let result = if let Some(app_dir) = *app_dir.await? {
-        let entrypoints = get_entrypoints_impl(app_dir, page_extensions);
+        let entrypoints = get_entrypoints_impl(app_dir, page_extensions, mode);
         let entrypoints_for_js = prepare_entrypoints_for_js(project_path, entrypoints);
 
         Some(entrypoints_for_js)
@@ -359,9 +363,15 @@ pub fn stream_entrypoints(
     root_dir: String,
     project_dir: String,
     page_extensions: Vec<String>,
+    dev: bool,
     func: JsFunction,
 ) -> napi::Result<()> {
     register();
+    let mode = if dev {
+        NextMode::Development
+    } else {
+        NextMode::Build
+    };
     let func: ThreadsafeFunction<Option<ReadRef<EntrypointsForJs>>, ErrorStrategy::CalleeHandled> =
         func.create_threadsafe_function(0, |ctx| {
             let value = ctx.value;
@@ -381,6 +391,7 @@ pub fn stream_entrypoints(
                 (*root_dir).clone(),
                 (*project_dir).clone(),
                 page_extensions.iter().map(|s| s.to_string()).collect(),
+                mode,
                 true,
             )
             .await?
@@ -405,14 +416,21 @@ pub async fn get_entrypoints(
     root_dir: String,
     project_dir: String,
     page_extensions: Vec<String>,
+    dev: bool,
 ) -> napi::Result<serde_json::Value> {
     register();
+    let mode = if dev {
+        NextMode::Development
+    } else {
+        NextMode::Build
+    };
     let result = turbo_tasks
         .run_once(async move {
             let value = if let Some(entrypoints) = &*get_value(
                 root_dir,
                 project_dir,
                 page_extensions.iter().map(|s| s.to_string()).collect(),
+                mode,
                 false,
             )
             .await?
@@ -0,0 +1,133 @@
+use anyhow::{anyhow, bail, Context as _};
+use napi::bindgen_prelude::*;
+use serde::Deserialize;
+use turbopack_binding::swc::core::{
+    common::{FileName, FilePathMapping, SourceMap},
+    css::{
+        ast::Stylesheet,
+        codegen::{
+            writer::basic::{BasicCssWriter, BasicCssWriterConfig},
+            CodeGenerator, CodegenConfig, Emit,
+        },
+        minifier::minify,
+        parser::parse_file,
+        prefixer::{options::Options as PrefixerOptions, prefixer},
+        visit::VisitMutWith,
+    },
+};
+
+use crate::util::MapErr;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CssTransformOptions {
+    /// The name of the file being transformed, used in error messages.
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// Whether to minify the output.
+    #[serde(default)]
+    pub minify: bool,
+
+    /// The browser versions to add vendor prefixes for, e.g.
+    /// `{ "env": { "safari": "14" } }`. No prefixes are added when unset.
+    #[serde(flatten)]
+    pub prefixer: PrefixerOptions,
+}
+
+#[napi(object)]
+pub struct CssTransformOutput {
+    pub code: String,
+}
+
+/// Parses, prefixes and optionally minifies a stylesheet, using the same
+/// CSS pipeline as the rest of next-swc.
+fn transform_css_inner(
+    code: String,
+    options: &CssTransformOptions,
+) -> anyhow::Result<CssTransformOutput> {
+    let cm = SourceMap::new(FilePathMapping::empty());
+    let filename = options
+        .filename
+        .as_ref()
+        .map_or(FileName::Anon, |f| FileName::Real(f.into()));
+    let fm = cm.new_source_file(filename, code);
+
+    let mut errors = vec![];
+    let mut stylesheet: Stylesheet = parse_file(&fm, None, Default::default(), &mut errors)
+        .map_err(|err| anyhow!("failed to parse css: {}", err.message()))?;
+    if let Some(err) = errors.first() {
+        bail!("failed to parse css: {}", err.message());
+    }
+
+    if options.prefixer.env.is_some() {
+        stylesheet.visit_mut_with(&mut prefixer(options.prefixer.clone()));
+    }
+
+    if options.minify {
+        minify(&mut stylesheet, Default::default());
+    }
+
+    let mut output = String::new();
+    let writer = BasicCssWriter::new(&mut output, None, BasicCssWriterConfig::default());
+    let mut generator = CodeGenerator::new(
+        writer,
+        CodegenConfig {
+            minify: options.minify,
+        },
+    );
+    generator
+        .emit(&stylesheet)
+        .context("failed to generate css")?;
+
+    Ok(CssTransformOutput { code: output })
+}
+
+pub struct TransformCssTask {
+    code: String,
+    options: CssTransformOptions,
+}
+
+impl Task for TransformCssTask {
+    type Output = CssTransformOutput;
+    type JsValue = CssTransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        transform_css_inner(std::mem::take(&mut self.code), &self.options).convert_err()
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+#[napi]
+pub fn transform_css(
+    code: String,
+    options: Buffer,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<TransformCssTask>> {
+    let options = serde_json::from_slice(&options)?;
+    let task = TransformCssTask { code, options };
+    Ok(AsyncTask::with_optional_signal(task, signal))
+}
+
+#[napi]
+pub fn transform_css_sync(code: String, options: Buffer) -> napi::Result<CssTransformOutput> {
+    let options: CssTransformOptions = serde_json::from_slice(&options)?;
+    transform_css_inner(code, &options).convert_err()
+}
+
+#[test]
+fn test_transform_css_minify() {
+    let output = transform_css_inner(
+        ".a {\n  color: #ff0000;\n}\n".to_string(),
+        &CssTransformOptions {
+            minify: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(output.code, ".a{color:red}");
+}
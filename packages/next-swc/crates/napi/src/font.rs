@@ -0,0 +1,43 @@
+use napi::bindgen_prelude::*;
+use next_core::{calculate_font_adjustment, DEFAULT_SANS_SERIF_FONT, DEFAULT_SERIF_FONT};
+
+use crate::util::MapErr;
+
+#[napi(object)]
+pub struct NapiFontAdjustment {
+    pub ascent: f64,
+    pub descent: f64,
+    pub line_gap: f64,
+    pub size_adjust: f64,
+}
+
+/// Calculates the `ascent-override`, `descent-override`, `line-gap-override`
+/// and `size-adjust` of the fallback font for the given font file, matching
+/// the values generated by `next/font/local`. `fallback` is either `"Arial"`
+/// or `"Times New Roman"`.
+#[napi]
+pub fn calculate_font_fallback_metrics(
+    font_file: Buffer,
+    fallback: String,
+) -> napi::Result<NapiFontAdjustment> {
+    let fallback_font = match fallback.as_str() {
+        "Arial" => &*DEFAULT_SANS_SERIF_FONT,
+        "Times New Roman" => &*DEFAULT_SERIF_FONT,
+        _ => {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!("invalid fallback font: {}", fallback),
+            ))
+        }
+    };
+
+    let adjustment =
+        calculate_font_adjustment(&font_file, "<buffer>", fallback_font).convert_err()?;
+
+    Ok(NapiFontAdjustment {
+        ascent: adjustment.ascent,
+        descent: adjustment.descent,
+        line_gap: adjustment.line_gap,
+        size_adjust: adjustment.size_adjust,
+    })
+}
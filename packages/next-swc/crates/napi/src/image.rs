@@ -0,0 +1,111 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context;
+use napi::bindgen_prelude::*;
+use next_core::next_image::{blur_options, exif_orientation};
+use once_cell::sync::Lazy;
+use turbo_tasks::TurboTasks;
+use turbopack_binding::{
+    turbo::{
+        tasks_fs::{rope::Rope, DiskFileSystem, File, FileContent, FileSystem},
+        tasks_memory::MemoryBackend,
+    },
+    turbopack::{
+        core::{error::PrettyPrintError, ident::AssetIdent},
+        image::process::get_meta_data,
+    },
+};
+
+use crate::register;
+
+/// The memory limit of the turbo tasks instance used when no instance is
+/// passed. Every call creates new tasks, so they need to be collected.
+const IMAGE_TURBO_TASKS_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+static IMAGE_TURBO_TASKS: Lazy<Arc<TurboTasks<MemoryBackend>>> =
+    Lazy::new(|| TurboTasks::new(MemoryBackend::new(IMAGE_TURBO_TASKS_MEMORY_LIMIT)));
+
+#[napi(object)]
+pub struct NapiBlurPlaceholder {
+    pub data_url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[napi(object)]
+pub struct NapiImageMetadata {
+    pub width: u32,
+    pub height: u32,
+    pub mime_type: Option<String>,
+    /// The EXIF orientation, from 1 to 8. `width` and `height` are the stored
+    /// dimensions, which are swapped when displayed with orientations 5 to 8.
+    pub orientation: Option<u32>,
+    pub blur_placeholder: Option<NapiBlurPlaceholder>,
+}
+
+/// Returns the dimensions, the mime type, the EXIF orientation and optionally a
+/// blur placeholder of the image at `path`, using the same implementation as
+/// statically imported images. If `content` is given, it is used instead of
+/// reading `path`, which is then only used to detect the image format.
+///
+/// The tasks run on `turbo_tasks`, or on a shared instance. The file is read
+/// on every call, as the tasks don't watch the file system.
+#[napi]
+pub async fn get_image_metadata(
+    path: String,
+    content: Option<Buffer>,
+    blur_placeholder: bool,
+    turbo_tasks: Option<External<Arc<TurboTasks<MemoryBackend>>>>,
+) -> napi::Result<NapiImageMetadata> {
+    register();
+
+    let turbo_tasks = turbo_tasks
+        .map(|t| t.clone())
+        .unwrap_or_else(|| IMAGE_TURBO_TASKS.clone());
+    let content = match content {
+        Some(content) => content.to_vec(),
+        None => tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read image {path}"))
+            .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?,
+    };
+    let orientation = exif_orientation(&content).map(u32::from);
+
+    turbo_tasks
+        .run_once(async move {
+            let path = Path::new(&path);
+            let dir = path
+                .parent()
+                .with_context(|| format!("{} has no parent directory", path.display()))?;
+            let name = path
+                .file_name()
+                .with_context(|| format!("{} is not a file", path.display()))?;
+
+            let fs = DiskFileSystem::new("image".to_string(), dir.to_string_lossy().to_string());
+            let fs_path = fs.root().join(name.to_string_lossy().to_string());
+            let content = FileContent::Content(File::from(Rope::from(content))).cell();
+
+            let info = get_meta_data(
+                AssetIdent::from_path(fs_path),
+                content,
+                blur_placeholder.then(blur_options),
+            )
+            .await?;
+
+            Ok(NapiImageMetadata {
+                width: info.width,
+                height: info.height,
+                mime_type: info.mime_type.as_ref().map(|mime| mime.to_string()),
+                orientation,
+                blur_placeholder: info.blur_placeholder.as_ref().map(|placeholder| {
+                    NapiBlurPlaceholder {
+                        data_url: placeholder.data_url.clone(),
+                        width: placeholder.width,
+                        height: placeholder.height,
+                    }
+                }),
+            })
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
@@ -49,6 +49,9 @@ use turbopack_binding::swc::core::{
 };
 
 pub mod app_structure;
+pub mod css;
+pub mod font;
+pub mod image;
 pub mod mdx;
 pub mod minify;
 pub mod next_api;
@@ -67,6 +67,11 @@ impl From<&WrittenEndpoint> for NapiWrittenEndpoint {
                 global_var_name: Some(global_var_name.clone()),
                 ..Default::default()
             },
+            WrittenEndpoint::Client { files } => Self {
+                r#type: "client".to_string(),
+                files: Some(files.clone()),
+                ..Default::default()
+            },
         }
     }
 }
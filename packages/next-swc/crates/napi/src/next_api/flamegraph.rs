@@ -0,0 +1,181 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// A [Layer] aggregating the time spent in spans by stack, and writing it as
+/// collapsed stacks when the [FlamegraphGuard] is dropped. The file can be
+/// rendered with `inferno-flamegraph`, `flamegraph.pl` or
+/// https://www.speedscope.app.
+///
+/// Only the time a span is entered counts, so turbo-tasks waiting on other
+/// tasks don't show up as busy. The time of a nested span is subtracted from
+/// its parent, so every stack is weighted with its self time in microseconds.
+/// This isn't a sampling profiler: work outside of any span, e.g. inside a
+/// function which isn't instrumented, is attributed to the enclosing span.
+pub struct FlamegraphLayer {
+    stacks: Arc<Mutex<HashMap<String, Duration>>>,
+}
+
+/// Writes the aggregated stacks of a [FlamegraphLayer] when dropped.
+pub struct FlamegraphGuard {
+    stacks: Arc<Mutex<HashMap<String, Duration>>>,
+    path: PathBuf,
+}
+
+impl Drop for FlamegraphGuard {
+    fn drop(&mut self) {
+        let Ok(stacks) = self.stacks.lock() else {
+            return;
+        };
+        let Ok(file) = File::create(&self.path) else {
+            return;
+        };
+        let mut stacks = stacks.iter().collect::<Vec<_>>();
+        stacks.sort();
+
+        let mut writer = BufWriter::new(file);
+        for (stack, duration) in stacks {
+            let micros = duration.as_micros();
+            if micros > 0 {
+                let _ = writeln!(writer, "{stack} {micros}");
+            }
+        }
+        let _ = writer.flush();
+    }
+}
+
+struct SpanData {
+    /// The frames from the root span to this one, separated by `;`.
+    stack: String,
+    entered: Option<Instant>,
+    busy: Duration,
+    nested: Duration,
+}
+
+/// Picks the `name` field of a span as its frame, which turbo-tasks uses for
+/// the name of the function being executed.
+struct NameVisitor<'a>(&'a mut Option<String>);
+
+impl Visit for NameVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "name" {
+            *self.0 = Some(format!("{:?}", value));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            *self.0 = Some(value.to_string());
+        }
+    }
+}
+
+impl FlamegraphLayer {
+    /// Creates a layer writing the collapsed stacks to `path` once the
+    /// returned guard is dropped.
+    pub fn new(path: &Path) -> (Self, FlamegraphGuard) {
+        let stacks = Arc::new(Mutex::new(HashMap::new()));
+        (
+            Self {
+                stacks: stacks.clone(),
+            },
+            FlamegraphGuard {
+                stacks,
+                path: path.to_path_buf(),
+            },
+        )
+    }
+}
+
+impl<S> Layer<S> for FlamegraphLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut name = None;
+        attrs.record(&mut NameVisitor(&mut name));
+        // `;` separates frames and spaces separate the weight in the
+        // collapsed stack format.
+        let frame = name
+            .unwrap_or_else(|| span.name().to_string())
+            .replace([';', ' ', '\n'], "_");
+
+        let stack = match span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|d| d.stack.clone())
+        }) {
+            Some(parent_stack) => format!("{parent_stack};{frame}"),
+            None => frame,
+        };
+        span.extensions_mut().insert(SpanData {
+            stack,
+            entered: None,
+            busy: Duration::ZERO,
+            nested: Duration::ZERO,
+        });
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+            data.entered = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let elapsed = {
+            let mut extensions = span.extensions_mut();
+            let Some(data) = extensions.get_mut::<SpanData>() else {
+                return;
+            };
+            let Some(entered) = data.entered.take() else {
+                return;
+            };
+            let elapsed = entered.elapsed();
+            data.busy += elapsed;
+            elapsed
+        };
+        if let Some(parent) = span.parent() {
+            if let Some(data) = parent.extensions_mut().get_mut::<SpanData>() {
+                if data.entered.is_some() {
+                    data.nested += elapsed;
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        let self_time = data.busy.saturating_sub(data.nested);
+        if let Ok(mut stacks) = self.stacks.lock() {
+            *stacks.entry(data.stack).or_default() += self_time;
+        }
+    }
+}
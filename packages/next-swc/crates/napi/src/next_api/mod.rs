@@ -1,3 +1,5 @@
 pub mod endpoint;
+pub mod flamegraph;
+pub mod next_trace;
 pub mod project;
 pub mod utils;
@@ -0,0 +1,176 @@
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context as _, Result};
+use serde_json::{json, Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// Span ids written by the JavaScript tracer start at 0. Rust spans start at
+/// this offset so both can share a trace file without colliding.
+const SPAN_ID_OFFSET: u64 = 1 << 32;
+
+/// A [Layer] writing closed spans to a `.next/trace` file, in the same format
+/// as the JavaScript tracer in `packages/next/src/trace`. Every line of the
+/// file is a JSON array of events.
+pub struct NextTraceLayer {
+    writer: Arc<Mutex<BufWriter<File>>>,
+    trace_id: String,
+    origin: Instant,
+    origin_micros: u64,
+    next_id: AtomicU64,
+}
+
+/// Flushes the buffered events of a [NextTraceLayer] when dropped.
+pub struct NextTraceGuard(Arc<Mutex<BufWriter<File>>>);
+
+impl Drop for NextTraceGuard {
+    fn drop(&mut self) {
+        if let Ok(mut writer) = self.0.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+struct SpanData {
+    id: u64,
+    start: Instant,
+    start_time: u64,
+    tags: Map<String, Value>,
+}
+
+struct TagVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for TagVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::String(format!("{:?}", value)),
+        );
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::Bool(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+fn now_since_epoch() -> std::time::Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+impl NextTraceLayer {
+    /// Creates a layer appending to the trace file at `path`. The trace id is
+    /// taken from `TRACE_ID`, like in the JavaScript tracer, so spans from
+    /// both sides of a build end up in the same trace.
+    pub fn new(path: &Path) -> Result<(Self, NextTraceGuard)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Unable to open trace file {}", path.display()))?;
+        let writer = Arc::new(Mutex::new(BufWriter::new(file)));
+        let origin_micros = now_since_epoch().as_micros() as u64;
+        let trace_id =
+            std::env::var("TRACE_ID").unwrap_or_else(|_| format!("{:016x}", origin_micros));
+
+        Ok((
+            Self {
+                writer: writer.clone(),
+                trace_id,
+                origin: Instant::now(),
+                origin_micros,
+                next_id: AtomicU64::new(SPAN_ID_OFFSET),
+            },
+            NextTraceGuard(writer),
+        ))
+    }
+}
+
+impl<S> Layer<S> for NextTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut tags = Map::new();
+        attrs.record(&mut TagVisitor(&mut tags));
+        span.extensions_mut().insert(SpanData {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            start: Instant::now(),
+            start_time: now_since_epoch().as_millis() as u64,
+            tags,
+        });
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+            values.record(&mut TagVisitor(&mut data.tags));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        let parent_id = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanData>().map(|data| data.id));
+
+        let mut event = json!({
+            "traceId": self.trace_id,
+            "name": span.name(),
+            "id": data.id,
+            "timestamp": self.origin_micros
+                + data.start.duration_since(self.origin).as_micros() as u64,
+            "duration": data.start.elapsed().as_micros() as u64,
+            "tags": data.tags,
+            "startTime": data.start_time,
+        });
+        if let Some(parent_id) = parent_id {
+            event["parentId"] = parent_id.into();
+        }
+
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "[{event}]");
+        }
+    }
+}
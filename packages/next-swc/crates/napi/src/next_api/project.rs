@@ -4,15 +4,23 @@ use anyhow::{anyhow, bail, Context, Result};
 use napi::{
     bindgen_prelude::External,
     threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
-    JsFunction, Status,
+    Env, JsFunction, JsObject, Status,
 };
 use next_api::{
-    project::{DefineEnv, Middleware, PartialProjectOptions, ProjectContainer, ProjectOptions},
+    project::{
+        DefineEnv, Instrumentation, Middleware, PartialProjectOptions, ProjectContainer,
+        ProjectOptions,
+    },
     route::{Endpoint, Route},
 };
-use next_core::tracing_presets::{
-    TRACING_NEXT_TARGETS, TRACING_NEXT_TURBOPACK_TARGETS, TRACING_NEXT_TURBO_TASKS_TARGETS,
+use next_core::{
+    next_config::normalize_dist_dir,
+    next_telemetry::module_feature_usage,
+    tracing_presets::{
+        TRACING_NEXT_TARGETS, TRACING_NEXT_TURBOPACK_TARGETS, TRACING_NEXT_TURBO_TASKS_TARGETS,
+    },
 };
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
 use tracing_subscriber::{
     prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry,
 };
@@ -41,9 +49,11 @@ use url::Url;
 
 use super::{
     endpoint::ExternalEndpoint,
+    flamegraph::{FlamegraphGuard, FlamegraphLayer},
+    next_trace::{NextTraceGuard, NextTraceLayer},
     utils::{
-        get_diagnostics, get_issues, subscribe, NapiDiagnostic, NapiIssue, RootTask,
-        TurbopackResult, VcArc,
+        get_diagnostics, get_issues, get_unique_diagnostics, get_unique_issues, subscribe,
+        IssueFilter, NapiDiagnostic, NapiIssue, NapiIssueFilter, RootTask, TurbopackResult, VcArc,
     },
 };
 use crate::register;
@@ -86,6 +96,16 @@ pub struct NapiProjectOptions {
 
     /// The address of the dev server.
     pub server_addr: String,
+
+    /// Whether the project is compiled for `next dev` or `next build`.
+    pub dev: bool,
+
+    /// The edge functions whose chunks are rolled into a single file, by
+    /// page.
+    pub single_file_edge_routes: Option<Vec<String>>,
+
+    /// The ID of the build generated by Next.js.
+    pub build_id: Option<String>,
 }
 
 /// [NapiProjectOptions] with all fields optional.
@@ -120,6 +140,16 @@ pub struct NapiPartialProjectOptions {
 
     /// The address of the dev server.
     pub server_addr: Option<String>,
+
+    /// Whether the project is compiled for `next dev` or `next build`.
+    pub dev: Option<bool>,
+
+    /// The edge functions whose chunks are rolled into a single file, by
+    /// page.
+    pub single_file_edge_routes: Option<Vec<String>>,
+
+    /// The ID of the build generated by Next.js.
+    pub build_id: Option<Option<String>>,
 }
 
 #[napi(object)]
@@ -134,6 +164,12 @@ pub struct NapiDefineEnv {
 pub struct NapiTurboEngineOptions {
     /// An upper bound of memory that turbopack will attempt to stay under.
     pub memory_limit: Option<f64>,
+
+    /// If set, garbage collection of unused tasks is run periodically with
+    /// the given interval in milliseconds, in addition to the collection
+    /// triggered when approaching the memory limit. An interval of 0 disables
+    /// it, like leaving it unset.
+    pub gc_interval: Option<u32>,
 }
 
 impl From<NapiProjectOptions> for ProjectOptions {
@@ -151,6 +187,9 @@ impl From<NapiProjectOptions> for ProjectOptions {
                 .collect(),
             define_env: val.define_env.into(),
             server_addr: val.server_addr,
+            dev: val.dev,
+            single_file_edge_routes: val.single_file_edge_routes.unwrap_or_default(),
+            build_id: val.build_id,
         }
     }
 }
@@ -168,6 +207,9 @@ impl From<NapiPartialProjectOptions> for PartialProjectOptions {
                 .map(|env| env.into_iter().map(|var| (var.name, var.value)).collect()),
             define_env: val.define_env.map(|env| env.into()),
             server_addr: val.server_addr,
+            dev: val.dev,
+            single_file_edge_routes: val.single_file_edge_routes,
+            build_id: val.build_id,
         }
     }
 }
@@ -194,11 +236,115 @@ impl From<NapiDefineEnv> for DefineEnv {
     }
 }
 
+/// Holds the writer of the active trace output, flushing any remaining events
+/// once the project is dropped or the process exits.
+#[allow(dead_code)]
+pub(crate) enum TraceGuard {
+    /// The raw turbopack trace format (`trace.log`).
+    Raw(TraceWriterGuard),
+    /// The Chrome Trace Event Format (`trace.json`), enabled with
+    /// `NEXT_TURBOPACK_TRACE_FORMAT=chrome`.
+    Chrome(FlushGuard),
+    /// The `.next/trace` format written by the JavaScript tracer, enabled with
+    /// `NEXT_TURBOPACK_TRACE_FORMAT=next`.
+    Next(NextTraceGuard),
+    /// Collapsed stacks of the self time of every span (`trace.folded`),
+    /// enabled with `NEXT_TURBOPACK_TRACE_FORMAT=flamegraph`.
+    Flamegraph(FlamegraphGuard),
+}
+
+/// Sets up the trace output selected with `NEXT_TURBOPACK_TRACING` and
+/// `NEXT_TURBOPACK_TRACE_FORMAT`, writing into the dist dir of the project.
+/// The returned guard flushes the trace when dropped.
+pub(crate) fn init_tracing(
+    project_path: &str,
+    dist_dir: Option<&str>,
+) -> napi::Result<Option<ExitGuard<TraceGuard>>> {
+    let Ok(mut trace) = std::env::var("NEXT_TURBOPACK_TRACING") else {
+        return Ok(None);
+    };
+
+    // Trace presets
+    match trace.as_str() {
+        "overview" => {
+            trace = TRACING_OVERVIEW_TARGETS.join(",");
+        }
+        "next" => {
+            trace = TRACING_NEXT_TARGETS.join(",");
+        }
+        "turbopack" => {
+            trace = TRACING_NEXT_TURBOPACK_TARGETS.join(",");
+        }
+        "turbo-tasks" => {
+            trace = TRACING_NEXT_TURBO_TASKS_TARGETS.join(",");
+        }
+        _ => {}
+    }
+
+    let subscriber = Registry::default();
+
+    let subscriber = subscriber.with(EnvFilter::builder().parse(trace).unwrap());
+    let dist_dir = normalize_dist_dir(dist_dir)
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+
+    let internal_dir = PathBuf::from(project_path).join(dist_dir);
+    std::fs::create_dir_all(&internal_dir)
+        .context("Unable to create .next directory")
+        .unwrap();
+
+    let trace_format = std::env::var("NEXT_TURBOPACK_TRACE_FORMAT").ok();
+    let guard = match trace_format.as_deref() {
+        // Trace Event Format, which can be loaded into chrome://tracing or
+        // https://ui.perfetto.dev and attached to performance bug reports.
+        Some("chrome") => {
+            let trace_file = internal_dir.join("trace.json");
+            let (chrome_layer, guard) = ChromeLayerBuilder::new()
+                .file(trace_file)
+                .include_args(true)
+                .build();
+            subscriber.with(chrome_layer).init();
+
+            TraceGuard::Chrome(guard)
+        }
+        // Appends to the trace file of the JavaScript tracer, so existing
+        // tooling working on `.next/trace` picks up the compilation spans.
+        Some("next") => {
+            let trace_file = internal_dir.join("trace");
+            let (next_layer, guard) = NextTraceLayer::new(&trace_file).unwrap();
+            subscriber.with(next_layer).init();
+
+            TraceGuard::Next(guard)
+        }
+        // The self time of the traced spans by stack, which shows which tasks
+        // (transforms, resolving, chunking, ...) dominate the compilation
+        // time. It's derived from the spans, not sampled, so untraced work is
+        // attributed to the enclosing span. Best used with the `turbo-tasks`
+        // preset, as `next build --experimental-turbo-profile` does.
+        Some("flamegraph") => {
+            let trace_file = internal_dir.join("trace.folded");
+            let (flamegraph_layer, guard) = FlamegraphLayer::new(&trace_file);
+            subscriber.with(flamegraph_layer).init();
+
+            TraceGuard::Flamegraph(guard)
+        }
+        _ => {
+            let trace_file = internal_dir.join("trace.log");
+            let trace_writer = std::fs::File::create(trace_file).unwrap();
+            let (trace_writer, guard) = TraceWriter::new(trace_writer);
+            subscriber.with(RawTraceLayer::new(trace_writer)).init();
+
+            TraceGuard::Raw(guard)
+        }
+    };
+
+    Ok(Some(ExitGuard::new(guard).unwrap()))
+}
+
 pub struct ProjectInstance {
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
     container: Vc<ProjectContainer>,
     #[allow(dead_code)]
-    guard: Option<ExitGuard<TraceWriterGuard>>,
+    guard: Option<ExitGuard<TraceGuard>>,
 }
 
 #[napi(ts_return_type = "{ __napiType: \"Project\" }")]
@@ -208,51 +354,7 @@ pub async fn project_new(
 ) -> napi::Result<External<ProjectInstance>> {
     register();
 
-    let trace = std::env::var("NEXT_TURBOPACK_TRACING").ok();
-
-    let guard = if let Some(mut trace) = trace {
-        // Trace presets
-        match trace.as_str() {
-            "overview" => {
-                trace = TRACING_OVERVIEW_TARGETS.join(",");
-            }
-            "next" => {
-                trace = TRACING_NEXT_TARGETS.join(",");
-            }
-            "turbopack" => {
-                trace = TRACING_NEXT_TURBOPACK_TARGETS.join(",");
-            }
-            "turbo-tasks" => {
-                trace = TRACING_NEXT_TURBO_TASKS_TARGETS.join(",");
-            }
-            _ => {}
-        }
-
-        let subscriber = Registry::default();
-
-        let subscriber = subscriber.with(EnvFilter::builder().parse(trace).unwrap());
-        let dist_dir = options
-            .dist_dir
-            .as_ref()
-            .map_or_else(|| ".next".to_string(), |d| d.to_string());
-
-        let internal_dir = PathBuf::from(&options.project_path).join(dist_dir);
-        std::fs::create_dir_all(&internal_dir)
-            .context("Unable to create .next directory")
-            .unwrap();
-        let trace_file = internal_dir.join("trace.log");
-        let trace_writer = std::fs::File::create(trace_file).unwrap();
-        let (trace_writer, guard) = TraceWriter::new(trace_writer);
-        let subscriber = subscriber.with(RawTraceLayer::new(trace_writer));
-
-        let guard = ExitGuard::new(guard).unwrap();
-
-        subscriber.init();
-
-        Some(guard)
-    } else {
-        None
-    };
+    let guard = init_tracing(&options.project_path, options.dist_dir.as_deref())?;
 
     let turbo_tasks = TurboTasks::new(MemoryBackend::new(
         turbo_engine_options
@@ -260,6 +362,23 @@ pub async fn project_new(
             .map(|m| m as usize)
             .unwrap_or(usize::MAX),
     ));
+    if let Some(gc_interval) = turbo_engine_options
+        .gc_interval
+        .filter(|&gc_interval| gc_interval > 0)
+    {
+        let turbo_tasks = Arc::downgrade(&turbo_tasks);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(gc_interval as u64));
+            loop {
+                interval.tick().await;
+                // Stop collecting once the project has been dropped.
+                let Some(turbo_tasks) = turbo_tasks.upgrade() else {
+                    break;
+                };
+                turbo_tasks.backend().run_gc(false, &*turbo_tasks);
+            }
+        });
+    }
     let options = options.into();
     let container = turbo_tasks
         .run_once(async move {
@@ -297,6 +416,161 @@ pub async fn project_update(
     Ok(())
 }
 
+#[napi(object)]
+pub struct NapiBuildProgress {
+    /// The pathname of the route which has just been written.
+    pub pathname: String,
+    /// The number of endpoints written so far.
+    pub completed: u32,
+    /// The total number of endpoints to write.
+    pub total: u32,
+}
+
+/// How often a feature module, e.g. `next/image`, is imported in the build.
+#[napi(object)]
+pub struct NapiFeatureUsage {
+    pub feature_name: String,
+    pub invocation_count: u32,
+}
+
+#[napi(object)]
+pub struct NapiBuildResult {
+    /// The usage of the feature modules, reported as the
+    /// `NEXT_BUILD_FEATURE_USAGE` telemetry events like for webpack builds.
+    pub feature_usage: Vec<NapiFeatureUsage>,
+}
+
+/// Writes all endpoints of the project to disk, reporting a
+/// [NapiBuildProgress] to `progress` after each written endpoint. The project
+/// must have been created with `dev: false` for production output, i.e.
+/// minified chunks and production manifests.
+///
+/// Resolves with the issues and diagnostics of the whole build, and the usage
+/// of the feature modules aggregated from its diagnostics. An issue of a module
+/// shared by several endpoints is only reported once.
+///
+/// This only writes the endpoints: nothing is prerendered, so static pages,
+/// `404.html` and the prerender manifest still have to be generated by the
+/// caller.
+#[napi(ts_return_type = "Promise<TurbopackResult<NapiBuildResult>>")]
+pub fn project_build(
+    env: Env,
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    progress: JsFunction,
+) -> napi::Result<JsObject> {
+    let progress: ThreadsafeFunction<NapiBuildProgress> =
+        progress.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    let turbo_tasks = project.turbo_tasks.clone();
+    let container = project.container;
+
+    env.execute_tokio_future(
+        async move {
+            turbo_tasks
+                .run_once(async move {
+                    let entrypoints = container.entrypoints().strongly_consistent().await?;
+
+                    let mut endpoints = vec![
+                        ("_document".to_string(), entrypoints.pages_document_endpoint),
+                        ("_app".to_string(), entrypoints.pages_app_endpoint),
+                        ("_error".to_string(), entrypoints.pages_error_endpoint),
+                    ];
+                    if let Some(middleware) = &entrypoints.middleware {
+                        endpoints.push(("middleware".to_string(), middleware.endpoint));
+                    }
+                    if let Some(instrumentation) = &entrypoints.instrumentation {
+                        endpoints.push(("instrumentation".to_string(), instrumentation.node_js));
+                        endpoints.push(("edge-instrumentation".to_string(), instrumentation.edge));
+                    }
+                    if let Some(service_worker_endpoint) = entrypoints.service_worker_endpoint {
+                        endpoints.push(("service-worker".to_string(), service_worker_endpoint));
+                    }
+                    for (pathname, route) in entrypoints.routes.iter() {
+                        match *route {
+                            Route::Page {
+                                html_endpoint,
+                                data_endpoint: _,
+                            } => endpoints.push((pathname.clone(), html_endpoint)),
+                            Route::PageApi { endpoint } | Route::AppRoute { endpoint } => {
+                                endpoints.push((pathname.clone(), endpoint))
+                            }
+                            Route::AppPage {
+                                html_endpoint,
+                                rsc_endpoint,
+                            } => {
+                                endpoints.push((pathname.clone(), html_endpoint));
+                                endpoints.push((pathname.clone(), rsc_endpoint));
+                            }
+                            Route::Conflict => {
+                                bail!("Conflicting app and page route found for {}", pathname)
+                            }
+                        }
+                    }
+
+                    let total = endpoints.len() as u32;
+                    let mut written = vec![];
+                    for (completed, (pathname, endpoint)) in endpoints.into_iter().enumerate() {
+                        let write_to_disk = endpoint.write_to_disk();
+                        write_to_disk.strongly_consistent().await?;
+                        written.push(write_to_disk);
+
+                        progress.call(
+                            Ok(NapiBuildProgress {
+                                pathname,
+                                completed: completed as u32 + 1,
+                                total,
+                            }),
+                            ThreadsafeFunctionCallMode::NonBlocking,
+                        );
+                    }
+
+                    let issues = get_unique_issues(&written).await?;
+                    let diags = get_unique_diagnostics(&written).await?;
+
+                    Ok((issues, diags))
+                })
+                .await
+                .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+        },
+        |_, (issues, diags)| {
+            let feature_usage = module_feature_usage(diags.iter().map(|d| &**d))
+                .into_iter()
+                .map(|(feature_name, invocation_count)| NapiFeatureUsage {
+                    feature_name,
+                    invocation_count: invocation_count as u32,
+                })
+                .collect();
+            Ok(TurbopackResult {
+                result: NapiBuildResult { feature_usage },
+                issues: issues.iter().map(|i| NapiIssue::from(&**i)).collect(),
+                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+            })
+        },
+    )
+}
+
+/// Runs garbage collection of unused tasks. An `idle` collection is more
+/// aggressive and may evict tasks which are still likely to be reused. Returns
+/// whether there is more to collect.
+#[napi]
+pub fn project_run_gc(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    idle: bool,
+) -> bool {
+    let turbo_tasks = &project.turbo_tasks;
+    turbo_tasks.backend().run_gc(idle, &**turbo_tasks)
+}
+
+/// Stops the project: waits for in-flight tasks, including pending writes to
+/// disk, to finish and flushes the trace output. The project must not be
+/// used afterwards.
+#[napi]
+pub async fn project_shutdown(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] mut project: External<ProjectInstance>,
+) {
+    project.turbo_tasks.stop_and_wait().await;
+    drop(project.guard.take());
+}
+
 #[napi(object)]
 #[derive(Default)]
 struct NapiRoute {
@@ -385,10 +659,37 @@ impl NapiMiddleware {
         })
     }
 }
+
+#[napi(object)]
+struct NapiInstrumentation {
+    pub node_js: External<ExternalEndpoint>,
+    pub edge: External<ExternalEndpoint>,
+}
+
+impl NapiInstrumentation {
+    fn from_instrumentation(
+        value: &Instrumentation,
+        turbo_tasks: &Arc<TurboTasks<MemoryBackend>>,
+    ) -> Result<Self> {
+        Ok(NapiInstrumentation {
+            node_js: External::new(ExternalEndpoint(VcArc::new(
+                turbo_tasks.clone(),
+                value.node_js,
+            ))),
+            edge: External::new(ExternalEndpoint(VcArc::new(
+                turbo_tasks.clone(),
+                value.edge,
+            ))),
+        })
+    }
+}
+
 #[napi(object)]
 struct NapiEntrypoints {
     pub routes: Vec<NapiRoute>,
     pub middleware: Option<NapiMiddleware>,
+    pub instrumentation: Option<NapiInstrumentation>,
+    pub service_worker_endpoint: Option<External<ExternalEndpoint>>,
     pub pages_document_endpoint: External<ExternalEndpoint>,
     pub pages_app_endpoint: External<ExternalEndpoint>,
     pub pages_error_endpoint: External<ExternalEndpoint>,
@@ -407,6 +708,12 @@ pub fn project_entrypoints_subscribe(
         move || async move {
             let entrypoints_operation = container.entrypoints();
             let entrypoints = entrypoints_operation.strongly_consistent().await?;
+            // Keep the route types up to date as routes are added or removed.
+            container
+                .project()
+                .emit_route_types()
+                .strongly_consistent()
+                .await?;
 
             let issues = get_issues(entrypoints_operation).await?;
             let diags = get_diagnostics(entrypoints_operation).await?;
@@ -430,6 +737,14 @@ pub fn project_entrypoints_subscribe(
                         .as_ref()
                         .map(|m| NapiMiddleware::from_middleware(m, &turbo_tasks))
                         .transpose()?,
+                    instrumentation: entrypoints
+                        .instrumentation
+                        .as_ref()
+                        .map(|i| NapiInstrumentation::from_instrumentation(i, &turbo_tasks))
+                        .transpose()?,
+                    service_worker_endpoint: entrypoints.service_worker_endpoint.map(|endpoint| {
+                        External::new(ExternalEndpoint(VcArc::new(turbo_tasks.clone(), endpoint)))
+                    }),
                     pages_document_endpoint: External::new(ExternalEndpoint(VcArc::new(
                         turbo_tasks.clone(),
                         entrypoints.pages_document_endpoint,
@@ -453,6 +768,49 @@ pub fn project_entrypoints_subscribe(
     )
 }
 
+/// Streams the issues of the project, e.g. invalid configuration or route
+/// conflicts, as structured objects. Unlike the issues attached to other
+/// results, these can be restricted to a minimum severity and a set of paths.
+#[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
+pub fn project_issues_subscribe(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    filter: Option<NapiIssueFilter>,
+    func: JsFunction,
+) -> napi::Result<External<RootTask>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let container = project.container;
+    let filter = IssueFilter::try_from(filter.unwrap_or_default())
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    subscribe(
+        turbo_tasks,
+        func,
+        move || {
+            let filter = filter.clone();
+            async move {
+                let entrypoints_operation = container.entrypoints();
+                entrypoints_operation.strongly_consistent().await?;
+
+                let issues = filter.filter(get_issues(entrypoints_operation).await?);
+                let diags = get_diagnostics(entrypoints_operation).await?;
+
+                Ok((issues, diags))
+            }
+        },
+        |ctx| {
+            let (issues, diags) = ctx.value;
+
+            Ok(vec![TurbopackResult {
+                result: (),
+                issues: issues
+                    .iter()
+                    .map(|issue| NapiIssue::from(&**issue))
+                    .collect(),
+                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+            }])
+        },
+    )
+}
+
 #[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
 pub fn project_hmr_events(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, future::Future, ops::Deref, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    ops::Deref,
+    sync::Arc,
+};
 
 use anyhow::{anyhow, Context, Result};
 use napi::{
@@ -13,7 +18,10 @@ use turbopack_binding::{
     turbopack::core::{
         diagnostics::{Diagnostic, DiagnosticContextExt, PlainDiagnostic},
         error::PrettyPrintError,
-        issue::{IssueDescriptionExt, PlainIssue, PlainIssueSource, PlainSource},
+        issue::{
+            IssueDescriptionExt, IssueSeverity, PlainIssue, PlainIssueProcessingPathItem,
+            PlainIssueSource, PlainSource,
+        },
         source_pos::SourcePos,
     },
 };
@@ -55,15 +63,17 @@ pub fn serde_enum_to_string<T: Serialize>(value: &T) -> Result<String> {
 
 /// The root of our turbopack computation.
 pub struct RootTask {
-    #[allow(dead_code)]
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
-    #[allow(dead_code)]
     task_id: Option<TaskId>,
 }
 
 impl Drop for RootTask {
     fn drop(&mut self) {
-        // TODO stop the root task
+        // A subscription garbage collected on the JS side without being disposed
+        // would otherwise keep recomputing after every change.
+        if let Some(task) = self.task_id.take() {
+            self.turbo_tasks.dispose_root_task(task);
+        }
     }
 }
 
@@ -95,6 +105,75 @@ pub async fn get_diagnostics<T: Send>(source: Vc<T>) -> Result<Vec<ReadRef<Plain
         .await
 }
 
+/// Like [get_diagnostics], but for several sources. Diagnostics emitted for
+/// more than one of them, e.g. by a module shared between endpoints, are only
+/// returned once.
+pub async fn get_unique_diagnostics<T: Send>(
+    sources: &[Vc<T>],
+) -> Result<Vec<ReadRef<PlainDiagnostic>>> {
+    let mut seen = HashSet::new();
+    let mut diagnostics = vec![];
+    for source in sources {
+        let captured_diags = source.peek_diagnostics().await?;
+        for &diagnostic in captured_diags.diagnostics.iter() {
+            if seen.insert(diagnostic) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    diagnostics
+        .into_iter()
+        .map(|d| d.into_plain())
+        .try_join()
+        .await
+}
+
+/// Like [get_issues], but for several sources. Issues reported for more than
+/// one of them, e.g. by a module shared between endpoints, are only returned
+/// once. Their processing path differs between the sources, so issues are
+/// identified by everything else.
+pub async fn get_unique_issues<T: Send>(sources: &[Vc<T>]) -> Result<Vec<ReadRef<PlainIssue>>> {
+    let mut seen = HashSet::new();
+    let mut issues = vec![];
+    for &source in sources {
+        for issue in get_issues(source).await? {
+            if seen.insert(issue_identity(&issue)) {
+                issues.push(issue);
+            }
+        }
+    }
+    Ok(issues)
+}
+
+type IssueIdentity = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    Option<(String, (usize, usize), (usize, usize))>,
+);
+
+fn issue_identity(issue: &PlainIssue) -> IssueIdentity {
+    (
+        issue.severity.as_str().to_string(),
+        issue.file_path.clone(),
+        issue.category.clone(),
+        issue.title.clone(),
+        issue.description.clone(),
+        issue.detail.clone(),
+        issue.source.as_deref().map(|source| {
+            (
+                source.asset.ident.to_string(),
+                (source.start.line, source.start.column),
+                (source.end.line, source.end.column),
+            )
+        }),
+    )
+}
+
 #[napi(object)]
 pub struct NapiIssue {
     pub severity: String,
@@ -106,6 +185,9 @@ pub struct NapiIssue {
     pub source: Option<NapiIssueSource>,
     pub documentation_link: String,
     pub sub_issues: Vec<NapiIssue>,
+    /// The chain of modules that led to the issue, starting at the module
+    /// closest to it and ending at the entrypoint.
+    pub processing_path: Vec<NapiIssueProcessingPathItem>,
 }
 
 impl From<&PlainIssue> for NapiIssue {
@@ -124,10 +206,108 @@ impl From<&PlainIssue> for NapiIssue {
                 .iter()
                 .map(|issue| (&**issue).into())
                 .collect(),
+            processing_path: issue
+                .processing_path
+                .0
+                .iter()
+                .flatten()
+                .map(|item| (&**item).into())
+                .collect(),
+        }
+    }
+}
+
+#[napi(object)]
+pub struct NapiIssueProcessingPathItem {
+    pub file_path: Option<String>,
+    pub description: String,
+}
+
+impl From<&PlainIssueProcessingPathItem> for NapiIssueProcessingPathItem {
+    fn from(item: &PlainIssueProcessingPathItem) -> Self {
+        Self {
+            file_path: item.context.as_ref().map(|context| context.path.clone()),
+            description: (*item.description).clone(),
+        }
+    }
+}
+
+/// Restricts which issues are reported to the JS side.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct NapiIssueFilter {
+    /// Only report issues at least as severe as this one, e.g. `"warning"`
+    /// reports warnings, errors, fatal errors and bugs.
+    pub min_severity: Option<String>,
+    /// Only report issues whose file path starts with one of these prefixes.
+    pub paths: Option<Vec<String>>,
+}
+
+/// A [NapiIssueFilter] with its severity parsed, so an invalid filter is
+/// rejected once when subscribing rather than on every update.
+#[derive(Clone, Debug, Default)]
+pub struct IssueFilter {
+    min_severity: Option<IssueSeverity>,
+    paths: Option<Vec<String>>,
+}
+
+impl TryFrom<NapiIssueFilter> for IssueFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: NapiIssueFilter) -> Result<Self> {
+        Ok(Self {
+            min_severity: filter
+                .min_severity
+                .as_deref()
+                .map(parse_issue_severity)
+                .transpose()?,
+            paths: filter.paths,
+        })
+    }
+}
+
+impl IssueFilter {
+    pub fn matches(&self, issue: &PlainIssue) -> bool {
+        if let Some(min_severity) = self.min_severity {
+            if issue.severity > min_severity {
+                return false;
+            }
         }
+        if let Some(paths) = &self.paths {
+            if !paths
+                .iter()
+                .any(|path| issue.file_path.starts_with(path.as_str()))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn filter(&self, issues: Vec<ReadRef<PlainIssue>>) -> Vec<ReadRef<PlainIssue>> {
+        issues
+            .into_iter()
+            .filter(|issue| self.matches(issue))
+            .collect()
     }
 }
 
+fn parse_issue_severity(severity: &str) -> Result<IssueSeverity> {
+    [
+        IssueSeverity::Bug,
+        IssueSeverity::Fatal,
+        IssueSeverity::Error,
+        IssueSeverity::Warning,
+        IssueSeverity::Hint,
+        IssueSeverity::Note,
+        IssueSeverity::Suggestion,
+        IssueSeverity::Info,
+    ]
+    .into_iter()
+    .find(|s| s.as_str() == severity)
+    .with_context(|| format!("invalid issue severity: {}", severity))
+}
+
 #[napi(object)]
 pub struct NapiIssueSource {
     pub source: NapiSource,
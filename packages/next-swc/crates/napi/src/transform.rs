@@ -28,10 +28,11 @@ DEALINGS IN THE SOFTWARE.
 
 use std::{
     cell::RefCell,
+    collections::VecDeque,
     fs::read_to_string,
     panic::{catch_unwind, AssertUnwindSafe},
     rc::Rc,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use anyhow::{anyhow, bail, Context as _};
@@ -40,7 +41,10 @@ use napi::bindgen_prelude::*;
 use next_swc::{custom_before_pass, TransformOptions};
 use turbopack_binding::swc::core::{
     base::{try_with_handler, Compiler, TransformOutput},
-    common::{comments::SingleThreadedComments, errors::ColorConfig, FileName, Mark, GLOBALS},
+    common::{
+        comments::SingleThreadedComments, errors::ColorConfig, FileName, FilePathMapping, Mark,
+        SourceMap, GLOBALS,
+    },
     ecma::transforms::base::pass::noop,
 };
 
@@ -66,95 +70,103 @@ fn skip_filename() -> bool {
     cfg!(debug_assertions)
 }
 
+fn transform_input(
+    c: &Arc<Compiler>,
+    input: &Input,
+    options: TransformOptions,
+) -> napi::Result<(TransformOutput, FxHashSet<String>)> {
+    GLOBALS.set(&Default::default(), || {
+        let eliminated_packages: Rc<RefCell<fxhash::FxHashSet<String>>> = Default::default();
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            try_with_handler(
+                c.cm.clone(),
+                turbopack_binding::swc::core::base::HandlerOpts {
+                    color: ColorConfig::Always,
+                    skip_filename: skip_filename(),
+                },
+                |handler| {
+                    c.run(|| {
+                        let fm = match input {
+                            Input::Source { src } => {
+                                let filename = if options.swc.filename.is_empty() {
+                                    FileName::Anon
+                                } else {
+                                    FileName::Real(options.swc.filename.clone().into())
+                                };
+
+                                c.cm.new_source_file(filename, src.to_string())
+                            }
+                            Input::FromFilename => {
+                                let filename = &options.swc.filename;
+                                if filename.is_empty() {
+                                    bail!("no filename is provided via options");
+                                }
+
+                                c.cm.new_source_file(
+                                    FileName::Real(filename.into()),
+                                    read_to_string(filename).with_context(|| {
+                                        format!("Failed to read source code from {}", filename)
+                                    })?,
+                                )
+                            }
+                        };
+                        let unresolved_mark = Mark::new();
+                        let mut options = options.patch(&fm);
+                        options.swc.unresolved_mark = Some(unresolved_mark);
+
+                        let cm = c.cm.clone();
+                        let file = fm.clone();
+
+                        let comments = SingleThreadedComments::default();
+                        c.process_js_with_custom_pass(
+                            fm,
+                            None,
+                            handler,
+                            &options.swc,
+                            comments.clone(),
+                            |_| {
+                                custom_before_pass(
+                                    cm,
+                                    file,
+                                    &options,
+                                    comments.clone(),
+                                    eliminated_packages.clone(),
+                                    unresolved_mark,
+                                )
+                            },
+                            |_| noop(),
+                        )
+                    })
+                },
+            )
+        }))
+        .map_err(|err| {
+            if let Some(s) = err.downcast_ref::<String>() {
+                anyhow!("failed to process {}", s)
+            } else {
+                anyhow!("failed to process")
+            }
+        });
+
+        match res {
+            Ok(res) => res
+                .map(|o| (o, eliminated_packages.replace(Default::default())))
+                .convert_err(),
+            Err(err) => Err(napi::Error::new(
+                Status::GenericFailure,
+                format!("{:?}", err),
+            )),
+        }
+    })
+}
+
 impl Task for TransformTask {
     type Output = (TransformOutput, FxHashSet<String>);
     type JsValue = Object;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        GLOBALS.set(&Default::default(), || {
-            let eliminated_packages: Rc<RefCell<fxhash::FxHashSet<String>>> = Default::default();
-            let res = catch_unwind(AssertUnwindSafe(|| {
-                try_with_handler(
-                    self.c.cm.clone(),
-                    turbopack_binding::swc::core::base::HandlerOpts {
-                        color: ColorConfig::Always,
-                        skip_filename: skip_filename(),
-                    },
-                    |handler| {
-                        self.c.run(|| {
-                            let options: TransformOptions = serde_json::from_slice(&self.options)?;
-                            let fm = match &self.input {
-                                Input::Source { src } => {
-                                    let filename = if options.swc.filename.is_empty() {
-                                        FileName::Anon
-                                    } else {
-                                        FileName::Real(options.swc.filename.clone().into())
-                                    };
-
-                                    self.c.cm.new_source_file(filename, src.to_string())
-                                }
-                                Input::FromFilename => {
-                                    let filename = &options.swc.filename;
-                                    if filename.is_empty() {
-                                        bail!("no filename is provided via options");
-                                    }
-
-                                    self.c.cm.new_source_file(
-                                        FileName::Real(filename.into()),
-                                        read_to_string(filename).with_context(|| {
-                                            format!("Failed to read source code from {}", filename)
-                                        })?,
-                                    )
-                                }
-                            };
-                            let unresolved_mark = Mark::new();
-                            let mut options = options.patch(&fm);
-                            options.swc.unresolved_mark = Some(unresolved_mark);
-
-                            let cm = self.c.cm.clone();
-                            let file = fm.clone();
-
-                            let comments = SingleThreadedComments::default();
-                            self.c.process_js_with_custom_pass(
-                                fm,
-                                None,
-                                handler,
-                                &options.swc,
-                                comments.clone(),
-                                |_| {
-                                    custom_before_pass(
-                                        cm,
-                                        file,
-                                        &options,
-                                        comments.clone(),
-                                        eliminated_packages.clone(),
-                                        unresolved_mark,
-                                    )
-                                },
-                                |_| noop(),
-                            )
-                        })
-                    },
-                )
-            }))
-            .map_err(|err| {
-                if let Some(s) = err.downcast_ref::<String>() {
-                    anyhow!("failed to process {}", s)
-                } else {
-                    anyhow!("failed to process")
-                }
-            });
-
-            match res {
-                Ok(res) => res
-                    .map(|o| (o, eliminated_packages.replace(Default::default())))
-                    .convert_err(),
-                Err(err) => Err(napi::Error::new(
-                    Status::GenericFailure,
-                    format!("{:?}", err),
-                )),
-            }
-        })
+        let options: TransformOptions = serde_json::from_slice(&self.options)?;
+        transform_input(&self.c, &self.input, options)
     }
 
     fn resolve(
@@ -208,6 +220,138 @@ pub fn transform_sync(
     let output = task.compute()?;
     task.resolve(env, output)
 }
+
+/// The number of outputs a [TransformCache] keeps. Editors and test watchers
+/// often go back to a previous version of a file, e.g. on undo.
+const TRANSFORM_CACHE_SIZE: usize = 16;
+
+/// The number of source files after which the compiler of a [TransformCache]
+/// is replaced. Source maps never drop their files, so the versions of the
+/// file would accumulate otherwise.
+const TRANSFORM_CACHE_MAX_FILES: usize = 256;
+
+/// A cache of the outputs of `transformCachedSync` for a single file. Repeated
+/// transforms, e.g. from editor integrations or test watchers, reuse the
+/// deserialized options and the outputs of recent versions of the file.
+/// Versions which aren't cached are transformed from scratch, as nothing of a
+/// previous transform is reused for a different source.
+///
+/// The file is transformed with a compiler of the cache instead of the shared
+/// one, so its versions are dropped with the cache.
+pub struct TransformCache {
+    options: TransformOptions,
+    inner: Mutex<TransformCacheInner>,
+}
+
+struct TransformCacheInner {
+    compiler: Arc<Compiler>,
+    files: usize,
+    /// The most recently used output is last.
+    outputs: VecDeque<CachedTransform>,
+}
+
+struct CachedTransform {
+    hash: u64,
+    src: String,
+    code: String,
+    map: Option<String>,
+    eliminated_packages: FxHashSet<String>,
+}
+
+impl TransformCache {
+    fn new(options: TransformOptions) -> Self {
+        TransformCache {
+            options,
+            inner: Mutex::new(TransformCacheInner {
+                compiler: new_compiler(),
+                files: 0,
+                outputs: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn transform(&self, src: String) -> napi::Result<(TransformOutput, FxHashSet<String>)> {
+        let hash = fxhash::hash64(&src);
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(index) = inner
+            .outputs
+            .iter()
+            .position(|output| output.hash == hash && output.src == src)
+        {
+            let cached = inner.outputs.remove(index).unwrap();
+            let output = (
+                TransformOutput {
+                    code: cached.code.clone(),
+                    map: cached.map.clone(),
+                },
+                cached.eliminated_packages.clone(),
+            );
+            inner.outputs.push_back(cached);
+            return Ok(output);
+        }
+
+        if inner.files >= TRANSFORM_CACHE_MAX_FILES {
+            inner.compiler = new_compiler();
+            inner.files = 0;
+        }
+        inner.files += 1;
+        let input = Input::Source { src: src.clone() };
+        let (output, eliminated_packages) =
+            transform_input(&inner.compiler, &input, self.options.clone())?;
+
+        if inner.outputs.len() >= TRANSFORM_CACHE_SIZE {
+            inner.outputs.pop_front();
+        }
+        inner.outputs.push_back(CachedTransform {
+            hash,
+            src,
+            code: output.code.clone(),
+            map: output.map.clone(),
+            eliminated_packages: eliminated_packages.clone(),
+        });
+        Ok((output, eliminated_packages))
+    }
+}
+
+fn new_compiler() -> Arc<Compiler> {
+    Arc::new(Compiler::new(Arc::new(SourceMap::new(
+        FilePathMapping::empty(),
+    ))))
+}
+
+/// Transforms `src` like `transformSync`. `cache` is the cache returned by a
+/// previous call for the same file, in which case `options` can be omitted to
+/// reuse the previous options. The returned object contains a `cache` to pass
+/// to the next call.
+#[napi(
+    ts_return_type = "{ code: string, map?: string, eliminatedPackages?: string, cache: { \
+                      __napiType: \"TransformCache\" } }"
+)]
+pub fn transform_cached_sync(
+    env: Env,
+    #[napi(ts_arg_type = "{ __napiType: \"TransformCache\" } | undefined | null")] cache: Option<
+        External<Arc<TransformCache>>,
+    >,
+    src: String,
+    options: Option<Buffer>,
+) -> napi::Result<Object> {
+    let cache = match (cache, options) {
+        (_, Some(options)) => Arc::new(TransformCache::new(serde_json::from_slice(&options)?)),
+        (Some(cache), None) => (*cache).clone(),
+        (None, None) => {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                "transformCachedSync requires either options or a previous cache",
+            ))
+        }
+    };
+
+    let (output, eliminated_packages) = cache.transform(src)?;
+    let mut js_output = complete_output(&env, output, eliminated_packages)?;
+    js_output.set_named_property("cache", External::new(cache))?;
+    Ok(js_output)
+}
+
 #[test]
 fn test_deser() {
     const JSON_STR: &str = r#"{"jsc":{"parser":{"syntax":"ecmascript","dynamicImport":true,"jsx":true},"transform":{"react":{"runtime":"automatic","pragma":"React.createElement","pragmaFrag":"React.Fragment","throwIfNamespace":true,"development":false,"useBuiltins":true}},"target":"es5"},"filename":"/Users/timneutkens/projects/next.js/packages/next/dist/client/next.js","sourceMaps":false,"sourceFileName":"/Users/timneutkens/projects/next.js/packages/next/dist/client/next.js"}"#;
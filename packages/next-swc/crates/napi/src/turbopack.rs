@@ -10,9 +10,15 @@ use next_build::{
     build_options::{BuildContext, DefineEnv},
     BuildOptions as NextBuildOptions,
 };
-use next_core::next_config::{Rewrite, Rewrites, RouteHas};
+use next_core::{
+    next_config::{Rewrite, Rewrites, RouteHas},
+    next_manifests::PrerenderManifestPreview,
+};
 
-use crate::{next_api::project::NapiDefineEnv, util::MapErr};
+use crate::{
+    next_api::project::{init_tracing, NapiDefineEnv, NapiFeatureUsage},
+    util::MapErr,
+};
 
 #[napi(object, object_to_js = false)]
 #[derive(Debug)]
@@ -34,6 +40,9 @@ pub struct NextBuildContext {
 
     /// The rewrites, as computed by Next.js.
     pub rewrites: Option<NapiRewrites>,
+
+    /// The keys of the preview and draft modes generated by Next.js.
+    pub preview_props: Option<NapiPreviewProps>,
     // TODO(alexkirsz) These are detected directly by Turbopack for now.
     // pub app_dir: Option<String>,
     // pub pages_dir: Option<String>,
@@ -64,6 +73,10 @@ impl TryFrom<NextBuildContext> for NextBuildOptions {
                     .rewrites
                     .context("NextBuildContext must provide rewrites")?
                     .into(),
+                preview_props: value
+                    .preview_props
+                    .context("NextBuildContext must provide preview props")?
+                    .into(),
             }),
             define_env: value.define_env.into(),
         })
@@ -92,6 +105,25 @@ impl From<NapiDefineEnv> for DefineEnv {
     }
 }
 
+/// Keep in sync with [`next_core::next_manifests::PrerenderManifestPreview`]
+#[napi(object, object_to_js = false)]
+#[derive(Debug)]
+pub struct NapiPreviewProps {
+    pub preview_mode_id: String,
+    pub preview_mode_signing_key: String,
+    pub preview_mode_encryption_key: String,
+}
+
+impl From<NapiPreviewProps> for PrerenderManifestPreview {
+    fn from(val: NapiPreviewProps) -> Self {
+        PrerenderManifestPreview {
+            preview_mode_id: val.preview_mode_id,
+            preview_mode_signing_key: val.preview_mode_signing_key,
+            preview_mode_encryption_key: val.preview_mode_encryption_key,
+        }
+    }
+}
+
 /// Keep in sync with [`next_core::next_config::Rewrites`]
 #[napi(object, object_to_js = false)]
 #[derive(Debug)]
@@ -148,6 +180,7 @@ impl From<NapiRewrite> for Rewrite {
             missing: val
                 .missing
                 .map(|missing| missing.into_iter().map(|missing| missing.into()).collect()),
+            regex: None,
         }
     }
 }
@@ -202,9 +235,20 @@ impl From<NapiRouteHas> for RouteHas {
     }
 }
 
+/// Resolves with the usage of the feature modules in the build.
 #[napi]
-pub async fn next_build(ctx: NextBuildContext) -> napi::Result<()> {
-    turbo_next_build(ctx.try_into()?).await.convert_err()
+pub async fn next_build(ctx: NextBuildContext) -> napi::Result<Vec<NapiFeatureUsage>> {
+    // The trace, e.g. of `next build --experimental-turbo-profile`, is flushed
+    // once the build is done.
+    let _guard = init_tracing(ctx.dir.as_deref().unwrap_or("."), ctx.dist_dir.as_deref())?;
+    let feature_usage = turbo_next_build(ctx.try_into()?).await.convert_err()?;
+    Ok(feature_usage
+        .into_iter()
+        .map(|(feature_name, invocation_count)| NapiFeatureUsage {
+            feature_name,
+            invocation_count: invocation_count as u32,
+        })
+        .collect())
 }
 
 #[napi]
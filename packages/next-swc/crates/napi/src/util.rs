@@ -49,6 +49,61 @@ pub fn get_target_triple() -> String {
     crate::build::BUILD_TARGET.to_string()
 }
 
+#[napi(object)]
+pub struct NapiBinaryMetadata {
+    /// The target triple the binary was compiled for.
+    pub target: String,
+    /// The version of the next-swc package the binary was built from.
+    pub version: String,
+    /// The rustc version used to compile the binary.
+    pub rustc_version: String,
+    /// The global allocator compiled into the binary.
+    pub allocator: String,
+    /// Enabled cargo features which affect the behavior of the binary.
+    pub features: Vec<String>,
+}
+
+/// Returns the provenance of the native binary, so `next info` and crash
+/// reports can include exactly which build is running.
+#[napi]
+pub fn get_binary_metadata() -> NapiBinaryMetadata {
+    let allocator = if cfg!(feature = "__internal_dhat-heap") {
+        "dhat"
+    } else if cfg!(any(
+        all(
+            target_os = "linux",
+            target_env = "musl",
+            target_arch = "aarch64"
+        ),
+        feature = "__internal_dhat-ad-hoc"
+    )) {
+        "system"
+    } else {
+        "turbo-malloc"
+    };
+
+    let features = [
+        ("plugin", cfg!(feature = "plugin")),
+        ("native-tls", cfg!(feature = "native-tls")),
+        ("rustls-tls", cfg!(feature = "rustls-tls")),
+        ("image-webp", cfg!(feature = "image-webp")),
+        ("image-avif", cfg!(feature = "image-avif")),
+        ("sentry", cfg!(feature = "sentry")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name.to_string())
+    .collect();
+
+    NapiBinaryMetadata {
+        target: crate::build::BUILD_TARGET.to_string(),
+        version: PACKAGE_VERSION.to_string(),
+        rustc_version: crate::build::RUST_VERSION.to_string(),
+        allocator: allocator.to_string(),
+        features,
+    }
+}
+
 pub trait MapErr<T>: Into<Result<T, anyhow::Error>> {
     fn convert_err(self) -> napi::Result<T> {
         self.into()
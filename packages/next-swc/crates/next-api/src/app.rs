@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
 use next_core::{
     app_structure::{
         get_entrypoints, Entrypoint as AppEntrypoint, Entrypoints as AppEntrypoints, LoaderTree,
@@ -31,9 +32,11 @@ use next_core::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    nft_json::NftJsonAsset,
     util::{get_asset_prefix_from_pathname, NextRuntime},
 };
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use turbo_tasks::{trace::TraceRawVcs, Completion, TryFlatJoinIterExt, TryJoinIterExt, Value, Vc};
 use turbopack_binding::{
     turbo::{
@@ -61,8 +64,10 @@ use crate::{
         collect_chunk_group, collect_evaluated_chunk_group, collect_next_dynamic_imports,
         DynamicImportedChunks,
     },
+    edge_function_size::check_edge_function_size,
+    edge_wasm::edge_wasm_assets,
     project::Project,
-    route::{Endpoint, Route, Routes, WrittenEndpoint},
+    route::{Endpoint, Route, RouteFiles, Routes, WrittenEndpoint},
     server_actions::create_server_actions_manifest,
     server_paths::all_server_paths,
 };
@@ -130,7 +135,11 @@ impl AppProject {
 
     #[turbo_tasks::function]
     fn app_entrypoints(&self) -> Vc<AppEntrypoints> {
-        get_entrypoints(self.app_dir, self.project.next_config().page_extensions())
+        get_entrypoints(
+            self.app_dir,
+            self.project.next_config().page_extensions(),
+            self.mode,
+        )
     }
 
     #[turbo_tasks::function]
@@ -394,6 +403,47 @@ impl AppProject {
                 .collect(),
         ))
     }
+
+    /// The files the routes are created from, used to report conflicts with
+    /// pages routes.
+    #[turbo_tasks::function]
+    pub async fn route_files(self: Vc<Self>) -> Result<Vc<RouteFiles>> {
+        let mut files = IndexMap::new();
+        for (pathname, app_entrypoint) in self.app_entrypoints().await?.iter() {
+            let file = match app_entrypoint {
+                AppEntrypoint::AppPage { loader_tree, .. } => {
+                    loader_tree_page_file(*loader_tree).await?
+                }
+                AppEntrypoint::AppRoute { path, .. } => Some(*path),
+                AppEntrypoint::AppMetadata { metadata, .. } => Some(metadata.into_path()),
+            };
+            if let Some(file) = file {
+                files.insert(pathname.to_string(), file);
+            }
+        }
+        Ok(Vc::cell(files))
+    }
+}
+
+/// Returns the `page` file of a loader tree, preferring the `children` slot
+/// over other parallel routes.
+async fn loader_tree_page_file(loader_tree: Vc<LoaderTree>) -> Result<Option<Vc<FileSystemPath>>> {
+    let mut queue = vec![loader_tree];
+    while let Some(loader_tree) = queue.pop() {
+        let loader_tree = loader_tree.await?;
+        if loader_tree.segment == "__PAGE__" {
+            return Ok(loader_tree.components.await?.page);
+        }
+        queue.extend(
+            loader_tree
+                .parallel_routes
+                .iter()
+                .filter(|(key, _)| key.as_str() != "children")
+                .map(|(_, tree)| *tree),
+        );
+        queue.extend(loader_tree.parallel_routes.get("children").copied());
+    }
+    Ok(None)
 }
 
 #[turbo_tasks::function]
@@ -480,15 +530,17 @@ struct AppEndpoint {
 #[turbo_tasks::value_impl]
 impl AppEndpoint {
     #[turbo_tasks::function]
-    fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Vc<AppEntry> {
-        get_app_page_entry(
+    async fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Result<Vc<AppEntry>> {
+        Ok(get_app_page_entry(
             self.app_project.rsc_module_context(),
             self.app_project.edge_rsc_module_context(),
             loader_tree,
             self.page.clone(),
             self.app_project.project().project_path(),
             self.app_project.project().next_config(),
-        )
+            self.app_project.project().build_id(),
+            *self.app_project.project().mode().await?,
+        ))
     }
 
     #[turbo_tasks::function]
@@ -699,6 +751,11 @@ impl AppEndpoint {
                     .project()
                     .next_config()
                     .computed_asset_prefix(),
+                this.app_project.project().next_config().cross_origin(),
+                this.app_project
+                    .project()
+                    .next_config()
+                    .deployment_id_query(),
                 runtime,
             );
             server_assets.push(entry_manifest);
@@ -757,7 +814,7 @@ impl AppEndpoint {
             let dynamic_import_entries = &*dynamic_import_entries.await?;
 
             let mut output = vec![];
-            let mut loadable_manifest: HashMap<String, LoadableManifest> = Default::default();
+            let mut loadable_manifest: BTreeMap<String, LoadableManifest> = Default::default();
 
             for (origin, dynamic_imports) in dynamic_import_entries.into_iter() {
                 let origin_path = &*origin.ident().path().await?;
@@ -837,10 +894,18 @@ impl AppEndpoint {
                 server_assets.push(manifest);
                 evaluatable_assets.push(loader);
 
-                let files = chunking_context.evaluated_chunk_group(
+                let files = this.app_project.project().edge_evaluated_chunk_group(
+                    app_entry.original_name.clone(),
                     app_entry.rsc_entry.ident(),
                     Vc::cell(evaluatable_assets.clone()),
                 );
+                check_edge_function_size(
+                    this.app_project.project(),
+                    app_entry.rsc_entry.ident().path(),
+                    &app_entry.original_name,
+                    files,
+                )
+                .await?;
                 server_assets.extend(files.await?.iter().copied());
 
                 // the next-edge-ssr-loader templates expect the manifests to be stored in
@@ -892,6 +957,10 @@ impl AppEndpoint {
 
                 file_paths_from_root.extend(rsc_paths_from_root);
 
+                let (wasm, wasm_assets) =
+                    edge_wasm_assets(node_root, Vc::upcast(app_entry.rsc_entry)).await?;
+                server_assets.extend(wasm_assets);
+
                 let entry_file = "app-edge-has-no-entrypoint".to_string();
 
                 // create middleware manifest
@@ -913,6 +982,7 @@ impl AppEndpoint {
                         .clone()
                         .map(Regions::Multiple),
                     matchers: vec![matchers],
+                    wasm: Some(wasm),
                     ..Default::default()
                 };
                 let middleware_manifest_v2 = MiddlewaresManifestV2 {
@@ -921,6 +991,7 @@ impl AppEndpoint {
                     functions: [(app_entry.original_name.clone(), edge_function_definition)]
                         .into_iter()
                         .collect(),
+                    ..Default::default()
                 };
                 let manifest_path_prefix = get_asset_prefix_from_pathname(&app_entry.pathname);
                 let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
@@ -1002,6 +1073,17 @@ impl AppEndpoint {
                     );
                 server_assets.push(rsc_chunk);
 
+                if *this.app_project.project().mode().await? == NextMode::Build {
+                    let project = this.app_project.project();
+                    server_assets.push(Vc::upcast(NftJsonAsset::new(
+                        rsc_chunk,
+                        Vc::upcast(app_entry.rsc_entry),
+                        project.project_path(),
+                        node_root,
+                        project.server_compile_time_info(),
+                    )));
+                }
+
                 let app_paths_manifest_output = create_app_paths_manifest(
                     node_root,
                     ty,
@@ -1092,10 +1174,19 @@ impl Endpoint for AppEndpoint {
         let node_root_ref = &node_root.await?;
 
         let node_root = this.app_project.project().node_root();
-        this.app_project
-            .project()
-            .emit_all_output_assets(Vc::cell(output_assets))
+
+        // Resolving the output assets compiles and chunks the route.
+        async { output_assets.await }
+            .instrument(tracing::info_span!("compile-path", path = %this.page))
             .await?;
+        async {
+            this.app_project
+                .project()
+                .emit_all_output_assets(Vc::cell(output_assets))
+                .await
+        }
+        .instrument(tracing::info_span!("emit-output", path = %this.page))
+        .await?;
 
         let server_paths = all_server_paths(output_assets, node_root)
             .await?
@@ -0,0 +1,229 @@
+use anyhow::Result;
+use indexmap::IndexSet;
+use next_core::{mode::NextMode, util::NextSourceConfig};
+use turbo_tasks::{
+    graph::{GraphTraversal, NonDeterministic},
+    Vc,
+};
+use turbopack_binding::{
+    swc::core::ecma::{
+        ast::{CallExpr, Callee, Expr, NewExpr},
+        visit::{Visit, VisitWith},
+    },
+    turbo::tasks_fs::{glob::Glob, FileSystemPath},
+    turbopack::{
+        core::{
+            issue::{Issue, IssueExt, IssueSeverity},
+            module::Module,
+            reference::primary_referenced_modules,
+        },
+        ecmascript::{
+            analyzer::{graph::EvalContext, JsValue},
+            parse::ParseResult,
+            EcmascriptModuleAsset,
+        },
+    },
+};
+
+use crate::project::Project;
+
+/// Packages known to contain `eval` or `new Function` that never runs, which
+/// are safe to use in the edge runtime.
+/// These are the `known-edge-safe-packages.json` of the webpack build, plus
+/// `regenerator-runtime`, which it skips too.
+const KNOWN_SAFE_DYNAMIC_PACKAGES: &[&str] = &["function-bind", "regenerator-runtime"];
+
+/// Returns the dynamic code evaluation APIs (`eval`, `new Function`, ...)
+/// called by a module.
+#[turbo_tasks::function]
+async fn dynamic_code_evaluation_apis(module: Vc<Box<dyn Module>>) -> Result<Vc<Vec<String>>> {
+    let Some(ecmascript_asset) =
+        Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+    // The module might not be parse-able, but this is reported separately.
+    let ParseResult::Ok {
+        program,
+        eval_context,
+        ..
+    } = &*ecmascript_asset.parse().await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let mut visitor = DynamicCodeEvaluationVisitor {
+        eval_context,
+        apis: IndexSet::new(),
+    };
+    program.visit_with(&mut visitor);
+    Ok(Vc::cell(visitor.apis.into_iter().collect()))
+}
+
+/// Finds calls to the global `eval`, `Function`, `WebAssembly.compile` and
+/// `WebAssembly.instantiate`, with or without `global.` or `globalThis.`.
+struct DynamicCodeEvaluationVisitor<'a> {
+    eval_context: &'a EvalContext,
+    apis: IndexSet<String>,
+}
+
+impl DynamicCodeEvaluationVisitor<'_> {
+    fn check_callee(&mut self, callee: &Expr) {
+        let api = match self.eval_context.eval(callee) {
+            JsValue::FreeVar(name) => name.to_string(),
+            JsValue::Member(_, obj, prop) => {
+                let (JsValue::FreeVar(obj), Some(prop)) = (&*obj, prop.as_str()) else {
+                    return;
+                };
+                match &**obj {
+                    "global" | "globalThis" => prop.to_string(),
+                    "WebAssembly" => format!("WebAssembly.{prop}"),
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        if matches!(
+            &*api,
+            "eval" | "Function" | "WebAssembly.compile" | "WebAssembly.instantiate"
+        ) {
+            self.apis.insert(api);
+        }
+    }
+}
+
+impl Visit for DynamicCodeEvaluationVisitor<'_> {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            self.check_callee(callee);
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, new: &NewExpr) {
+        self.check_callee(&new.callee);
+        new.visit_children_with(self);
+    }
+}
+
+async fn get_referenced_modules(
+    parent: Vc<Box<dyn Module>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn Module>>> + Send> {
+    primary_referenced_modules(parent)
+        .await
+        .map(|modules| modules.clone_value().into_iter())
+}
+
+/// Reports an error for every module reachable from `entry` that evaluates
+/// code dynamically in `next build`, unless its path relative to the project
+/// matches one of the `unstable_allowDynamic` globs of the entry's config.
+pub(crate) async fn check_dynamic_code_evaluation(
+    project: Vc<Project>,
+    entry: Vc<Box<dyn Module>>,
+    config: Vc<NextSourceConfig>,
+) -> Result<()> {
+    if *project.mode().await? != NextMode::Build {
+        return Ok(());
+    }
+
+    let allowed = config
+        .await?
+        .unstable_allow_dynamic
+        .iter()
+        .flatten()
+        // The globs are relative to the project, which turbopack's paths
+        // don't start with.
+        .map(|glob| Glob::new(glob.trim_start_matches('/').to_string()))
+        .collect::<Vec<_>>();
+
+    let modules = NonDeterministic::new()
+        .skip_duplicates()
+        .visit([entry], get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner();
+
+    let project_path = project.project_path().await?;
+    for module in modules {
+        let apis = dynamic_code_evaluation_apis(module).await?;
+        if apis.is_empty() {
+            continue;
+        }
+
+        let path = module.ident().path();
+        let path_value = path.await?;
+        if KNOWN_SAFE_DYNAMIC_PACKAGES.iter().any(|package| {
+            path_value
+                .path
+                .contains(&format!("node_modules/{package}/"))
+        }) {
+            continue;
+        }
+        let relative_path = project_path
+            .get_path_to(&path_value)
+            .unwrap_or(path_value.path.as_str());
+        let mut is_allowed = false;
+        for glob in &allowed {
+            if glob.await?.execute(relative_path) {
+                is_allowed = true;
+                break;
+            }
+        }
+        if !is_allowed {
+            DynamicCodeEvaluationIssue {
+                path,
+                apis: apis.clone_value(),
+            }
+            .cell()
+            .emit();
+        }
+    }
+
+    Ok(())
+}
+
+#[turbo_tasks::value(shared)]
+struct DynamicCodeEvaluationIssue {
+    path: Vc<FileSystemPath>,
+    apis: Vec<String>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for DynamicCodeEvaluationIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn title(&self) -> Result<Vc<String>> {
+        Ok(Vc::cell(format!(
+            "Dynamic Code Evaluation (e. g. 'eval', 'new Function', 'WebAssembly.compile') not \
+             allowed in Edge Runtime, used by {}",
+            self.path.await?.path
+        )))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The module calls {}. If this code never runs in the edge runtime, add the module to \
+             `unstable_allowDynamic` in the middleware config.\nLearn More: https://nextjs.org/docs/messages/edge-dynamic-code-evaluation",
+            self.apis
+                .iter()
+                .map(|api| format!("`{api}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
@@ -0,0 +1,116 @@
+use std::io::Write;
+
+use anyhow::Result;
+use flate2::{write::GzEncoder, Compression};
+use next_core::mode::NextMode;
+use turbo_tasks::{TryJoinIterExt, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPath},
+    turbopack::core::{
+        asset::{Asset, AssetContent},
+        issue::{Issue, IssueExt, IssueSeverity},
+        output::{OutputAsset, OutputAssets},
+    },
+};
+
+use crate::project::Project;
+
+/// Returns the gzipped size of the JavaScript files of an edge function,
+/// which is what the platform limit applies to.
+#[turbo_tasks::function]
+async fn edge_function_compressed_size(files: Vc<OutputAssets>) -> Result<Vc<u64>> {
+    let sizes = files
+        .await?
+        .iter()
+        .map(|&file| async move {
+            if file.ident().path().await?.extension_ref() != Some("js") {
+                return Ok(0);
+            }
+            let AssetContent::File(content) = *file.content().await? else {
+                return Ok(0);
+            };
+            let FileContent::Content(content) = &*content.await? else {
+                return Ok(0);
+            };
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&content.content().to_bytes()?)?;
+            Ok(encoder.finish()?.len() as u64)
+        })
+        .try_join()
+        .await?;
+    Ok(Vc::cell(sizes.into_iter().sum()))
+}
+
+/// Reports an error when an edge function exceeds
+/// `experimental.edgeFunctionSizeLimit` in `next build`, so it fails the build
+/// instead of the deployment.
+pub(crate) async fn check_edge_function_size(
+    project: Vc<Project>,
+    path: Vc<FileSystemPath>,
+    name: &str,
+    files: Vc<OutputAssets>,
+) -> Result<()> {
+    if *project.mode().await? != NextMode::Build {
+        return Ok(());
+    }
+
+    let limit = *project.next_config().edge_function_size_limit().await?;
+    let size = *edge_function_compressed_size(files).await?;
+    if size > limit {
+        EdgeFunctionSizeIssue {
+            path,
+            name: name.to_string(),
+            size,
+            limit,
+        }
+        .cell()
+        .emit();
+    }
+
+    Ok(())
+}
+
+#[turbo_tasks::value(shared)]
+struct EdgeFunctionSizeIssue {
+    path: Vc<FileSystemPath>,
+    name: String,
+    size: u64,
+    limit: u64,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for EdgeFunctionSizeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The edge function for {} is {} KB gzipped, which exceeds the size limit of {} KB",
+            self.name,
+            self.size / 1024,
+            self.limit / 1024
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("output".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "Reduce the code imported by this entry, or raise \
+             `experimental.edgeFunctionSizeLimit` if your platform allows larger functions."
+                .to_string(),
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use next_core::{next_edge::wasm::edge_wasm_binding, next_manifests::AssetBinding};
+use turbo_tasks::{
+    graph::{GraphTraversal, NonDeterministic},
+    Vc,
+};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::core::{
+        asset::Asset, module::Module, output::OutputAsset, reference::primary_referenced_modules,
+        virtual_output::VirtualOutputAsset,
+    },
+};
+
+async fn get_referenced_modules(
+    parent: Vc<Box<dyn Module>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn Module>>> + Send> {
+    primary_referenced_modules(parent)
+        .await
+        .map(|modules| modules.clone_value().into_iter())
+}
+
+/// Collects the `.wasm` files imported by the edge function of `entry`. They
+/// are emitted to `server/edge-chunks`, and the returned bindings tell the
+/// Edge Runtime which global to compile each of them into, through the `wasm`
+/// field of the middleware manifest.
+pub(crate) async fn edge_wasm_assets(
+    node_root: Vc<FileSystemPath>,
+    entry: Vc<Box<dyn Module>>,
+) -> Result<(Vec<AssetBinding>, Vec<Vc<Box<dyn OutputAsset>>>)> {
+    let modules = NonDeterministic::new()
+        .skip_duplicates()
+        .visit([entry], get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner();
+
+    let mut bindings = vec![];
+    let mut assets = vec![];
+    for module in modules {
+        let Some(binding) = *edge_wasm_binding(module).await? else {
+            continue;
+        };
+        let binding = binding.await?;
+        // The same file imported twice is only bound once.
+        if bindings
+            .iter()
+            .any(|existing: &AssetBinding| existing.name == binding.name)
+        {
+            continue;
+        }
+        let file_path = format!("server/edge-chunks/{}.wasm", binding.name);
+        assets.push(Vc::upcast(VirtualOutputAsset::new(
+            node_root.join(file_path.clone()),
+            binding.wasm.content(),
+        )));
+        bindings.push(AssetBinding {
+            name: binding.name.clone(),
+            file_path,
+        });
+    }
+    bindings.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok((bindings, assets))
+}
@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 use turbo_tasks::Vc;
 
 use crate::{
-    project::Middleware,
+    project::{Instrumentation, Middleware},
     route::{Endpoint, Route},
 };
 
@@ -10,6 +10,8 @@ use crate::{
 pub struct Entrypoints {
     pub routes: IndexMap<String, Route>,
     pub middleware: Option<Middleware>,
+    pub instrumentation: Option<Instrumentation>,
+    pub service_worker_endpoint: Option<Vc<Box<dyn Endpoint>>>,
     pub pages_document_endpoint: Vc<Box<dyn Endpoint>>,
     pub pages_app_endpoint: Vc<Box<dyn Endpoint>>,
     pub pages_error_endpoint: Vc<Box<dyn Endpoint>>,
@@ -3,14 +3,20 @@
 #![feature(async_fn_in_trait)]
 
 mod app;
+mod dynamic_code_evaluation;
 mod dynamic_imports;
+mod edge_function_size;
+mod edge_wasm;
 mod entrypoints;
+mod instrumentation;
 mod middleware;
 mod pages;
 pub mod project;
 pub mod route;
+mod route_conflict;
 mod server_actions;
 pub mod server_paths;
+mod service_worker;
 mod versioned_content_map;
 
 // Declare build-time information variables generated in build.rs
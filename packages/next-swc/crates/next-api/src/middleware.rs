@@ -1,9 +1,8 @@
 use anyhow::{bail, Context, Result};
 use next_core::{
     middleware::get_middleware_module,
-    mode::NextMode,
     next_edge::entry::wrap_edge_entry,
-    next_manifests::{EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2},
+    next_manifests::{EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2, Regions},
     next_server::{get_server_runtime_entries, ServerContextType},
     util::parse_config_from_source,
 };
@@ -13,7 +12,6 @@ use turbopack_binding::{
     turbopack::{
         core::{
             asset::AssetContent,
-            chunk::ChunkingContext,
             context::AssetContext,
             module::Module,
             output::{OutputAsset, OutputAssets},
@@ -24,6 +22,9 @@ use turbopack_binding::{
 };
 
 use crate::{
+    dynamic_code_evaluation::check_dynamic_code_evaluation,
+    edge_function_size::check_edge_function_size,
+    edge_wasm::edge_wasm_assets,
     project::Project,
     route::{Endpoint, WrittenEndpoint},
     server_paths::all_server_paths,
@@ -69,7 +70,7 @@ impl MiddlewareEndpoint {
 
         let mut evaluatable_assets = get_server_runtime_entries(
             Value::new(ServerContextType::Middleware),
-            NextMode::Development,
+            *self.project.mode().await?,
         )
         .resolve_entries(self.context)
         .await?
@@ -86,10 +87,11 @@ impl MiddlewareEndpoint {
         };
         evaluatable_assets.push(evaluatable);
 
-        let edge_chunking_context = self.project.edge_chunking_context();
-
-        let edge_files = edge_chunking_context
-            .evaluated_chunk_group(module.ident(), Vc::cell(evaluatable_assets));
+        let edge_files = self.project.edge_evaluated_chunk_group(
+            "/middleware".to_string(),
+            module.ident(),
+            Vc::cell(evaluatable_assets),
+        );
 
         Ok(edge_files)
     }
@@ -100,10 +102,23 @@ impl MiddlewareEndpoint {
 
         let config = parse_config_from_source(this.userland_module);
 
-        let mut output_assets = self.edge_files().await?.clone_value();
+        check_dynamic_code_evaluation(this.project, this.userland_module, config).await?;
+
+        let edge_files = self.edge_files();
+        check_edge_function_size(
+            this.project,
+            this.userland_module.ident().path(),
+            "middleware",
+            edge_files,
+        )
+        .await?;
+
+        let mut output_assets = edge_files.await?.clone_value();
 
         let node_root = this.project.node_root();
 
+        let (wasm, wasm_assets) = edge_wasm_assets(node_root, this.userland_module).await?;
+
         let files_paths_from_root = {
             let node_root = &node_root.await?;
             output_assets
@@ -118,11 +133,15 @@ impl MiddlewareEndpoint {
                 .await?
         };
 
-        let matchers = if let Some(matchers) = config.await?.matcher.as_ref() {
+        let config = config.await?;
+        let matchers = if let Some(matchers) = config.matcher.as_ref() {
             matchers
                 .iter()
                 .map(|matcher| MiddlewareMatcher {
-                    original_source: matcher.to_string(),
+                    original_source: matcher.source.clone(),
+                    locale: matcher.locale,
+                    has: matcher.has.clone(),
+                    missing: matcher.missing.clone(),
                     ..Default::default()
                 })
                 .collect()
@@ -138,8 +157,9 @@ impl MiddlewareEndpoint {
             files: files_paths_from_root,
             name: "middleware".to_string(),
             page: "/".to_string(),
-            regions: None,
+            regions: config.regions.clone().map(Regions::Multiple),
             matchers,
+            wasm: Some(wasm),
             ..Default::default()
         };
         let middleware_manifest_v2 = MiddlewaresManifestV2 {
@@ -147,7 +167,7 @@ impl MiddlewareEndpoint {
             middleware: [("/".to_string(), edge_function_definition)]
                 .into_iter()
                 .collect(),
-            functions: Default::default(),
+            ..Default::default()
         };
         let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
             node_root.join("server/middleware/middleware-manifest.json".to_string()),
@@ -159,6 +179,7 @@ impl MiddlewareEndpoint {
             ),
         ));
         output_assets.push(middleware_manifest_v2);
+        output_assets.extend(wasm_assets);
 
         Ok(Vc::cell(output_assets))
     }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use anyhow::{bail, Context, Result};
 use indexmap::IndexMap;
@@ -14,20 +14,25 @@ use next_core::{
     next_edge::route_regex::get_named_middleware_regex,
     next_manifests::{
         BuildManifest, EdgeFunctionDefinition, LoadableManifest, MiddlewareMatcher,
-        MiddlewaresManifestV2, PagesManifest,
+        MiddlewaresManifestV2, PagesManifest, Regions,
     },
     next_pages::create_page_ssr_entry_module,
+    next_script::find_before_interactive_scripts_in_graph,
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    nft_json::NftJsonAsset,
     pages_structure::{
         find_pages_structure, PagesDirectoryStructure, PagesStructure, PagesStructureItem,
     },
-    util::{get_asset_prefix_from_pathname, parse_config_from_source, NextRuntime},
+    util::{
+        get_asset_prefix_from_pathname, parse_config_from_source, NextRuntime, NextSourceAmpConfig,
+    },
     PageLoaderAsset,
 };
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use turbo_tasks::{
     trace::TraceRawVcs, Completion, TaskInput, TryFlatJoinIterExt, TryJoinIterExt, Value, Vc,
 };
@@ -69,8 +74,10 @@ use crate::{
         collect_chunk_group, collect_evaluated_chunk_group, collect_next_dynamic_imports,
         DynamicImportedChunks,
     },
+    edge_function_size::check_edge_function_size,
+    edge_wasm::edge_wasm_assets,
     project::Project,
-    route::{Endpoint, Route, Routes, WrittenEndpoint},
+    route::{Endpoint, Route, RouteFiles, Routes, WrittenEndpoint},
     server_paths::all_server_paths,
 };
 
@@ -178,6 +185,32 @@ impl PagesProject {
         Ok(Vc::cell(routes))
     }
 
+    /// The files the routes are created from, used to report conflicts with
+    /// app routes.
+    #[turbo_tasks::function]
+    pub async fn route_files(self: Vc<Self>) -> Result<Vc<RouteFiles>> {
+        let PagesStructure { api, pages, .. } = &*self.pages_structure().await?;
+        let mut files = IndexMap::new();
+        let mut queue: Vec<_> = api.iter().chain(pages.iter()).copied().collect();
+        while let Some(dir) = queue.pop() {
+            let PagesDirectoryStructure {
+                ref items,
+                ref children,
+                ..
+            } = *dir.await?;
+            for &item in items.iter() {
+                let PagesStructureItem {
+                    next_router_path,
+                    project_path,
+                    ..
+                } = *item.await?;
+                files.insert(format!("/{}", next_router_path.await?.path), project_path);
+            }
+            queue.extend(children.iter().copied());
+        }
+        Ok(Vc::cell(files))
+    }
+
     #[turbo_tasks::function]
     async fn to_endpoint(
         self: Vc<Self>,
@@ -652,6 +685,8 @@ impl PageEndpoint {
                 this.original_name,
                 config.runtime,
                 this.pages_project.project().next_config(),
+                this.pages_project.project().build_id(),
+                *this.pages_project.project().mode().await?,
             );
 
             let mut evaluatable_assets = edge_runtime_entries.await?.clone_value();
@@ -660,8 +695,11 @@ impl PageEndpoint {
             };
             evaluatable_assets.push(evaluatable);
 
-            let edge_files = edge_chunking_context
-                .evaluated_chunk_group(ssr_module.ident(), Vc::cell(evaluatable_assets.clone()));
+            let edge_files = this.pages_project.project().edge_evaluated_chunk_group(
+                this.original_name.await?.clone_value(),
+                ssr_module.ident(),
+                Vc::cell(evaluatable_assets.clone()),
+            );
 
             let dynamic_import_modules = collect_next_dynamic_imports(ssr_module).await?;
             let dynamic_import_entries = collect_evaluated_chunk_group(
@@ -672,8 +710,10 @@ impl PageEndpoint {
             .await?;
 
             Ok(SsrChunk::Edge {
+                entry: Vc::upcast(ssr_module),
                 files: edge_files,
                 dynamic_import_entries,
+                regions: config.regions.clone(),
             }
             .cell())
         } else {
@@ -686,6 +726,8 @@ impl PageEndpoint {
                 this.original_name,
                 config.runtime,
                 this.pages_project.project().next_config(),
+                this.pages_project.project().build_id(),
+                *this.pages_project.project().mode().await?,
             );
 
             let asset_path = get_asset_path_from_pathname(&this.pathname.await?, ".js");
@@ -706,6 +748,7 @@ impl PageEndpoint {
 
             Ok(SsrChunk::NodeJs {
                 entry: ssr_entry_chunk,
+                module: Vc::upcast(ssr_module),
                 dynamic_import_entries,
             }
             .cell())
@@ -810,7 +853,7 @@ impl PageEndpoint {
         let dynamic_import_entries = &*dynamic_import_entries.await?;
 
         let mut output = vec![];
-        let mut loadable_manifest: HashMap<String, LoadableManifest> = Default::default();
+        let mut loadable_manifest: BTreeMap<String, LoadableManifest> = Default::default();
         for (origin, dynamic_imports) in dynamic_import_entries.into_iter() {
             let origin_path = &*origin.ident().path().await?;
 
@@ -879,6 +922,33 @@ impl PageEndpoint {
         let node_root = this.pages_project.project().node_root();
         let client_relative_path = this.pages_project.project().client_relative_path();
         let client_relative_path_ref = client_relative_path.await?;
+        let client_module = this.pages_project.client_module_context().process(
+            self.source(),
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Page)),
+        );
+        // `beforeInteractive` scripts need to be part of the initial HTML, so
+        // they are recorded here instead of being loaded with the page chunks.
+        // The page is rendered with `_app` and `_document`, so their scripts
+        // apply to it as well. `_document` is only rendered on the server.
+        let pages_structure = this.pages_project.pages_structure().await?;
+        let app_module = this.pages_project.client_module_context().process(
+            Vc::upcast(FileSource::new(pages_structure.app.project_path())),
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Page)),
+        );
+        let document_module = this.pages_project.ssr_module_context().process(
+            Vc::upcast(FileSource::new(pages_structure.document.project_path())),
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Page)),
+        );
+        let before_interactive_scripts = find_before_interactive_scripts_in_graph(Vc::cell(vec![
+            client_module,
+            app_module,
+            document_module,
+        ]))
+        .await?;
+        let is_amp_first = matches!(
+            parse_config_from_source(client_module).await?.amp,
+            NextSourceAmpConfig::AmpOnly
+        );
         let build_manifest = BuildManifest {
             pages: [(
                 this.pathname.await?.clone_value(),
@@ -901,6 +971,21 @@ impl PageEndpoint {
             )]
             .into_iter()
             .collect(),
+            before_interactive_scripts: if before_interactive_scripts.is_empty() {
+                Default::default()
+            } else {
+                [(
+                    this.pathname.await?.clone_value(),
+                    before_interactive_scripts.clone_value(),
+                )]
+                .into_iter()
+                .collect()
+            },
+            amp_first_pages: if is_amp_first {
+                vec![this.pathname.await?.clone_value()]
+            } else {
+                vec![]
+            },
             ..Default::default()
         };
         let manifest_path_prefix = get_asset_prefix_from_pathname(&this.pathname.await?);
@@ -940,12 +1025,24 @@ impl PageEndpoint {
         let page_output = match *ssr_chunk.await? {
             SsrChunk::NodeJs {
                 entry,
+                module,
                 dynamic_import_entries,
             } => {
                 let pages_manifest = self.pages_manifest(entry);
                 server_assets.push(pages_manifest);
                 server_assets.push(entry);
 
+                if *this.pages_project.project().mode().await? == NextMode::Build {
+                    let project = this.pages_project.project();
+                    server_assets.push(Vc::upcast(NftJsonAsset::new(
+                        entry,
+                        module,
+                        project.project_path(),
+                        project.node_root(),
+                        project.server_compile_time_info(),
+                    )));
+                }
+
                 let loadable_manifest_output = self.react_loadable_manifest(dynamic_import_entries);
                 server_assets.extend(loadable_manifest_output.await?.iter().copied());
 
@@ -956,10 +1053,20 @@ impl PageEndpoint {
                 }
             }
             SsrChunk::Edge {
+                entry,
                 files,
                 dynamic_import_entries,
+                ref regions,
             } => {
                 let node_root = this.pages_project.project().node_root();
+                check_edge_function_size(
+                    this.pages_project.project(),
+                    this.path,
+                    &this.original_name.await?,
+                    files,
+                )
+                .await?;
+
                 let files_value = files.await?;
                 if let Some(&file) = files_value.first() {
                     let pages_manifest = self.pages_manifest(file);
@@ -994,6 +1101,9 @@ impl PageEndpoint {
 
                 file_paths_from_root.extend(middleware_paths_from_root);
 
+                let (wasm, wasm_assets) = edge_wasm_assets(node_root, entry).await?;
+                server_assets.extend(wasm_assets);
+
                 let pathname = this.pathname.await?;
                 let named_regex = get_named_middleware_regex(&pathname);
                 let matchers = MiddlewareMatcher {
@@ -1006,8 +1116,9 @@ impl PageEndpoint {
                     files: file_paths_from_root,
                     name: pathname.to_string(),
                     page: original_name.to_string(),
-                    regions: None,
+                    regions: regions.clone().map(Regions::Multiple),
                     matchers: vec![matchers],
+                    wasm: Some(wasm),
                     ..Default::default()
                 };
                 let middleware_manifest_v2 = MiddlewaresManifestV2 {
@@ -1016,6 +1127,7 @@ impl PageEndpoint {
                     functions: [(pathname.to_string(), edge_function_definition)]
                         .into_iter()
                         .collect(),
+                    ..Default::default()
                 };
                 let manifest_path_prefix = get_asset_prefix_from_pathname(&this.pathname.await?);
                 let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
@@ -1061,11 +1173,20 @@ impl Endpoint for PageEndpoint {
         let output_assets = self.output_assets();
 
         let this = self.await?;
+        let pathname = this.pathname.await?;
 
-        this.pages_project
-            .project()
-            .emit_all_output_assets(Vc::cell(output_assets))
+        // Resolving the output assets compiles and chunks the page.
+        async { output_assets.await }
+            .instrument(tracing::info_span!("compile-path", path = %pathname))
             .await?;
+        async {
+            this.pages_project
+                .project()
+                .emit_all_output_assets(Vc::cell(output_assets))
+                .await
+        }
+        .instrument(tracing::info_span!("emit-output", path = %pathname))
+        .await?;
 
         let node_root = this.pages_project.project().node_root();
         let server_paths = all_server_paths(output_assets, node_root)
@@ -1170,10 +1291,13 @@ impl PageEndpointOutput {
 pub enum SsrChunk {
     NodeJs {
         entry: Vc<Box<dyn OutputAsset>>,
+        module: Vc<Box<dyn Module>>,
         dynamic_import_entries: Vc<DynamicImportedChunks>,
     },
     Edge {
+        entry: Vc<Box<dyn Module>>,
         files: Vc<OutputAssets>,
         dynamic_import_entries: Vc<DynamicImportedChunks>,
+        regions: Option<Vec<String>>,
     },
 }
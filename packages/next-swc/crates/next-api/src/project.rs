@@ -7,15 +7,25 @@ use next_core::{
     app_structure::find_app_dir,
     emit_assets, get_edge_chunking_context, get_edge_compile_time_info,
     get_edge_resolve_options_context,
+    instrumentation::instrumentation_files,
     middleware::middleware_files,
     mode::NextMode,
-    next_client::{get_client_chunking_context, get_client_compile_time_info},
-    next_config::{JsConfig, NextConfig},
+    next_client::{
+        get_client_chunking_context, get_client_compile_time_info,
+        get_client_module_options_context, get_client_relative_path,
+        get_client_resolve_options_context, get_service_worker_chunking_context,
+        get_service_worker_compile_time_info, ClientContextType,
+    },
+    next_config::{load_redirects, load_rewrites, JsConfig, NextConfig},
+    next_edge::{single_file::EdgeSingleFileAsset, unsupported::check_edge_runtime_module_graph},
     next_server::{
         get_server_chunking_context, get_server_compile_time_info,
-        get_server_module_options_context, ServerContextType,
+        get_server_module_options_context, get_server_resolve_options_context, ServerContextType,
     },
     next_telemetry::NextFeatureTelemetry,
+    route_collisions::find_route_collisions,
+    service_worker::service_worker_files,
+    typed_routes::route_type_definitions,
 };
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{
@@ -28,17 +38,22 @@ use turbo_tasks::{
 use turbopack_binding::{
     turbo::{
         tasks_env::{EnvMap, ProcessEnv},
-        tasks_fs::{DiskFileSystem, FileSystem, FileSystemPath, VirtualFileSystem},
+        tasks_fs::{
+            DiskFileSystem, File, FileContent, FileSystem, FileSystemPath, VirtualFileSystem,
+        },
     },
     turbopack::{
         build::BuildChunkingContext,
         core::{
             changed::content_changed,
+            chunk::{ChunkingContext, EvaluatableAssets},
             compile_time_info::CompileTimeInfo,
             context::AssetContext,
             diagnostics::DiagnosticExt,
             environment::ServerAddr,
             file_source::FileSource,
+            ident::AssetIdent,
+            issue::IssueExt,
             output::{OutputAsset, OutputAssets},
             reference_type::{EntryReferenceSubType, ReferenceType},
             resolve::{find_context_file, FindContextFileResult},
@@ -57,9 +72,12 @@ use crate::{
     app::{AppProject, OptionAppProject},
     build,
     entrypoints::Entrypoints,
+    instrumentation::InstrumentationEndpoint,
     middleware::MiddlewareEndpoint,
     pages::PagesProject,
     route::{Endpoint, Route},
+    route_conflict::{RouteCollisionIssue, RouteConflictIssue},
+    service_worker::ServiceWorkerEndpoint,
     versioned_content_map::{OutputAssetsOperation, VersionedContentMap},
 };
 
@@ -91,6 +109,20 @@ pub struct ProjectOptions {
 
     /// The address of the dev server.
     pub server_addr: String,
+
+    /// Whether the project is compiled for `next dev` or `next build`.
+    pub dev: bool,
+
+    /// The edge functions whose chunks are rolled into a single file, by
+    /// page, e.g. `/api/hello` for pages, `/api/hello/route` for app routes or
+    /// `/middleware`.
+    #[serde(default)]
+    pub single_file_edge_routes: Vec<String>,
+
+    /// The ID of the build generated by Next.js. When it isn't provided, the
+    /// ID is computed from `generateBuildId` of next.config.js.
+    #[serde(default)]
+    pub build_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, TaskInput, PartialEq, Eq, TraceRawVcs)]
@@ -121,6 +153,17 @@ pub struct PartialProjectOptions {
 
     /// The address of the dev server.
     pub server_addr: Option<String>,
+
+    /// Whether the project is compiled for `next dev` or `next build`.
+    pub dev: Option<bool>,
+
+    /// The edge functions whose chunks are rolled into a single file, by
+    /// page, e.g. `/api/hello` for pages, `/api/hello/route` for app routes or
+    /// `/middleware`.
+    pub single_file_edge_routes: Option<Vec<String>>,
+
+    /// The ID of the build generated by Next.js.
+    pub build_id: Option<Option<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, TaskInput, PartialEq, Eq, TraceRawVcs)]
@@ -136,6 +179,12 @@ pub struct Middleware {
     pub endpoint: Vc<Box<dyn Endpoint>>,
 }
 
+#[derive(Serialize, Deserialize, TraceRawVcs, PartialEq, Eq, ValueDebugFormat)]
+pub struct Instrumentation {
+    pub node_js: Vc<Box<dyn Endpoint>>,
+    pub edge: Vc<Box<dyn Endpoint>>,
+}
+
 #[turbo_tasks::value]
 pub struct ProjectContainer {
     options_state: State<ProjectOptions>,
@@ -181,6 +230,15 @@ impl ProjectContainer {
         if let Some(server_addr) = options.server_addr {
             new_options.server_addr = server_addr;
         }
+        if let Some(dev) = options.dev {
+            new_options.dev = dev;
+        }
+        if let Some(single_file_edge_routes) = options.single_file_edge_routes {
+            new_options.single_file_edge_routes = single_file_edge_routes;
+        }
+        if let Some(build_id) = options.build_id {
+            new_options.build_id = build_id;
+        }
 
         self.options_state.set(new_options);
 
@@ -191,7 +249,19 @@ impl ProjectContainer {
     pub async fn project(self: Vc<Self>) -> Result<Vc<Project>> {
         let this = self.await?;
 
-        let (env, define_env, next_config, js_config, root_path, project_path, watch, server_addr) = {
+        let (
+            env,
+            define_env,
+            next_config,
+            js_config,
+            root_path,
+            project_path,
+            watch,
+            server_addr,
+            mode,
+            single_file_edge_routes,
+            build_id,
+        ) = {
             let options = this.options_state.get();
             let env: Vc<EnvMap> = Vc::cell(options.env.iter().cloned().collect());
             let define_env: Vc<ProjectDefineEnv> = ProjectDefineEnv {
@@ -206,6 +276,13 @@ impl ProjectContainer {
             let project_path = options.project_path.clone();
             let watch = options.watch;
             let server_addr = options.server_addr.parse()?;
+            let mode = if options.dev {
+                NextMode::Development
+            } else {
+                NextMode::Build
+            };
+            let single_file_edge_routes = options.single_file_edge_routes.clone();
+            let build_id = options.build_id.clone();
             (
                 env,
                 define_env,
@@ -215,14 +292,13 @@ impl ProjectContainer {
                 project_path,
                 watch,
                 server_addr,
+                mode,
+                single_file_edge_routes,
+                build_id,
             )
         };
 
-        let dist_dir = next_config
-            .await?
-            .dist_dir
-            .as_ref()
-            .map_or_else(|| ".next".to_string(), |d| d.to_string());
+        let dist_dir = next_config.dist_dir().await?.clone_value();
 
         Ok(Project {
             root_path,
@@ -237,7 +313,9 @@ impl ProjectContainer {
             browserslist_query: "last 1 Chrome versions, last 1 Firefox versions, last 1 Safari \
                                  versions, last 1 Edge versions"
                 .to_string(),
-            mode: NextMode::Development,
+            mode,
+            single_file_edge_routes,
+            build_id,
             versioned_content_map: this.versioned_content_map,
         }
         .cell())
@@ -301,6 +379,12 @@ pub struct Project {
 
     mode: NextMode,
 
+    /// See [ProjectOptions::single_file_edge_routes].
+    single_file_edge_routes: Vec<String>,
+
+    /// See [ProjectOptions::build_id].
+    build_id: Option<String>,
+
     versioned_content_map: Vc<VersionedContentMap>,
 }
 
@@ -329,6 +413,20 @@ impl ProjectDefineEnv {
     }
 }
 
+/// Merges the defines derived from next.config into the ones passed in by
+/// the caller. Caller-provided keys take precedence.
+#[turbo_tasks::function]
+async fn merge_define_env(define_env: Vc<EnvMap>, extra: Vc<EnvMap>) -> Result<Vc<EnvMap>> {
+    let mut merged = extra.await?.clone_value();
+    merged.extend(
+        define_env
+            .await?
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone())),
+    );
+    Ok(Vc::cell(merged))
+}
+
 #[turbo_tasks::value_impl]
 impl Project {
     #[turbo_tasks::function]
@@ -381,6 +479,20 @@ impl Project {
         ServerAddr::new(self.server_addr).cell()
     }
 
+    #[turbo_tasks::function]
+    pub(super) fn mode(&self) -> Vc<NextMode> {
+        self.mode.cell()
+    }
+
+    #[turbo_tasks::function]
+    pub async fn build_id(self: Vc<Self>) -> Result<Vc<String>> {
+        let this = self.await?;
+        Ok(match &this.build_id {
+            Some(build_id) if this.mode == NextMode::Build => Vc::cell(build_id.clone()),
+            _ => self.next_config().build_id(this.mode),
+        })
+    }
+
     #[turbo_tasks::function]
     pub async fn dist_dir(self: Vc<Self>) -> Result<Vc<String>> {
         Ok(Vc::cell(self.await?.dist_dir.to_string()))
@@ -403,15 +515,8 @@ impl Project {
     }
 
     #[turbo_tasks::function]
-    pub async fn client_relative_path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
-        let next_config = self.next_config().await?;
-        Ok(self.client_root().join(format!(
-            "{}/_next",
-            next_config
-                .base_path
-                .clone()
-                .unwrap_or_else(|| "".to_string()),
-        )))
+    pub fn client_relative_path(self: Vc<Self>) -> Vc<FileSystemPath> {
+        get_client_relative_path(self.client_root(), self.next_config())
     }
 
     #[turbo_tasks::function]
@@ -467,7 +572,15 @@ impl Project {
     pub(super) async fn client_compile_time_info(&self) -> Result<Vc<CompileTimeInfo>> {
         Ok(get_client_compile_time_info(
             self.browserslist_query.clone(),
-            self.define_env.client(),
+            merge_define_env(self.define_env.client(), self.next_config.define_env(false)),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub(super) async fn service_worker_compile_time_info(&self) -> Result<Vc<CompileTimeInfo>> {
+        Ok(get_service_worker_compile_time_info(
+            self.browserslist_query.clone(),
+            merge_define_env(self.define_env.client(), self.next_config.define_env(false)),
         ))
     }
 
@@ -477,7 +590,7 @@ impl Project {
         Ok(get_server_compile_time_info(
             self.env(),
             self.server_addr(),
-            this.define_env.nodejs(),
+            merge_define_env(this.define_env.nodejs(), this.next_config.define_env(true)),
         ))
     }
 
@@ -487,7 +600,8 @@ impl Project {
         Ok(get_edge_compile_time_info(
             self.project_path(),
             self.server_addr(),
-            this.define_env.edge(),
+            merge_define_env(this.define_env.edge(), this.next_config.define_env(true)),
+            this.mode,
         ))
     }
 
@@ -502,6 +616,22 @@ impl Project {
             self.next_config().computed_asset_prefix(),
             self.client_compile_time_info().environment(),
             this.mode,
+            self.next_config(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub(super) async fn service_worker_chunking_context(
+        self: Vc<Self>,
+    ) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
+        let this = self.await?;
+        Ok(get_service_worker_chunking_context(
+            self.project_path(),
+            self.client_relative_path(),
+            self.next_config().computed_asset_prefix(),
+            self.service_worker_compile_time_info().environment(),
+            this.mode,
+            self.next_config(),
         ))
     }
 
@@ -521,10 +651,49 @@ impl Project {
         get_edge_chunking_context(
             self.project_path(),
             self.node_root(),
+            self.client_relative_path(),
+            self.next_config().computed_asset_prefix(),
             self.edge_compile_time_info().environment(),
+            self.next_config(),
         )
     }
 
+    /// Chunks the edge function of `page`, rolling all chunks into a single
+    /// file when the page is one of [ProjectOptions::single_file_edge_routes].
+    /// Code which doesn't work in the Edge Runtime is reported here, as this
+    /// knows the entry.
+    #[turbo_tasks::function]
+    pub(super) async fn edge_evaluated_chunk_group(
+        self: Vc<Self>,
+        page: String,
+        ident: Vc<AssetIdent>,
+        evaluatable_assets: Vc<EvaluatableAssets>,
+    ) -> Result<Vc<OutputAssets>> {
+        check_edge_runtime_module_graph(
+            &page,
+            *self.mode().await?,
+            evaluatable_assets
+                .await?
+                .iter()
+                .map(|&asset| Vc::upcast(asset)),
+        )
+        .await?;
+
+        let edge_chunking_context = self.edge_chunking_context();
+        let chunks = edge_chunking_context.evaluated_chunk_group(ident, evaluatable_assets);
+        if !self.await?.single_file_edge_routes.contains(&page) {
+            return Ok(chunks);
+        }
+
+        let path = edge_chunking_context.chunk_path(
+            ident.with_modifier(Vc::cell("edge single file".to_string())),
+            ".js".to_string(),
+        );
+        Ok(Vc::cell(vec![Vc::upcast(EdgeSingleFileAsset::new(
+            path, chunks,
+        ))]))
+    }
+
     /// Emit a telemetry event corresponding to webpack configuration telemetry
     /// (https://github.com/vercel/next.js/blob/9da305fe320b89ee2f8c3cfb7ecbf48856368913/packages/next/src/build/webpack-config.ts#L2516)
     /// to detect which feature is enabled.
@@ -613,7 +782,8 @@ impl Project {
         let app_project = self.app_project();
         let pages_project = self.pages_project();
 
-        if let Some(app_project) = &*app_project.await? {
+        let app_project = &*app_project.await?;
+        if let Some(app_project) = app_project {
             let app_routes = app_project.routes();
             routes.extend(app_routes.await?.iter().map(|(k, v)| (k.clone(), *v)));
         }
@@ -622,6 +792,21 @@ impl Project {
             match routes.entry(pathname.clone()) {
                 Entry::Occupied(mut entry) => {
                     *entry.get_mut() = Route::Conflict;
+
+                    let app_file = match app_project {
+                        Some(app_project) => {
+                            app_project.route_files().await?.get(pathname).copied()
+                        }
+                        None => None,
+                    };
+                    RouteConflictIssue {
+                        project_path: self.project_path(),
+                        pathname: pathname.clone(),
+                        app_file,
+                        pages_file: pages_project.route_files().await?.get(pathname).copied(),
+                    }
+                    .cell()
+                    .emit();
                 }
                 Entry::Vacant(entry) => {
                     entry.insert(*page_route);
@@ -629,6 +814,17 @@ impl Project {
             }
         }
 
+        for collision in find_route_collisions(routes.keys().map(|pathname| pathname.as_str())) {
+            RouteCollisionIssue {
+                project_path: self.project_path(),
+                first: collision.first,
+                second: collision.second,
+                description: collision.kind.description().to_string(),
+            }
+            .cell()
+            .emit();
+        }
+
         let pages_document_endpoint = TraitRef::cell(
             self.pages_project()
                 .document_endpoint()
@@ -661,9 +857,52 @@ impl Project {
             None
         };
 
+        let instrumentation = find_context_file(
+            self.project_path(),
+            instrumentation_files(self.next_config().page_extensions()),
+        );
+        let instrumentation = if !*self.next_config().instrumentation_hook().await? {
+            None
+        } else if let FindContextFileResult::Found(fs_path, _) = *instrumentation.await? {
+            let source = Vc::upcast(FileSource::new(fs_path));
+            Some(Instrumentation {
+                node_js: TraitRef::cell(
+                    Vc::upcast::<Box<dyn Endpoint>>(self.instrumentation_endpoint(source, false))
+                        .into_trait_ref()
+                        .await?,
+                ),
+                edge: TraitRef::cell(
+                    Vc::upcast::<Box<dyn Endpoint>>(self.instrumentation_endpoint(source, true))
+                        .into_trait_ref()
+                        .await?,
+                ),
+            })
+        } else {
+            None
+        };
+
+        let service_worker = find_context_file(
+            self.project_path(),
+            service_worker_files(self.next_config().page_extensions()),
+        );
+        let service_worker_endpoint = if !*self.next_config().service_worker().await? {
+            None
+        } else if let FindContextFileResult::Found(fs_path, _) = *service_worker.await? {
+            let source = Vc::upcast(FileSource::new(fs_path));
+            Some(TraitRef::cell(
+                Vc::upcast::<Box<dyn Endpoint>>(self.service_worker_endpoint(source))
+                    .into_trait_ref()
+                    .await?,
+            ))
+        } else {
+            None
+        };
+
         Ok(Entrypoints {
             routes,
             middleware,
+            instrumentation,
+            service_worker_endpoint,
             pages_document_endpoint,
             pages_app_endpoint,
             pages_error_endpoint,
@@ -671,27 +910,93 @@ impl Project {
         .cell())
     }
 
+    /// Writes the route types of `experimental.typedRoutes` for the routes of
+    /// the entrypoints to `types/link.d.ts`, like the `NextTypesPlugin` of
+    /// webpack.
+    #[turbo_tasks::function]
+    pub async fn emit_route_types(self: Vc<Self>) -> Result<Vc<Completion>> {
+        if !*self.next_config().typed_routes().await? {
+            return Ok(Completion::immutable());
+        }
+
+        let entrypoints = self.entrypoints().await?;
+        let routes = entrypoints
+            .routes
+            .keys()
+            .map(|pathname| pathname.as_str())
+            // Error pages can't be linked to.
+            .filter(|pathname| !matches!(*pathname, "/404" | "/500" | "/_error" | "/_not-found"));
+        let rewrites = load_rewrites(self.execution_context()).await?;
+        let redirects = load_redirects(self.execution_context()).await?;
+        let content = route_type_definitions(routes, &rewrites, &redirects);
+
+        Ok(self
+            .node_root()
+            .join("types/link.d.ts".to_string())
+            .write(FileContent::Content(File::from(content)).cell()))
+    }
+
     #[turbo_tasks::function]
-    fn middleware_context(self: Vc<Self>) -> Vc<Box<dyn AssetContext>> {
-        Vc::upcast(ModuleAssetContext::new(
+    async fn service_worker_context(self: Vc<Self>) -> Result<Vc<Box<dyn AssetContext>>> {
+        let mode = self.await?.mode;
+        let ty = Value::new(ClientContextType::Other);
+        Ok(Vc::upcast(ModuleAssetContext::new(
+            Default::default(),
+            self.service_worker_compile_time_info(),
+            get_client_module_options_context(
+                self.project_path(),
+                self.execution_context(),
+                self.service_worker_compile_time_info().environment(),
+                ty,
+                mode,
+                self.next_config(),
+            ),
+            get_client_resolve_options_context(
+                self.project_path(),
+                ty,
+                mode,
+                self.next_config(),
+                self.execution_context(),
+            ),
+            Vc::cell("service-worker".to_string()),
+        )))
+    }
+
+    #[turbo_tasks::function]
+    fn service_worker_endpoint(
+        self: Vc<Self>,
+        source: Vc<Box<dyn Source>>,
+    ) -> Vc<ServiceWorkerEndpoint> {
+        let module = self.service_worker_context().process(
+            source,
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+        );
+
+        ServiceWorkerEndpoint::new(self, module)
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_context(self: Vc<Self>) -> Result<Vc<Box<dyn AssetContext>>> {
+        let mode = self.await?.mode;
+        Ok(Vc::upcast(ModuleAssetContext::new(
             Default::default(),
             self.edge_compile_time_info(),
             get_server_module_options_context(
                 self.project_path(),
                 self.execution_context(),
                 Value::new(ServerContextType::Middleware),
-                NextMode::Development,
+                mode,
                 self.next_config(),
             ),
             get_edge_resolve_options_context(
                 self.project_path(),
                 Value::new(ServerContextType::Middleware),
-                NextMode::Development,
+                mode,
                 self.next_config(),
                 self.execution_context(),
             ),
             Vc::cell("middleware".to_string()),
-        ))
+        )))
     }
 
     #[turbo_tasks::function]
@@ -706,6 +1011,74 @@ impl Project {
         MiddlewareEndpoint::new(self, context, module)
     }
 
+    #[turbo_tasks::function]
+    async fn instrumentation_context(self: Vc<Self>) -> Result<Vc<Box<dyn AssetContext>>> {
+        let mode = self.await?.mode;
+        Ok(Vc::upcast(ModuleAssetContext::new(
+            Default::default(),
+            self.server_compile_time_info(),
+            get_server_module_options_context(
+                self.project_path(),
+                self.execution_context(),
+                Value::new(ServerContextType::Instrumentation),
+                mode,
+                self.next_config(),
+            ),
+            get_server_resolve_options_context(
+                self.project_path(),
+                Value::new(ServerContextType::Instrumentation),
+                mode,
+                self.next_config(),
+                self.execution_context(),
+            ),
+            Vc::cell("instrumentation".to_string()),
+        )))
+    }
+
+    #[turbo_tasks::function]
+    async fn edge_instrumentation_context(self: Vc<Self>) -> Result<Vc<Box<dyn AssetContext>>> {
+        let mode = self.await?.mode;
+        Ok(Vc::upcast(ModuleAssetContext::new(
+            Default::default(),
+            self.edge_compile_time_info(),
+            get_server_module_options_context(
+                self.project_path(),
+                self.execution_context(),
+                Value::new(ServerContextType::Instrumentation),
+                mode,
+                self.next_config(),
+            ),
+            get_edge_resolve_options_context(
+                self.project_path(),
+                Value::new(ServerContextType::Instrumentation),
+                mode,
+                self.next_config(),
+                self.execution_context(),
+            ),
+            Vc::cell("instrumentation-edge".to_string()),
+        )))
+    }
+
+    #[turbo_tasks::function]
+    fn instrumentation_endpoint(
+        self: Vc<Self>,
+        source: Vc<Box<dyn Source>>,
+        is_edge: bool,
+    ) -> Vc<InstrumentationEndpoint> {
+        let context = if is_edge {
+            self.edge_instrumentation_context()
+        } else {
+            self.instrumentation_context()
+        };
+
+        let module = context.process(
+            source,
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+        );
+
+        InstrumentationEndpoint::new(self, context, module, is_edge)
+    }
+
     #[turbo_tasks::function]
     pub async fn emit_all_output_assets(
         self: Vc<Self>,
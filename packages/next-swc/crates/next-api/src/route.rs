@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use turbo_tasks::{Completion, Vc};
+use turbopack_binding::turbo::tasks_fs::FileSystemPath;
 
 use crate::server_paths::ServerPath;
 
@@ -44,9 +45,17 @@ pub enum WrittenEndpoint {
         global_var_name: String,
         server_paths: Vec<ServerPath>,
     },
+    Client {
+        /// Relative to the client output directory (the `/_next/` URL prefix)
+        files: Vec<String>,
+    },
 }
 
 /// The routes as map from pathname to route. (pathname includes the leading
 /// slash)
 #[turbo_tasks::value(transparent)]
 pub struct Routes(IndexMap<String, Route>);
+
+/// The files the routes are created from as map from pathname to file path.
+#[turbo_tasks::value(transparent)]
+pub struct RouteFiles(IndexMap<String, Vc<FileSystemPath>>);
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::core::issue::{Issue, IssueSeverity},
+};
+
+/// An app route and a pages route resolving to the same pathname. Which of the
+/// two would be served depends on compilation order, so this is an error.
+#[turbo_tasks::value(shared)]
+pub struct RouteConflictIssue {
+    pub project_path: Vc<FileSystemPath>,
+    pub pathname: String,
+    pub app_file: Option<Vc<FileSystemPath>>,
+    pub pages_file: Option<Vc<FileSystemPath>>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RouteConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Conflicting app and page file found for {}",
+            self.pathname
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("next app".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.app_file.unwrap_or(self.project_path)
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<String>> {
+        let project_path = &*self.project_path.await?;
+        Ok(Vc::cell(format!(
+            "Both \"{}\" and \"{}\" resolve to {}. Please remove one of the conflicting files to \
+             continue.",
+            display_path(project_path, self.pages_file).await?,
+            display_path(project_path, self.app_file).await?,
+            self.pathname
+        )))
+    }
+}
+
+async fn display_path(
+    project_path: &FileSystemPath,
+    file: Option<Vc<FileSystemPath>>,
+) -> Result<String> {
+    let Some(file) = file else {
+        return Ok("unknown file".to_string());
+    };
+    let file = file.await?;
+    Ok(project_path
+        .get_path_to(&file)
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| file.path.clone()))
+}
+
+/// Two routes the router can't tell apart, like `/[id]` and `/[slug]`.
+#[turbo_tasks::value(shared)]
+pub struct RouteCollisionIssue {
+    pub project_path: Vc<FileSystemPath>,
+    pub first: String,
+    pub second: String,
+    pub description: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RouteCollisionIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Conflicting routes {} and {}",
+            self.first, self.second
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("next app".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.project_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(self.description.clone())
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::{bail, Context, Result};
+use turbo_tasks::{Completion, TryFlatJoinIterExt, TryJoinIterExt, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent},
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            chunk::ChunkingContext,
+            module::Module,
+            output::{OutputAsset, OutputAssets},
+            virtual_output::VirtualOutputAsset,
+        },
+        ecmascript::chunk::EcmascriptChunkPlaceable,
+    },
+};
+
+use crate::{
+    project::Project,
+    route::{Endpoint, WrittenEndpoint},
+};
+
+/// The service worker declared in `sw.{ext}` at the project root, bundled when
+/// `experimental.serviceWorker` is enabled.
+///
+/// Its chunks are loaded by a bootstrap script at `static/sw.js` with a stable
+/// name, which the server exposes at `/sw.js` so the worker's scope covers the
+/// whole site.
+#[turbo_tasks::value]
+pub struct ServiceWorkerEndpoint {
+    project: Vc<Project>,
+    userland_module: Vc<Box<dyn Module>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ServiceWorkerEndpoint {
+    #[turbo_tasks::function]
+    pub fn new(project: Vc<Project>, userland_module: Vc<Box<dyn Module>>) -> Vc<Self> {
+        Self {
+            project,
+            userland_module,
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn chunks(&self) -> Result<Vc<OutputAssets>> {
+        let Some(module) =
+            Vc::try_resolve_downcast::<Box<dyn EcmascriptChunkPlaceable>>(self.userland_module)
+                .await?
+        else {
+            bail!("Service worker entry module must be evaluatable");
+        };
+
+        let Some(evaluatable) = Vc::try_resolve_sidecast(module).await? else {
+            bail!("Service worker entry module must be evaluatable");
+        };
+
+        Ok(self
+            .project
+            .service_worker_chunking_context()
+            .evaluated_chunk_group(module.ident(), Vc::cell(vec![evaluatable])))
+    }
+
+    #[turbo_tasks::function]
+    async fn output_assets(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+        let mut output_assets = self.chunks().await?.clone_value();
+
+        let client_relative_path = this.project.client_relative_path();
+        let asset_prefix = this.project.next_config().computed_asset_prefix().await?;
+        let asset_prefix = asset_prefix.as_deref().unwrap_or("/_next/");
+
+        let chunk_urls = {
+            let client_relative_path = &client_relative_path.await?;
+            output_assets
+                .iter()
+                .map(|&chunk| async move {
+                    let chunk_path = chunk.ident().path().await?;
+                    if chunk_path.extension_ref() != Some("js") {
+                        return Ok(None);
+                    }
+                    let chunk_path = client_relative_path
+                        .get_path_to(&chunk_path)
+                        .context("service worker chunk path must be inside the client root")?;
+                    Ok(Some(serde_json::to_string(&format!(
+                        "{asset_prefix}{chunk_path}"
+                    ))?))
+                })
+                .try_flat_join()
+                .await?
+        };
+
+        let bootstrap = Vc::upcast(VirtualOutputAsset::new(
+            client_relative_path.join("static/sw.js".to_string()),
+            AssetContent::file(
+                FileContent::Content(File::from(format!(
+                    "importScripts({});\n",
+                    chunk_urls.join(", ")
+                )))
+                .cell(),
+            ),
+        ));
+        output_assets.push(bootstrap);
+
+        Ok(Vc::cell(output_assets))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Endpoint for ServiceWorkerEndpoint {
+    #[turbo_tasks::function]
+    async fn write_to_disk(self: Vc<Self>) -> Result<Vc<WrittenEndpoint>> {
+        let this = self.await?;
+        let output_assets = self.output_assets();
+        this.project
+            .emit_all_output_assets(Vc::cell(output_assets))
+            .await?;
+
+        let client_relative_path = &this.project.client_relative_path().await?;
+        let files = output_assets
+            .await?
+            .iter()
+            .map(|&file| async move {
+                Ok(client_relative_path
+                    .get_path_to(&*file.ident().path().await?)
+                    .context("service worker file path must be inside the client root")?
+                    .to_string())
+            })
+            .try_join()
+            .await?;
+
+        Ok(WrittenEndpoint::Client { files }.cell())
+    }
+
+    #[turbo_tasks::function]
+    fn server_changed(self: Vc<Self>) -> Vc<Completion> {
+        Completion::immutable()
+    }
+
+    #[turbo_tasks::function]
+    async fn client_changed(self: Vc<Self>) -> Result<Vc<Completion>> {
+        Ok(self.await?.project.client_changed(self.output_assets()))
+    }
+}
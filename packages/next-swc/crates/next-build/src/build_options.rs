@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use next_core::{next_config::Rewrites, turbopack::core::issue::IssueSeverity};
+use next_core::{
+    next_config::Rewrites, next_manifests::PrerenderManifestPreview,
+    turbopack::core::issue::IssueSeverity,
+};
 
 #[derive(Clone, Debug)]
 pub struct BuildOptions {
@@ -41,6 +44,9 @@ pub struct BuildContext {
 
     /// Next.js config rewrites.
     pub rewrites: Rewrites,
+
+    /// The keys of the preview and draft modes.
+    pub preview_props: PrerenderManifestPreview,
 }
 
 #[derive(Debug, Clone)]
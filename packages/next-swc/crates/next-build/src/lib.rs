@@ -2,9 +2,14 @@
 #![feature(arbitrary_self_types)]
 #![feature(async_fn_in_trait)]
 
-use turbopack_binding::turbo::{
-    tasks::{run_once, TransientInstance, TurboTasks},
-    tasks_memory::MemoryBackend,
+use indexmap::IndexMap;
+use next_core::next_telemetry::module_feature_usage;
+use turbopack_binding::{
+    turbo::{
+        tasks::{run_once, TransientInstance, TryJoinIterExt, TurboTasks},
+        tasks_memory::MemoryBackend,
+    },
+    turbopack::core::diagnostics::DiagnosticContextExt,
 };
 
 pub mod build_options;
@@ -17,7 +22,9 @@ use turbo_tasks::{StatsType, TurboTasksBackendApi};
 
 pub use self::build_options::BuildOptions;
 
-pub async fn build(options: BuildOptions) -> Result<()> {
+/// Builds the project, and returns how often each feature module, e.g.
+/// `next/image`, is imported in it for the feature usage telemetry events.
+pub async fn build(options: BuildOptions) -> Result<IndexMap<String, usize>> {
     #[cfg(feature = "tokio_console")]
     console_subscriber::init();
     register();
@@ -34,14 +41,23 @@ pub async fn build(options: BuildOptions) -> Result<()> {
     };
     tt.set_stats_type(stats_type);
 
-    run_once(tt, async move {
-        next_build::next_build(TransientInstance::new(options)).await?;
-
-        Ok(())
+    let feature_usage = run_once(tt, async move {
+        let build = next_build::next_build(TransientInstance::new(options));
+        build.await?;
+
+        let diagnostics = build
+            .peek_diagnostics()
+            .await?
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.into_plain())
+            .try_join()
+            .await?;
+        Ok(module_feature_usage(diagnostics.iter().map(|d| &**d)))
     })
     .await?;
 
-    Ok(())
+    Ok(feature_usage)
 }
 
 fn setup_tracing() {
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow::Result;
 use next_core::{
@@ -15,16 +15,20 @@ use next_core::{
     next_client_reference::{ClientReferenceGraph, NextEcmascriptClientReferenceTransition},
     next_config::NextConfig,
     next_dynamic::NextDynamicTransition,
-    next_manifests::{AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest},
+    next_manifests::{
+        AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest,
+        NextFontManifest,
+    },
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    nft_json::NftJsonAsset,
     util::NextRuntime,
 };
 use turbo_tasks::{TryJoinIterExt, Value, Vc};
 use turbopack_binding::{
-    turbo::tasks_fs::FileSystemPath,
+    turbo::tasks_fs::{DiskFileSystem, FileSystemPath},
     turbopack::{
         build::BuildChunkingContext,
         core::{
@@ -32,7 +36,7 @@ use turbopack_binding::{
             compile_time_info::CompileTimeInfo,
             file_source::FileSource,
             ident::AssetIdent,
-            output::OutputAsset,
+            output::{OutputAsset, OutputAssets},
         },
         ecmascript::chunk::EcmascriptChunkingContext,
         node::execution_context::ExecutionContext,
@@ -40,6 +44,8 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_build::{get_font_files, is_preloaded_font_file, is_size_adjusted_font_file};
+
 const ECMASCRIPT_CLIENT_TRANSITION_NAME: &str = "next-ecmascript-client-reference";
 
 #[turbo_tasks::value]
@@ -62,6 +68,7 @@ pub async fn get_app_entries(
     client_compile_time_info: Vc<CompileTimeInfo>,
     server_compile_time_info: Vc<CompileTimeInfo>,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
 ) -> Result<Vc<AppEntries>> {
     let app_dir = find_app_dir_if_enabled(project_root);
 
@@ -73,10 +80,10 @@ pub async fn get_app_entries(
         }));
     };
 
-    let entrypoints = get_entrypoints(app_dir, next_config.page_extensions());
-
     let mode = NextMode::Build;
 
+    let entrypoints = get_entrypoints(app_dir, next_config.page_extensions(), mode);
+
     let client_ty = Value::new(ClientContextType::App { app_dir });
 
     let rsc_ty: Value<ServerContextType> = Value::new(ServerContextType::AppRSC {
@@ -200,6 +207,8 @@ pub async fn get_app_entries(
                     page.clone(),
                     project_root,
                     next_config,
+                    build_id,
+                    mode,
                 ),
                 Entrypoint::AppRoute { page, path } => get_app_route_entry(
                     rsc_context,
@@ -257,17 +266,34 @@ pub async fn compute_app_entries_chunks(
     rsc_chunking_context: Vc<BuildChunkingContext>,
     client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ssr_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
+    project_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
+    server_compile_time_info: Vc<CompileTimeInfo>,
     client_relative_path: Vc<FileSystemPath>,
     app_paths_manifest_dir_path: &FileSystemPath,
     app_build_manifest: &mut AppBuildManifest,
     build_manifest: &mut BuildManifest,
     app_paths_manifest: &mut AppPathsManifest,
+    next_font_manifest: &mut NextFontManifest,
     all_chunks: &mut Vec<Vc<Box<dyn OutputAsset>>>,
     runtime: NextRuntime,
 ) -> Result<()> {
     let client_relative_path_ref = client_relative_path.await?;
 
+    // Like webpack, the font manifest is keyed by the path on disk of the entry
+    // module without its extension, which is what app-render looks up.
+    let app_dir_on_disk = match (
+        &*find_app_dir_if_enabled(project_root).await?,
+        Vc::try_resolve_downcast_type::<DiskFileSystem>(project_root.fs()).await?,
+    ) {
+        (Some(app_dir), Some(disk_fs)) => {
+            let mut app_dir_on_disk = PathBuf::from(&disk_fs.await?.root);
+            app_dir_on_disk.extend(app_dir.await?.path.split('/'));
+            Some(app_dir_on_disk)
+        }
+        _ => None,
+    };
+
     let app_client_shared_chunks = get_app_client_shared_chunks(
         AssetIdent::from_path(
             client_chunking_context
@@ -309,6 +335,13 @@ pub async fn compute_app_entries_chunks(
             app_entries.rsc_runtime_entries,
         );
         all_chunks.push(rsc_chunk);
+        all_chunks.push(Vc::upcast(NftJsonAsset::new(
+            rsc_chunk,
+            Vc::upcast(app_entry.rsc_entry),
+            project_root,
+            node_root,
+            server_compile_time_info,
+        )));
 
         let mut app_entry_client_chunks = vec![];
         // TODO(alexkirsz) In which manifest should this go?
@@ -344,6 +377,33 @@ pub async fn compute_app_entries_chunks(
             app_entry_client_chunks_paths,
         );
 
+        if let Some(app_dir_on_disk) = &app_dir_on_disk {
+            let font_files = get_font_files(
+                Vc::<OutputAssets>::cell(app_entry_client_chunks.clone()),
+                &client_relative_path_ref,
+            )
+            .await?;
+            next_font_manifest.app_using_size_adjust |= font_files
+                .iter()
+                .any(|font_file| is_size_adjusted_font_file(font_file));
+            if !font_files.is_empty() {
+                let mut entry_path = app_dir_on_disk.clone();
+                entry_path.extend(
+                    app_entry
+                        .original_name
+                        .split('/')
+                        .filter(|segment| !segment.is_empty()),
+                );
+                next_font_manifest.app.insert(
+                    entry_path.to_string_lossy().into_owned(),
+                    font_files
+                        .into_iter()
+                        .filter(|font_file| is_preloaded_font_file(font_file))
+                        .collect(),
+                );
+            }
+        }
+
         app_paths_manifest.node_server_app_paths.pages.insert(
             app_entry.original_name.clone(),
             app_paths_manifest_dir_path
@@ -361,6 +421,8 @@ pub async fn compute_app_entries_chunks(
             client_chunking_context,
             ssr_chunking_context,
             next_config.computed_asset_prefix(),
+            next_config.cross_origin(),
+            next_config.deployment_id_query(),
             runtime,
         );
 
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashSet},
     env::current_dir,
     path::{PathBuf, MAIN_SEPARATOR},
 };
@@ -7,26 +7,34 @@ use std::{
 use anyhow::{Context, Result};
 use dunce::canonicalize;
 use next_core::{
+    get_asset_path_from_pathname,
     mode::NextMode,
     next_app::get_app_client_references_chunks,
-    next_client::{get_client_chunking_context, get_client_compile_time_info},
+    next_client::{
+        get_client_chunking_context, get_client_compile_time_info, get_client_relative_path,
+    },
     next_client_reference::{ClientReferenceGraph, ClientReferenceType},
-    next_config::load_next_config,
+    next_config::{
+        load_headers, load_next_config, load_redirects, load_rewrites, normalize_dist_dir,
+        Redirect, RedirectStatus,
+    },
     next_dynamic::NextDynamicEntries,
+    next_edge::route_regex::{escape_string_regexp, get_named_route_regex, NamedRouteRegex},
     next_manifests::{
         AppBuildManifest, AppPathsManifest, BuildManifest, ClientBuildManifest, FontManifest,
-        MiddlewaresManifest, NextFontManifest, PagesManifest, ReactLoadableManifest,
-        ServerReferenceManifest,
+        InitialRevalidateSeconds, MiddlewaresManifest, NextFontManifest, PagesManifest,
+        PrerenderManifest, PrerenderManifestPreview, PrerenderManifestRoute, ReactLoadableManifest,
+        RoutesManifest, RoutesManifestDataRoute, RoutesManifestRoute, ServerReferenceManifest,
     },
     next_server::{get_server_chunking_context, get_server_compile_time_info},
-    url_node::get_sorted_routes,
+    url_node::{get_sorted_routes, is_dynamic_route},
     util::NextRuntime,
-    {self},
+    NextRevalidate, NextSegmentDynamic, {self},
 };
 use serde::Serialize;
 use turbo_tasks::{
     graph::{AdjacencyMap, GraphTraversal},
-    Completion, Completions, TransientInstance, TryJoinIterExt, Vc,
+    Completion, Completions, TransientInstance, TryFlatJoinIterExt, TryJoinIterExt, Vc,
 };
 use turbopack_binding::{
     turbo::tasks_fs::{rebase, DiskFileSystem, FileContent, FileSystem, FileSystemPath},
@@ -48,7 +56,7 @@ use turbopack_binding::{
 };
 
 use crate::{
-    build_options::{BuildContext, BuildOptions},
+    build_options::BuildOptions,
     next_app::app_entries::{compute_app_entries_chunks, get_app_entries},
     next_pages::page_entries::{compute_page_entries_chunks, get_page_entries},
 };
@@ -90,10 +98,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         log_level: options.log_level.unwrap_or(IssueSeverity::Warning),
     };
 
-    let dist_dir = options
-        .dist_dir
-        .as_ref()
-        .map_or_else(|| ".next".to_string(), |d| d.to_string());
+    let dist_dir = normalize_dist_dir(options.dist_dir.as_deref())?;
 
     let issue_reporter: Vc<Box<dyn IssueReporter>> =
         Vc::upcast(ConsoleUi::new(TransientInstance::new(log_options)));
@@ -133,6 +138,13 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
     let mode = NextMode::Build;
 
+    // The ID Next.js generated takes precedence, as it also writes it to the
+    // other build output.
+    let build_id = match &options.build_context {
+        Some(build_context) => Vc::cell(build_context.build_id.clone()),
+        None => next_config.build_id(mode),
+    };
+
     let client_define_env = Vc::cell(options.define_env.client.iter().cloned().collect());
     let client_compile_time_info =
         get_client_compile_time_info(browserslist_query, client_define_env);
@@ -151,6 +163,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         client_compile_time_info,
         server_compile_time_info,
         next_config,
+        build_id,
     );
 
     let app_entries = get_app_entries(
@@ -159,6 +172,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         client_compile_time_info,
         server_compile_time_info,
         next_config,
+        build_id,
     );
 
     handle_issues(
@@ -248,14 +262,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     // This ensures that the _next prefix is properly stripped from all client paths
     // in manifests. It will be added back on the client through the chunk_base_path
     // mechanism.
-    let next_config_ref = next_config.await?;
-    let client_relative_path = client_root.join(format!(
-        "{}/_next",
-        next_config_ref
-            .base_path
-            .clone()
-            .unwrap_or_else(|| "".to_string()),
-    ));
+    let client_relative_path = get_client_relative_path(client_root, next_config);
     let client_relative_path_ref = client_relative_path.await?;
 
     let client_chunking_context = get_client_chunking_context(
@@ -264,6 +271,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         next_config.computed_asset_prefix(),
         client_compile_time_info.environment(),
         mode,
+        next_config,
     );
 
     let server_chunking_context = get_server_chunking_context(
@@ -278,6 +286,8 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let mut build_manifest: BuildManifest = Default::default();
     let build_manifest_path = client_root.join("build-manifest.json".to_string());
 
+    let mut next_font_manifest = NextFontManifest::default();
+
     // PAGE CHUNKING
 
     let mut pages_manifest: PagesManifest = Default::default();
@@ -288,11 +298,14 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         &page_entries,
         client_chunking_context,
         server_chunking_context,
+        project_root,
         node_root,
+        server_compile_time_info,
         &pages_manifest_dir_path,
         &client_relative_path_ref,
         &mut pages_manifest,
         &mut build_manifest,
+        &mut next_font_manifest,
         &mut all_chunks,
     )
     .await?;
@@ -334,12 +347,15 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         server_chunking_context,
         client_chunking_context,
         Vc::upcast(server_chunking_context),
+        project_root,
         node_root,
+        server_compile_time_info,
         client_relative_path,
         &app_paths_manifest_dir_path,
         &mut app_build_manifest,
         &mut build_manifest,
         &mut app_paths_manifest,
+        &mut next_font_manifest,
         &mut all_chunks,
         // TODO(WEB-1824): add edge support
         NextRuntime::NodeJs,
@@ -348,79 +364,220 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
     let mut completions = vec![];
 
-    if let Some(build_context) = &options.build_context {
-        let BuildContext { build_id, rewrites } = build_context;
+    let build_id = &*build_id.await?;
+    completions.push(
+        node_root
+            .join("BUILD_ID".to_string())
+            .write(FileContent::Content(build_id.as_str().into()).cell()),
+    );
 
-        let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
+    // The manifests named after the build ID are written without Next.js
+    // providing the ID as well, so they always match the BUILD_ID file.
+    let loaded_rewrites;
+    let rewrites = match &options.build_context {
+        Some(build_context) => &build_context.rewrites,
+        None => {
+            loaded_rewrites = load_rewrites(execution_context).await?;
+            &*loaded_rewrites
+        }
+    };
 
-        let ssg_manifest_fs_path = node_root.join(ssg_manifest_path.clone());
-        completions.push(
-            ssg_manifest_fs_path.write(
-                FileContent::Content(
-                    "self.__SSG_MANIFEST=new Set;self.__SSG_MANIFEST_CB&&self.__SSG_MANIFEST_CB()"
-                        .into(),
-                )
-                .cell(),
-            ),
-        );
+    let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
 
-        build_manifest.low_priority_files.push(ssg_manifest_path);
+    let ssg_manifest_fs_path = node_root.join(ssg_manifest_path.clone());
+    completions.push(
+        ssg_manifest_fs_path.write(
+            FileContent::Content(
+                "self.__SSG_MANIFEST=new Set;self.__SSG_MANIFEST_CB&&self.__SSG_MANIFEST_CB()"
+                    .into(),
+            )
+            .cell(),
+        ),
+    );
 
-        let sorted_pages =
-            get_sorted_routes(&pages_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
+    build_manifest.low_priority_files.push(ssg_manifest_path);
 
-        let app_dependencies: HashSet<&str> = pages_manifest
-            .pages
-            .get("/_app")
-            .iter()
-            .map(|s| s.as_str())
-            .collect();
-        let mut pages = HashMap::new();
+    let sorted_pages =
+        get_sorted_routes(&pages_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
 
-        for page in &sorted_pages {
-            if page == "_app" {
-                continue;
-            }
+    let app_dependencies: HashSet<&str> = pages_manifest
+        .pages
+        .get("/_app")
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+    let mut pages = BTreeMap::new();
 
-            let dependencies = pages_manifest
-                .pages
-                .get(page)
-                .iter()
-                .map(|dep| dep.as_str())
-                .filter(|dep| !app_dependencies.contains(*dep))
-                .collect::<Vec<_>>();
+    for page in &sorted_pages {
+        if page == "_app" {
+            continue;
+        }
 
-            if !dependencies.is_empty() {
-                pages.insert(page.to_string(), dependencies);
-            }
+        let dependencies = pages_manifest
+            .pages
+            .get(page)
+            .iter()
+            .map(|dep| dep.as_str())
+            .filter(|dep| !app_dependencies.contains(*dep))
+            .collect::<Vec<_>>();
+
+        if !dependencies.is_empty() {
+            pages.insert(page.to_string(), dependencies);
         }
+    }
 
-        let client_manifest = ClientBuildManifest {
-            rewrites,
-            sorted_pages: &sorted_pages,
-            pages,
-        };
+    let client_manifest = ClientBuildManifest {
+        rewrites,
+        sorted_pages: &sorted_pages,
+        pages,
+    };
 
-        let client_manifest_path = format!("static/{build_id}/_buildManifest.js");
-
-        let client_manifest_fs_path = node_root.join(client_manifest_path.clone());
-        completions.push(
-            client_manifest_fs_path.write(
-                FileContent::Content(
-                    format!(
-                        "self.__BUILD_MANIFEST={};self.__BUILD_MANIFEST_CB && \
-                         self.__BUILD_MANIFEST_CB()",
-                        StringifyJs(&client_manifest)
-                    )
-                    .into(),
+    let client_manifest_path = format!("static/{build_id}/_buildManifest.js");
+
+    let client_manifest_fs_path = node_root.join(client_manifest_path.clone());
+    completions.push(
+        client_manifest_fs_path.write(
+            FileContent::Content(
+                format!(
+                    "self.__BUILD_MANIFEST={};self.__BUILD_MANIFEST_CB && \
+                     self.__BUILD_MANIFEST_CB()",
+                    StringifyJs(&client_manifest)
                 )
-                .cell(),
-            ),
-        );
+                .into(),
+            )
+            .cell(),
+        ),
+    );
 
-        build_manifest.low_priority_files.push(client_manifest_path);
+    build_manifest.low_priority_files.push(client_manifest_path);
+
+    let app_paths = app_entries
+        .entries
+        .iter()
+        .map(|entry| async move { Ok(entry.await?.pathname.clone()) })
+        .try_join()
+        .await?;
+    let sorted_routes = get_sorted_routes(
+        &pages_manifest
+            .pages
+            .keys()
+            .cloned()
+            .chain(app_paths)
+            .collect::<Vec<_>>(),
+    )?;
+    let mut static_routes = vec![];
+    let mut dynamic_routes = vec![];
+    for route in &sorted_routes {
+        if is_dynamic_route(route) {
+            dynamic_routes.push(routes_manifest_route(route));
+        } else if !is_reserved_page(route) {
+            static_routes.push(routes_manifest_route(route));
+        }
     }
 
+    let data_pages = page_entries
+        .entries
+        .iter()
+        .map(|entry| async move {
+            let entry = entry.await?;
+            Ok(if *entry.has_data.await? {
+                Some(entry.pathname.await?.clone_value())
+            } else {
+                None
+            })
+        })
+        .try_flat_join()
+        .await?;
+    let data_routes = get_sorted_routes(&data_pages)?
+        .iter()
+        .map(|page| routes_manifest_data_route(page, build_id))
+        .collect();
+
+    let next_config_ref = next_config.await?;
+    let rewrites = load_rewrites(execution_context).await?;
+    let headers = load_headers(execution_context).await?;
+    let redirects = load_redirects(execution_context).await?;
+    let routes_manifest = RoutesManifest {
+        version: 3,
+        pages404: true,
+        case_sensitive: next_config_ref
+            .experimental
+            .case_sensitive_routes
+            .unwrap_or(false),
+        base_path: next_config_ref.base_path.as_deref().unwrap_or_default(),
+        redirects: redirects
+            .iter()
+            .map(|redirect| Redirect {
+                status: RedirectStatus::StatusCode(redirect.status.status_code()),
+                ..redirect.clone()
+            })
+            .collect(),
+        headers: &headers,
+        rewrites: (&*rewrites).into(),
+        static_routes,
+        dynamic_routes,
+        data_routes,
+        i18n: next_config_ref.i18n.as_ref(),
+        rsc: Default::default(),
+        skip_middleware_url_normalize: next_config_ref.skip_middleware_url_normalize,
+    };
+
+    completions.push(write_manifest(
+        routes_manifest,
+        node_root.join("routes-manifest.json".to_string()),
+    )?);
+
+    // The app pages without dynamic segments are prerendered, and revalidate
+    // as often as the segment configs of their layouts and pages allow.
+    let prerender_routes = app_entries
+        .entries
+        .iter()
+        .map(|entry| async move {
+            let entry = entry.await?;
+            if !entry.original_name.ends_with("/page") || is_dynamic_route(&entry.pathname) {
+                return Ok(None);
+            }
+            let config = entry.config.await?;
+            if config.dynamic == Some(NextSegmentDynamic::ForceDynamic) {
+                return Ok(None);
+            }
+            let initial_revalidate_seconds = match config.revalidate {
+                Some(NextRevalidate::Frequency { seconds: 0 }) => return Ok(None),
+                Some(NextRevalidate::Frequency { seconds }) => {
+                    InitialRevalidateSeconds::Seconds(seconds)
+                }
+                Some(NextRevalidate::Never | NextRevalidate::ForceCache) | None => {
+                    InitialRevalidateSeconds::Never
+                }
+            };
+            let data_route = match entry.pathname.as_str() {
+                "/" => "/index.rsc".to_string(),
+                pathname => format!("{pathname}.rsc"),
+            };
+            Ok(Some((
+                entry.pathname.clone(),
+                PrerenderManifestRoute {
+                    initial_revalidate_seconds,
+                    src_route: Some(entry.pathname.clone()),
+                    data_route: Some(data_route),
+                },
+            )))
+        })
+        .try_flat_join()
+        .await?;
+    completions.push(write_manifest(
+        PrerenderManifest {
+            version: 4,
+            routes: prerender_routes.into_iter().collect(),
+            dynamic_routes: Default::default(),
+            not_found_routes: vec![],
+            preview: match &options.build_context {
+                Some(build_context) => build_context.preview_props.clone(),
+                None => PrerenderManifestPreview::generate(),
+            },
+        },
+        node_root.join("prerender-manifest.json".to_string()),
+    )?);
     completions.push(write_manifest(pages_manifest, pages_manifest_path)?);
     completions.push(write_manifest(app_build_manifest, app_build_manifest_path)?);
     completions.push(write_manifest(app_paths_manifest, app_paths_manifest_path)?);
@@ -435,7 +592,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         node_root.join("server/middleware-manifest.json".to_string()),
     )?);
     completions.push(write_manifest(
-        NextFontManifest::default(),
+        next_font_manifest,
         node_root.join("server/next-font-manifest.json".to_string()),
     )?);
     completions.push(write_manifest(
@@ -584,6 +741,48 @@ async fn all_assets_from_entries(entries: Vc<OutputAssets>) -> Result<Vc<OutputA
     ))
 }
 
+/// Returns the paths, relative to `client_relative_path`, of the font files
+/// referenced by the given client chunks, e.g. from the CSS `next/font`
+/// generates.
+pub(crate) async fn get_font_files(
+    client_chunks: Vc<OutputAssets>,
+    client_relative_path: &FileSystemPath,
+) -> Result<Vec<String>> {
+    let mut font_files = vec![];
+    for asset in all_assets_from_entries(client_chunks).await?.iter() {
+        let asset_path = asset.ident().path().await?;
+        if !asset_path
+            .extension_ref()
+            .is_some_and(|ext| ["woff", "woff2", "eot", "ttf", "otf"].contains(&ext))
+        {
+            continue;
+        }
+        if let Some(asset_path) = client_relative_path.get_path_to(&asset_path) {
+            font_files.push(asset_path.to_string());
+        }
+    }
+    Ok(font_files)
+}
+
+/// Whether the font file should be preloaded, i.e. whether `next/font` marked
+/// its name with `.p`. Unlike webpack, the content hash is placed between the
+/// marker and the extension, e.g. `6a1b2c3d.p.4e5f6a7b.woff2`.
+pub(crate) fn is_preloaded_font_file(font_file: &str) -> bool {
+    font_file_name(font_file).contains(".p.")
+}
+
+/// Whether the font file uses an adjusted fallback font, i.e. whether
+/// `next/font` marked its name with `-s`.
+pub(crate) fn is_size_adjusted_font_file(font_file: &str) -> bool {
+    font_file_name(font_file).contains("-s")
+}
+
+fn font_file_name(font_file: &str) -> &str {
+    font_file
+        .rsplit_once('/')
+        .map_or(font_file, |(_, file_name)| file_name)
+}
+
 /// Computes the list of all chunk children of a given chunk.
 async fn get_referenced_assets(
     asset: Vc<Box<dyn OutputAsset>>,
@@ -597,6 +796,63 @@ async fn get_referenced_assets(
         .into_iter())
 }
 
+/// Whether `page` is a page which isn't routed to, like `isReservedPage`.
+fn is_reserved_page(page: &str) -> bool {
+    ["/_app", "/_error", "/_document"]
+        .iter()
+        .any(|reserved| page.starts_with(reserved))
+        || page == "/api"
+        || page.starts_with("/api/")
+}
+
+/// The route of `page` in the routes manifest, like `pageToRoute`.
+fn routes_manifest_route(page: &str) -> RoutesManifestRoute {
+    let NamedRouteRegex {
+        regex,
+        named_regex,
+        route_keys,
+    } = get_named_route_regex(page, true);
+    RoutesManifestRoute {
+        page: page.to_string(),
+        regex: regex.regex,
+        route_keys: route_keys.into_iter().collect(),
+        // The server compiles the regex in JavaScript, which doesn't support
+        // the `(?P<name>)` syntax for named groups.
+        named_regex: named_regex.replace("(?P<", "(?<"),
+    }
+}
+
+/// The data route of a page with `getStaticProps` or `getServerSideProps`,
+/// like `buildDataRoute`.
+fn routes_manifest_data_route(page: &str, build_id: &str) -> RoutesManifestDataRoute {
+    let page_path = get_asset_path_from_pathname(page, "");
+    if !is_dynamic_route(page) {
+        return RoutesManifestDataRoute {
+            page: page.to_string(),
+            data_route_regex: format!(
+                "^/_next/data/{}{page_path}.json$",
+                escape_string_regexp(build_id)
+            ),
+            route_keys: None,
+            named_data_route_regex: None,
+        };
+    }
+
+    let NamedRouteRegex {
+        regex,
+        named_regex,
+        route_keys,
+    } = get_named_route_regex(&format!("/_next/data/{build_id}{page_path}"), true);
+    let with_json_suffix =
+        |regex: &str| format!(r"{}\.json$", regex.strip_suffix("(?:/)?$").unwrap_or(regex));
+    RoutesManifestDataRoute {
+        page: page.to_string(),
+        data_route_regex: with_json_suffix(&regex.regex),
+        route_keys: Some(route_keys.into_iter().collect()),
+        named_data_route_regex: Some(with_json_suffix(&named_regex).replace("(?P<", "(?<")),
+    }
+}
+
 /// Writes a manifest to disk. This consumes the manifest to ensure we don't
 /// write to it afterwards.
 fn write_manifest<T>(manifest: T, manifest_path: Vc<FileSystemPath>) -> Result<Vc<Completion>>
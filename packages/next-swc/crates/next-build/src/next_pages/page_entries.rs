@@ -8,12 +8,13 @@ use next_core::{
     },
     next_config::NextConfig,
     next_dynamic::NextDynamicTransition,
-    next_manifests::{BuildManifest, PagesManifest},
+    next_manifests::{BuildManifest, NextFontManifest, PagesManifest},
     next_pages::create_page_ssr_entry_module,
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
         get_server_runtime_entries, ServerContextType,
     },
+    nft_json::NftJsonAsset,
     pages_structure::{
         find_pages_structure, PagesDirectoryStructure, PagesStructure, PagesStructureItem,
     },
@@ -37,7 +38,7 @@ use turbopack_binding::{
             source::Source,
         },
         ecmascript::{
-            chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext},
+            chunk::{EcmascriptChunkPlaceable, EcmascriptChunkingContext, EcmascriptExports},
             EcmascriptModuleAsset,
         },
         node::execution_context::ExecutionContext,
@@ -45,6 +46,8 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_build::{get_font_files, is_preloaded_font_file, is_size_adjusted_font_file};
+
 #[turbo_tasks::value]
 pub struct PageEntries {
     pub entries: Vec<Vc<PageEntry>>,
@@ -61,6 +64,7 @@ pub async fn get_page_entries(
     client_compile_time_info: Vc<CompileTimeInfo>,
     server_compile_time_info: Vc<CompileTimeInfo>,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
 ) -> Result<Vc<PageEntries>> {
     let pages_structure = find_pages_structure(
         project_root,
@@ -162,6 +166,7 @@ pub async fn get_page_entries(
         project_root,
         next_router_root,
         next_config,
+        build_id,
     )
     .await?;
 
@@ -180,6 +185,7 @@ async fn get_page_entries_for_root_directory(
     project_root: Vc<FileSystemPath>,
     next_router_root: Vc<FileSystemPath>,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
 ) -> Result<Vec<Vc<PageEntry>>> {
     let PagesStructure {
         app,
@@ -203,6 +209,7 @@ async fn get_page_entries_for_root_directory(
         app.original_path,
         PathType::PagesPage,
         next_config,
+        build_id,
     ));
 
     // This only makes sense on the server.
@@ -217,6 +224,7 @@ async fn get_page_entries_for_root_directory(
         document.original_path,
         PathType::PagesPage,
         next_config,
+        build_id,
     ));
 
     // This only makes sense on both the client and the server, but they should map
@@ -232,6 +240,7 @@ async fn get_page_entries_for_root_directory(
         error.original_path,
         PathType::PagesPage,
         next_config,
+        build_id,
     ));
 
     if let Some(api) = api {
@@ -244,6 +253,7 @@ async fn get_page_entries_for_root_directory(
             &mut entries,
             PathType::PagesApi,
             next_config,
+            build_id,
         )
         .await?;
     }
@@ -258,6 +268,7 @@ async fn get_page_entries_for_root_directory(
             &mut entries,
             PathType::PagesPage,
             next_config,
+            build_id,
         )
         .await?;
     }
@@ -275,6 +286,7 @@ async fn get_page_entries_for_directory(
     entries: &mut Vec<Vc<PageEntry>>,
     path_type: PathType,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
 ) -> Result<()> {
     let PagesDirectoryStructure {
         ref items,
@@ -298,6 +310,7 @@ async fn get_page_entries_for_directory(
             original_path,
             path_type,
             next_config,
+            build_id,
         ));
     }
 
@@ -311,6 +324,7 @@ async fn get_page_entries_for_directory(
             entries,
             path_type,
             next_config,
+            build_id,
         )
         .await?;
     }
@@ -327,6 +341,9 @@ pub struct PageEntry {
     pub ssr_module: Vc<Box<dyn EcmascriptChunkPlaceable>>,
     /// The client entry module asset.
     pub client_module: Vc<EcmascriptModuleAsset>,
+    /// Whether the page exports `getStaticProps` or `getServerSideProps`, so it
+    /// has a data route.
+    pub has_data: Vc<bool>,
 }
 
 #[turbo_tasks::function]
@@ -340,6 +357,7 @@ async fn get_page_entry_for_file(
     next_original_path: Vc<FileSystemPath>,
     path_type: PathType,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
 ) -> Result<Vc<PageEntry>> {
     let reference_type = Value::new(ReferenceType::Entry(match path_type {
         PathType::PagesPage => EntryReferenceSubType::Page,
@@ -350,6 +368,13 @@ async fn get_page_entry_for_file(
     let pathname = pathname_for_path(next_router_root, next_router_path, path_type);
     let original_name = next_original_path.await?.path.clone();
 
+    let has_data = match path_type {
+        PathType::PagesPage => {
+            has_data_exports(ssr_module_context.process(source, reference_type.clone()))
+        }
+        _ => Vc::cell(false),
+    };
+
     let ssr_module = create_page_ssr_entry_module(
         pathname,
         reference_type,
@@ -359,6 +384,8 @@ async fn get_page_entry_for_file(
         Vc::cell(original_name),
         NextRuntime::NodeJs,
         next_config,
+        build_id,
+        NextMode::Build,
     );
 
     let client_module = create_page_loader_entry_module(client_module_context, source, pathname);
@@ -373,10 +400,27 @@ async fn get_page_entry_for_file(
         pathname,
         ssr_module,
         client_module,
+        has_data,
     }
     .cell())
 }
 
+/// Whether a page module exports `getStaticProps` or `getServerSideProps`.
+#[turbo_tasks::function]
+async fn has_data_exports(module: Vc<Box<dyn Module>>) -> Result<Vc<bool>> {
+    let Some(module) = Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await? else {
+        return Ok(Vc::cell(false));
+    };
+    let EcmascriptExports::EsmExports(exports) = &*module.get_exports().await? else {
+        return Ok(Vc::cell(false));
+    };
+    let exports = exports.await?;
+    Ok(Vc::cell(
+        exports.exports.contains_key("getStaticProps")
+            || exports.exports.contains_key("getServerSideProps"),
+    ))
+}
+
 /// Computes the pathname for a given path.
 #[turbo_tasks::function]
 async fn pathname_from_path(next_router_path: Vc<FileSystemPath>) -> Result<Vc<String>> {
@@ -390,11 +434,14 @@ pub async fn compute_page_entries_chunks(
     page_entries: &PageEntries,
     client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     ssr_chunking_context: Vc<BuildChunkingContext>,
+    project_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
+    server_compile_time_info: Vc<CompileTimeInfo>,
     pages_manifest_dir_path: &FileSystemPath,
     client_relative_path: &FileSystemPath,
     pages_manifest: &mut PagesManifest,
     build_manifest: &mut BuildManifest,
+    next_font_manifest: &mut NextFontManifest,
     all_chunks: &mut Vec<Vc<Box<dyn OutputAsset>>>,
 ) -> Result<()> {
     for page_entry in page_entries.entries.iter() {
@@ -408,6 +455,13 @@ pub async fn compute_page_entries_chunks(
             page_entries.ssr_runtime_entries,
         );
         all_chunks.push(ssr_entry_chunk);
+        all_chunks.push(Vc::upcast(NftJsonAsset::new(
+            ssr_entry_chunk,
+            Vc::upcast(page_entry.ssr_module),
+            project_root,
+            node_root,
+            server_compile_time_info,
+        )));
 
         let chunk_path = ssr_entry_chunk.ident().path().await?;
         if let Some(asset_path) = pages_manifest_dir_path.get_path_to(&chunk_path) {
@@ -435,6 +489,22 @@ pub async fn compute_page_entries_chunks(
                 build_manifest_pages_entry.push(asset_path.to_string());
             }
         }
+
+        let font_files = get_font_files(client_chunks, client_relative_path).await?;
+        next_font_manifest.pages_using_size_adjust |= font_files
+            .iter()
+            .any(|font_file| is_size_adjusted_font_file(font_file));
+        // Like webpack, an entry is added even if no font file should be
+        // preloaded, in which case a preconnect tag is rendered instead.
+        if !font_files.is_empty() {
+            next_font_manifest.pages.insert(
+                pathname.clone_value(),
+                font_files
+                    .into_iter()
+                    .filter(|font_file| is_preloaded_font_file(font_file))
+                    .collect(),
+            );
+        }
     }
     Ok(())
 }
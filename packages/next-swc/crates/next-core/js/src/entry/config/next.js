@@ -1,5 +1,9 @@
 import loadConfig from 'next/dist/server/config'
-import loadCustomRoutes from 'next/dist/lib/load-custom-routes'
+import loadCustomRoutes, {
+  normalizeRouteRegex,
+} from 'next/dist/lib/load-custom-routes'
+import { modifyRouteRegex } from 'next/dist/lib/redirect-status'
+import { pathToRegexp } from 'next/dist/compiled/path-to-regexp'
 import { PHASE_DEVELOPMENT_SERVER } from 'next/dist/shared/lib/constants'
 import assert from 'node:assert'
 
@@ -15,6 +19,7 @@ const loadNextConfig = async (silent) => {
   nextConfig.generateBuildId = await nextConfig.generateBuildId?.()
 
   const customRoutes = await loadCustomRoutes(nextConfig)
+  compileCustomRoutes(customRoutes, nextConfig)
 
   // TODO: these functions takes arguments, have to be supported in a different way
   nextConfig.exportPathMap = nextConfig.exportPathMap && {}
@@ -53,6 +58,34 @@ const loadNextConfig = async (silent) => {
 
 export { loadNextConfig as default }
 
+// Adds the regex matching the `source` of every route, like `buildCustomRoute`
+// does for the routes-manifest of the webpack build.
+function compileCustomRoutes(customRoutes, nextConfig) {
+  const restrictedRedirectPaths = [`${nextConfig.basePath}/_next`]
+  const compile = (route, restrictedPaths) => {
+    let regex = pathToRegexp(route.source, [], {
+      strict: true,
+      sensitive: false,
+      delimiter: '/', // default is `/#?`, but Next does not pass query info
+    }).source
+    if (!route.internal) {
+      regex = modifyRouteRegex(regex, restrictedPaths)
+    }
+    route.regex = normalizeRouteRegex(regex)
+  }
+
+  for (const route of customRoutes.headers) {
+    compile(route)
+  }
+  for (const route of customRoutes.redirects) {
+    compile(route, restrictedRedirectPaths)
+  }
+  const { beforeFiles, afterFiles, fallback } = customRoutes.rewrites
+  for (const route of [...beforeFiles, ...afterFiles, ...fallback]) {
+    compile(route)
+  }
+}
+
 function ensureLoadersHaveSerializableOptions(turbopackLoaders) {
   for (const [ext, loaderItems] of Object.entries(turbopackLoaders)) {
     for (const loaderItem of loaderItems) {
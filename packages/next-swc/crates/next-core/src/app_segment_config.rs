@@ -58,6 +58,41 @@ pub enum NextRevalidate {
     },
 }
 
+impl NextRevalidate {
+    /// Returns the revalidate value that results in the most frequent
+    /// revalidation, as the lowest revalidate period of all segments of a
+    /// route determines the revalidate period of the whole route.
+    pub fn min(self, other: Self) -> Self {
+        match (self, other) {
+            (
+                NextRevalidate::Frequency { seconds: a },
+                NextRevalidate::Frequency { seconds: b },
+            ) => NextRevalidate::Frequency { seconds: a.min(b) },
+            (NextRevalidate::Frequency { .. }, _) => self,
+            (_, NextRevalidate::Frequency { .. }) => other,
+            _ => self,
+        }
+    }
+
+    /// Merges the revalidate values of two segments of a route. Unlike the
+    /// other options, segments may disagree on it, and the lowest one wins.
+    fn merge(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Formats the value like it's exported from a segment.
+    fn to_export_value(self) -> String {
+        match self {
+            NextRevalidate::Never => "false".to_string(),
+            NextRevalidate::ForceCache => "'force-cache'".to_string(),
+            NextRevalidate::Frequency { seconds } => seconds.to_string(),
+        }
+    }
+}
+
 #[turbo_tasks::value]
 #[derive(Debug, Default)]
 pub struct NextSegmentConfig {
@@ -91,7 +126,7 @@ impl NextSegmentConfig {
         } = self;
         *dynamic = dynamic.or(parent.dynamic);
         *dynamic_params = dynamic_params.or(parent.dynamic_params);
-        *revalidate = revalidate.or(parent.revalidate);
+        *revalidate = NextRevalidate::merge(*revalidate, parent.revalidate);
         *fetch_cache = fetch_cache.or(parent.fetch_cache);
         *runtime = runtime.or(parent.runtime);
         *preferred_region = preferred_region.take().or(parent.preferred_region.clone());
@@ -135,7 +170,9 @@ impl NextSegmentConfig {
             &parallel_config.dynamic_params,
             "dynamicParams",
         )?;
-        merge_parallel(revalidate, &parallel_config.revalidate, "revalidate")?;
+        // Sibling segments may disagree on `revalidate`, the route then
+        // revalidates as often as its most frequently revalidated segment.
+        *revalidate = NextRevalidate::merge(*revalidate, parallel_config.revalidate);
         merge_parallel(fetch_cache, &parallel_config.fetch_cache, "fetchCache")?;
         merge_parallel(runtime, &parallel_config.runtime, "runtime")?;
         merge_parallel(
@@ -147,19 +184,94 @@ impl NextSegmentConfig {
     }
 }
 
+/// Reported when segments of a route export different `revalidate` values.
+/// The lowest one is used for the whole route, which overrides the others.
+#[turbo_tasks::value(shared)]
+struct NextSegmentRevalidateConflictIssue {
+    path: Vc<FileSystemPath>,
+    revalidate: NextRevalidate,
+    overridden: NextRevalidate,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NextSegmentRevalidateConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell("Segments of the route export conflicting `revalidate` values".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The route is revalidated with `revalidate = {}`, which overrides `revalidate = {}` \
+             of another of its segments, as the lowest value is used for the whole route.",
+            self.revalidate.to_export_value(),
+            self.overridden.to_export_value(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell(
+            "https://nextjs.org/docs/app/api-reference/file-conventions/route-segment-config#revalidate"
+                .to_string(),
+        )
+    }
+}
+
+/// Reports the `revalidate` of a segment conflicting with the one of the
+/// segments merged into the config so far. `path` is the file declaring it,
+/// or the layout of the parallel routes disagreeing on it.
+fn report_revalidate_conflict(
+    path: Vc<FileSystemPath>,
+    merged: Option<NextRevalidate>,
+    revalidate: Option<NextRevalidate>,
+) {
+    let (Some(merged), Some(revalidate)) = (merged, revalidate) else {
+        return;
+    };
+    if merged == revalidate {
+        return;
+    }
+    let winner = merged.min(revalidate);
+    NextSegmentRevalidateConflictIssue {
+        path,
+        revalidate: winner,
+        overridden: if winner == merged { revalidate } else { merged },
+    }
+    .cell()
+    .emit();
+}
+
 /// An issue that occurred while parsing the app segment config.
 #[turbo_tasks::value(shared)]
 pub struct NextSegmentConfigParsingIssue {
     ident: Vc<AssetIdent>,
     detail: Vc<String>,
     source: Vc<IssueSource>,
+    severity: Vc<IssueSeverity>,
 }
 
 #[turbo_tasks::value_impl]
 impl Issue for NextSegmentConfigParsingIssue {
     #[turbo_tasks::function]
     fn severity(&self) -> Vc<IssueSeverity> {
-        IssueSeverity::Warning.into()
+        self.severity
     }
 
     #[turbo_tasks::function]
@@ -280,16 +392,22 @@ fn parse_config_value(
     eval_context: &EvalContext,
 ) {
     let span = init.span();
-    let invalid_config = |detail: &str, value: &JsValue| {
+    let report = |severity: IssueSeverity, detail: &str, value: &JsValue| {
         let (explainer, hints) = value.explain(2, 0);
         NextSegmentConfigParsingIssue {
             ident: source.ident(),
             detail: Vc::cell(format!("{detail} Got {explainer}.{hints}")),
             source: issue_source(source, span),
+            severity: severity.into(),
         }
         .cell()
         .emit();
     };
+    let invalid_config =
+        |detail: &str, value: &JsValue| report(IssueSeverity::Warning, detail, value);
+    // An unknown runtime would silently fall back to Node.js, so it's an error.
+    let invalid_runtime =
+        |detail: &str, value: &JsValue| report(IssueSeverity::Error, detail, value);
 
     match &*ident.sym {
         "dynamic" => {
@@ -358,14 +476,14 @@ fn parse_config_value(
         "runtime" => {
             let value = eval_context.eval(init);
             let Some(val) = value.as_str() else {
-                invalid_config("`runtime` needs to be a static string", &value);
+                invalid_runtime("`runtime` needs to be a static string", &value);
                 return;
             };
 
             config.runtime = match serde_json::from_value(Value::String(val.to_string())) {
                 Ok(runtime) => Some(runtime),
                 Err(err) => {
-                    invalid_config(&format!("`runtime` has an invalid value: {}", err), &value);
+                    invalid_runtime(&format!("`runtime` has an invalid value: {}", err), &value);
                     return;
                 }
             };
@@ -422,7 +540,11 @@ pub async fn parse_segment_config_from_loader_tree(
         .map(parse_segment_config_from_loader_tree)
         .try_join()
         .await?;
+    let segment_path = components.layout.or(components.page).or(components.default);
     for tree in parallel_configs {
+        if let Some(segment_path) = segment_path {
+            report_revalidate_conflict(segment_path, config.revalidate, tree.revalidate);
+        }
         config.apply_parallel_config(&tree)?;
     }
     for component in [components.page, components.default, components.layout]
@@ -430,7 +552,9 @@ pub async fn parse_segment_config_from_loader_tree(
         .flatten()
     {
         let source = Vc::upcast(FileSource::new(component));
-        config.apply_parent_config(&*parse_segment_config_from_source(source).await?);
+        let component_config = parse_segment_config_from_source(source).await?;
+        report_revalidate_conflict(component, config.revalidate, component_config.revalidate);
+        config.apply_parent_config(&component_config);
     }
 
     Ok(config.cell())
@@ -17,6 +17,7 @@ use turbopack_binding::{
 };
 
 use crate::{
+    mode::NextMode,
     next_app::{
         metadata::{
             match_global_metadata_file, match_local_metadata_file, normalize_metadata_route,
@@ -627,11 +628,13 @@ fn add_app_metadata_route(
 pub fn get_entrypoints(
     app_dir: Vc<FileSystemPath>,
     page_extensions: Vc<Vec<String>>,
+    mode: NextMode,
 ) -> Vc<Entrypoints> {
     directory_tree_to_entrypoints(
         app_dir,
         get_directory_tree(app_dir, page_extensions),
         get_global_metadata(app_dir, page_extensions),
+        mode,
     )
 }
 
@@ -640,6 +643,7 @@ fn directory_tree_to_entrypoints(
     app_dir: Vc<FileSystemPath>,
     directory_tree: Vc<DirectoryTree>,
     global_metadata: Vc<GlobalMetadata>,
+    mode: NextMode,
 ) -> Vc<Entrypoints> {
     directory_tree_to_entrypoints_internal(
         app_dir,
@@ -647,6 +651,7 @@ fn directory_tree_to_entrypoints(
         "".to_string(),
         directory_tree,
         AppPage::new(),
+        mode,
     )
 }
 
@@ -862,6 +867,7 @@ async fn directory_tree_to_entrypoints_internal(
     directory_name: String,
     directory_tree: Vc<DirectoryTree>,
     app_page: AppPage,
+    mode: NextMode,
 ) -> Result<Vc<Entrypoints>> {
     let mut result = IndexMap::new();
 
@@ -951,7 +957,7 @@ async fn directory_tree_to_entrypoints_internal(
         // Next.js has this logic in "collect-app-paths", where the root not-found page
         // is considered as its own entry point.
         if let Some(_not_found) = components.not_found {
-            let dev_not_found_tree = LoaderTree {
+            let not_found_tree = LoaderTree {
                 page: app_page.clone(),
                 segment: directory_name.clone(),
                 parallel_routes: indexmap! {
@@ -973,13 +979,15 @@ async fn directory_tree_to_entrypoints_internal(
             }
             .cell();
 
-            {
+            // `/not-found` is only routable during development. Builds only emit
+            // `/_not-found`, which is prerendered into the global 404 page.
+            if mode == NextMode::Development {
                 let app_page = app_page.clone_push_str("not-found")?;
-                add_app_page(app_dir, &mut result, app_page, dev_not_found_tree).await?;
+                add_app_page(app_dir, &mut result, app_page, not_found_tree).await?;
             }
             {
                 let app_page = app_page.clone_push_str("_not-found")?;
-                add_app_page(app_dir, &mut result, app_page, dev_not_found_tree).await?;
+                add_app_page(app_dir, &mut result, app_page, not_found_tree).await?;
             }
         } else {
             // Create default not-found page for production if there's no customized
@@ -1028,6 +1036,7 @@ async fn directory_tree_to_entrypoints_internal(
             subdir_name.to_string(),
             subdirectory,
             child_app_page.clone(),
+            mode,
         )
         .await?;
 
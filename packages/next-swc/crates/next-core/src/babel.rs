@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde_json::Value as JsonValue;
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::{
     turbo::tasks_fs::{FileSystemEntryType, FileSystemPath},
@@ -16,6 +17,8 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_config::{NextConfig, ReactCompilerConfig};
+
 const BABEL_CONFIG_FILES: &[&str] = &[
     ".babelrc",
     ".babelrc.json",
@@ -117,13 +120,91 @@ pub async fn maybe_add_babel_loader(
     Ok(Vc::cell(webpack_rules))
 }
 
+/// If `experimental.reactCompiler` is enabled, adds a loader running
+/// `babel-plugin-react-compiler` to each file type which can contain React
+/// components. Modules opt out with a `'use no memo'` directive, and the
+/// diagnostics of the compiler are reported as warnings.
+#[turbo_tasks::function]
+pub async fn maybe_add_react_compiler_loader(
+    project_root: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+    webpack_rules: Option<Vc<WebpackRules>>,
+) -> Result<Vc<OptionWebpackRules>> {
+    let compiler_options = match &next_config.await?.experimental.react_compiler {
+        Some(ReactCompilerConfig::Boolean(true)) => serde_json::Map::new(),
+        Some(ReactCompilerConfig::Options(JsonValue::Object(options))) => options.clone(),
+        Some(ReactCompilerConfig::Boolean(false) | ReactCompilerConfig::Options(_)) | None => {
+            return Ok(Vc::cell(webpack_rules));
+        }
+    };
+
+    if !*is_package_available(project_root, "babel-plugin-react-compiler".to_string()).await? {
+        BabelIssue {
+            path: project_root,
+            title: Vc::cell(
+                "Unable to resolve babel-plugin-react-compiler, but experimental.reactCompiler is \
+                 enabled"
+                    .to_owned(),
+            ),
+            description: Vc::cell(
+                "Make sure babel-plugin-react-compiler is installed via your package manager."
+                    .to_owned(),
+            ),
+            severity: IssueSeverity::Fatal.cell(),
+        }
+        .cell()
+        .emit();
+    }
+
+    let mut loader_options = serde_json::Map::new();
+    loader_options.insert(
+        "compilerOptions".to_string(),
+        JsonValue::Object(compiler_options),
+    );
+
+    let mut rules = if let Some(webpack_rules) = webpack_rules {
+        webpack_rules.await?.clone_value()
+    } else {
+        Default::default()
+    };
+    for pattern in ["*.js", "*.jsx", "*.ts", "*.tsx"] {
+        let loader = WebpackLoaderItem {
+            loader: "next/dist/build/webpack/loaders/next-react-compiler-loader".to_string(),
+            options: loader_options.clone(),
+        };
+        if let Some(rule) = rules.get_mut(pattern) {
+            let mut loaders = rule.loaders.await?.clone_value();
+            loaders.push(loader);
+            rule.loaders = Vc::cell(loaders);
+        } else {
+            rules.insert(
+                pattern.to_string(),
+                LoaderRuleItem {
+                    loaders: Vc::cell(vec![loader]),
+                    rename_as: Some("*".to_string()),
+                },
+            );
+        }
+    }
+
+    Ok(Vc::cell(Some(Vc::cell(rules))))
+}
+
+#[turbo_tasks::function]
+pub fn is_babel_loader_available(project_path: Vc<FileSystemPath>) -> Vc<bool> {
+    is_package_available(project_path, "babel-loader".to_string())
+}
+
 #[turbo_tasks::function]
-pub async fn is_babel_loader_available(project_path: Vc<FileSystemPath>) -> Result<Vc<bool>> {
+async fn is_package_available(
+    project_path: Vc<FileSystemPath>,
+    package: String,
+) -> Result<Vc<bool>> {
     let result = resolve(
         project_path,
-        Request::parse(Value::new(Pattern::Constant(
-            "babel-loader/package.json".to_string(),
-        ))),
+        Request::parse(Value::new(Pattern::Constant(format!(
+            "{package}/package.json"
+        )))),
         resolve_options(
             project_path,
             ResolveOptionsContext {
@@ -1,11 +1,12 @@
 use anyhow::Result;
+use serde_json::Value as JsonValue;
 use turbo_tasks::{
     graph::{AdjacencyMap, GraphTraversal},
     Completion, Completions, TryFlatJoinIterExt, Vc,
 };
-use turbo_tasks_fs::{rebase, FileSystemPath};
+use turbo_tasks_fs::{rebase, File, FileContent, FileSystemPath};
 use turbopack_binding::turbopack::core::{
-    asset::Asset,
+    asset::{Asset, AssetContent},
     output::{OutputAsset, OutputAssets},
 };
 
@@ -118,19 +119,84 @@ pub async fn emit_client_assets(
 }
 
 #[turbo_tasks::function]
-fn emit(asset: Vc<Box<dyn OutputAsset>>) -> Vc<Completion> {
-    asset.content().write(asset.ident().path())
+async fn emit(asset: Vc<Box<dyn OutputAsset>>) -> Result<Vc<Completion>> {
+    Ok(emitted_content(asset).await?.write(asset.ident().path()))
 }
 
 #[turbo_tasks::function]
-fn emit_rebase(
+async fn emit_rebase(
     asset: Vc<Box<dyn OutputAsset>>,
     from: Vc<FileSystemPath>,
     to: Vc<FileSystemPath>,
-) -> Vc<Completion> {
-    asset
-        .content()
-        .write(rebase(asset.ident().path(), from, to))
+) -> Result<Vc<Completion>> {
+    Ok(emitted_content(asset)
+        .await?
+        .write(rebase(asset.ident().path(), from, to)))
+}
+
+/// Returns the content of an asset as it is written to disk.
+async fn emitted_content(asset: Vc<Box<dyn OutputAsset>>) -> Result<Vc<AssetContent>> {
+    let content = asset.content();
+    if asset.ident().path().await?.path.ends_with(".map") {
+        return Ok(source_map_with_ignore_list(content));
+    }
+    Ok(content)
+}
+
+/// Adds an `ignoreList` to a source map, so browser devtools hide frames from
+/// third-party code in stack traces.
+#[turbo_tasks::function]
+async fn source_map_with_ignore_list(content: Vc<AssetContent>) -> Result<Vc<AssetContent>> {
+    let AssetContent::File(file_content) = &*content.await? else {
+        return Ok(content);
+    };
+    let FileContent::Content(file) = &*file_content.await? else {
+        return Ok(content);
+    };
+    let Ok(mut map) = serde_json::from_str::<JsonValue>(&file.content().to_str()?) else {
+        return Ok(content);
+    };
+    if !add_ignore_list(&mut map) {
+        return Ok(content);
+    }
+    Ok(AssetContent::file(File::from(map.to_string()).into()))
+}
+
+/// Whether a source map source is third-party code: a package from
+/// `node_modules` or the turbopack runtime.
+fn is_ignored_source(source: &str) -> bool {
+    source.contains("/node_modules/") || source.starts_with("turbopack://[turbopack]/")
+}
+
+/// Sets `ignoreList` (and the older `x_google_ignoreList`) on a source map, or
+/// on each section of an index map. Returns whether the map was changed.
+fn add_ignore_list(map: &mut JsonValue) -> bool {
+    if let Some(sections) = map.get_mut("sections").and_then(|s| s.as_array_mut()) {
+        let mut changed = false;
+        for section in sections {
+            if let Some(map) = section.get_mut("map") {
+                changed |= add_ignore_list(map);
+            }
+        }
+        return changed;
+    }
+
+    let Some(sources) = map.get("sources").and_then(|s| s.as_array()) else {
+        return false;
+    };
+    let ignore_list: Vec<JsonValue> = sources
+        .iter()
+        .enumerate()
+        .filter(|(_, source)| source.as_str().map_or(false, is_ignored_source))
+        .map(|(index, _)| index.into())
+        .collect();
+    if ignore_list.is_empty() {
+        return false;
+    }
+
+    map["ignoreList"] = ignore_list.clone().into();
+    map["x_google_ignoreList"] = ignore_list.into();
+    true
 }
 
 /// Walks the asset graph from multiple assets and collect all referenced
@@ -161,3 +227,51 @@ async fn get_referenced_assets(
         .collect::<Vec<_>>()
         .into_iter())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::add_ignore_list;
+
+    #[test]
+    fn ignores_node_modules_sources() {
+        let mut map = json!({
+            "version": 3,
+            "sources": [
+                "turbopack://[project]/app/page.tsx",
+                "turbopack://[project]/node_modules/react/index.js",
+                "turbopack://[turbopack]/shared/runtime-utils.ts",
+            ],
+        });
+        assert!(add_ignore_list(&mut map));
+        assert_eq!(map["ignoreList"], json!([1, 2]));
+        assert_eq!(map["x_google_ignoreList"], json!([1, 2]));
+    }
+
+    #[test]
+    fn handles_index_maps() {
+        let mut map = json!({
+            "version": 3,
+            "sections": [
+                {
+                    "offset": { "line": 0, "column": 0 },
+                    "map": {
+                        "version": 3,
+                        "sources": ["turbopack://[project]/app/page.tsx"],
+                    },
+                },
+                {
+                    "offset": { "line": 10, "column": 0 },
+                    "map": {
+                        "version": 3,
+                        "sources": ["turbopack://[project]/node_modules/next/dist/client/index.js"],
+                    },
+                },
+            ],
+        });
+        assert!(add_ignore_list(&mut map));
+        assert!(map["sections"][0]["map"].get("ignoreList").is_none());
+        assert_eq!(map["sections"][1]["map"]["ignoreList"], json!([0]));
+    }
+}
@@ -12,6 +12,7 @@ mod babel;
 mod bootstrap;
 mod embed_js;
 mod emit;
+pub mod instrumentation;
 mod loader_tree;
 pub mod middleware;
 pub mod mode;
@@ -23,30 +24,40 @@ pub mod next_config;
 pub mod next_dynamic;
 pub mod next_edge;
 mod next_font;
-mod next_image;
+pub mod next_image;
 mod next_import_map;
 pub mod next_manifests;
 pub mod next_pages;
 mod next_route_matcher;
+pub mod next_script;
 pub mod next_server;
 mod next_server_component;
 mod next_shared;
 pub mod next_telemetry;
+pub mod nft_json;
 mod page_loader;
 pub mod pages_structure;
+pub mod route_collisions;
 mod sass;
+pub mod service_worker;
 pub mod tracing_presets;
 mod transform_options;
+pub mod typed_routes;
 pub mod url_node;
 pub mod util;
 
 pub use app_segment_config::{
-    parse_segment_config_from_loader_tree, parse_segment_config_from_source,
+    parse_segment_config_from_loader_tree, parse_segment_config_from_source, NextRevalidate,
+    NextSegmentDynamic,
 };
 pub use emit::{all_assets_from_entries, emit_all_assets, emit_assets, emit_client_assets};
 pub use next_edge::context::{
     get_edge_chunking_context, get_edge_compile_time_info, get_edge_resolve_options_context,
 };
+pub use next_font::{
+    font_fallback::{FontAdjustment, DEFAULT_SANS_SERIF_FONT, DEFAULT_SERIF_FONT},
+    local::font_fallback::calculate_font_adjustment,
+};
 pub use page_loader::{create_page_loader_entry_module, PageLoaderAsset};
 pub use turbopack_binding::{turbopack::node::source_map, *};
 pub use util::{get_asset_path_from_pathname, pathname_for_path, PathType};
@@ -37,6 +37,7 @@ pub struct LoaderTreeBuilder {
     loader_tree_code: String,
     context: Vc<ModuleAssetContext>,
     mode: NextMode,
+    base_path: String,
     server_component_transition: Vc<Box<dyn Transition>>,
     pages: Vec<Vc<FileSystemPath>>,
 }
@@ -66,11 +67,25 @@ impl ComponentType {
     }
 }
 
+/// The path of the metadata routes of a page, which includes the base path
+/// like `path.join(basePath, segment)` in webpack.
+pub(crate) fn metadata_pathname_prefix(base_path: &str, app_page: &AppPage) -> String {
+    let page = app_page.to_string();
+    if base_path.is_empty() {
+        page
+    } else if page == "/" {
+        base_path.to_string()
+    } else {
+        format!("{base_path}{page}")
+    }
+}
+
 impl LoaderTreeBuilder {
     fn new(
         context: Vc<ModuleAssetContext>,
         server_component_transition: Vc<Box<dyn Transition>>,
         mode: NextMode,
+        base_path: String,
     ) -> Self {
         LoaderTreeBuilder {
             inner_assets: IndexMap::new(),
@@ -80,6 +95,7 @@ impl LoaderTreeBuilder {
             context,
             server_component_transition,
             mode,
+            base_path,
             pages: Vec::new(),
         }
     }
@@ -266,6 +282,7 @@ impl LoaderTreeBuilder {
                     *path,
                     name.to_string(),
                     app_page.clone(),
+                    self.base_path.clone(),
                 );
 
                 self.inner_assets.insert(
@@ -323,7 +340,7 @@ impl LoaderTreeBuilder {
             self.loader_tree_code,
             "{s}  url: fillMetadataSegment({}, props.params, {}) + \
              `?${{{identifier}.src.split(\"/\").splice(-1)[0]}}`,",
-            StringifyJs(&app_page.to_string()),
+            StringifyJs(&metadata_pathname_prefix(&self.base_path, app_page)),
             StringifyJs(metadata_route),
         )?;
 
@@ -450,8 +467,9 @@ impl LoaderTreeModule {
         context: Vc<ModuleAssetContext>,
         server_component_transition: Vc<Box<dyn Transition>>,
         mode: NextMode,
+        base_path: String,
     ) -> Result<Self> {
-        LoaderTreeBuilder::new(context, server_component_transition, mode)
+        LoaderTreeBuilder::new(context, server_component_transition, mode, base_path)
             .build(loader_tree)
             .await
     }
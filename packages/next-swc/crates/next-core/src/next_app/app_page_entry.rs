@@ -26,7 +26,7 @@ use crate::{
     mode::NextMode,
     next_app::{AppPage, AppPath},
     next_config::NextConfig,
-    next_edge::entry::wrap_edge_entry,
+    next_edge::entry::{get_incremental_cache_handler, wrap_edge_entry},
     next_server_component::NextServerComponentTransition,
     parse_segment_config_from_loader_tree,
     util::{file_content_rope, load_next_js_template, NextRuntime},
@@ -41,6 +41,8 @@ pub async fn get_app_page_entry(
     page: AppPage,
     project_root: Vc<FileSystemPath>,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
+    mode: NextMode,
 ) -> Result<Vc<AppEntry>> {
     let config = parse_segment_config_from_loader_tree(loader_tree);
     let is_edge = matches!(config.await?.runtime, Some(NextRuntime::Edge));
@@ -52,11 +54,13 @@ pub async fn get_app_page_entry(
 
     let server_component_transition = Vc::upcast(NextServerComponentTransition::new());
 
+    let base_path = next_config.await?.base_path.clone().unwrap_or_default();
     let loader_tree = LoaderTreeModule::build(
         loader_tree,
         context,
         server_component_transition,
-        NextMode::Build,
+        mode,
+        base_path,
     )
     .await?;
 
@@ -118,6 +122,8 @@ pub async fn get_app_page_entry(
             rsc_entry,
             page,
             next_config,
+            build_id,
+            mode,
         );
     };
 
@@ -143,20 +149,33 @@ async fn wrap_edge_page(
     entry: Vc<Box<dyn Module>>,
     page: AppPage,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
+    mode: NextMode,
 ) -> Result<Vc<Box<dyn Module>>> {
     const INNER: &str = "INNER_PAGE_ENTRY";
+    const INNER_INCREMENTAL_CACHE_HANDLER: &str = "INNER_INCREMENTAL_CACHE_HANDLER";
 
+    let mut inner_assets = indexmap! {
+        INNER.to_string() => entry
+    };
+
+    let incremental_cache_handler =
+        get_incremental_cache_handler(context, project_root, next_config).await?;
+    if let Some(incremental_cache_handler) = incremental_cache_handler {
+        inner_assets.insert(
+            INNER_INCREMENTAL_CACHE_HANDLER.to_string(),
+            incremental_cache_handler,
+        );
+    }
+
+    let server_actions = next_config.server_actions().await?;
     let next_config = &*next_config.await?;
 
-    // TODO(WEB-1824): add build support
-    let build_id = "development";
-    let dev = true;
+    let dev = mode == NextMode::Development;
 
     // TODO(timneutkens): remove this
     let is_server_component = true;
 
-    let server_actions = next_config.experimental.server_actions.as_ref();
-
     let sri_enabled = !dev
         && next_config
             .experimental
@@ -171,25 +190,22 @@ async fn wrap_edge_page(
         indexmap! {
             "VAR_USERLAND" => INNER.to_string(),
             "VAR_PAGE" => page.to_string(),
-            "VAR_BUILD_ID" => build_id.to_string(),
+            "VAR_BUILD_ID" => build_id.await?.clone_value(),
         },
         indexmap! {
             "sriEnabled" => serde_json::Value::Bool(sri_enabled).to_string(),
             "nextConfig" => serde_json::to_string(next_config)?,
             "isServerComponent" => serde_json::Value::Bool(is_server_component).to_string(),
             "dev" => serde_json::Value::Bool(dev).to_string(),
-            "serverActions" => serde_json::to_string(&server_actions)?
+            "serverActions" => serde_json::to_string(&*server_actions)?
         },
         indexmap! {
-            "incrementalCacheHandler" => None,
+            "incrementalCacheHandler" => incremental_cache_handler
+                .map(|_| INNER_INCREMENTAL_CACHE_HANDLER.to_string()),
         },
     )
     .await?;
 
-    let inner_assets = indexmap! {
-        INNER.to_string() => entry
-    };
-
     let wrapped = context.process(
         Vc::upcast(source),
         Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
@@ -6,27 +6,24 @@ use anyhow::{bail, Result};
 use indoc::formatdoc;
 use turbo_tasks::{ValueToString, Vc};
 use turbo_tasks_fs::{File, FileContent, FileSystemPath};
-use turbopack_binding::{
-    turbo::tasks_hash::hash_xxh3_hash64,
-    turbopack::{
-        core::{
-            asset::AssetContent,
-            context::AssetContext,
-            file_source::FileSource,
-            module::Module,
-            reference_type::{EcmaScriptModulesReferenceSubType, ReferenceType},
-            source::Source,
-            virtual_source::VirtualSource,
-        },
-        ecmascript::{
-            chunk::{EcmascriptChunkPlaceable, EcmascriptExports},
-            utils::StringifyJs,
-            EcmascriptModuleAsset,
-        },
+use turbopack_binding::turbopack::{
+    core::{
+        asset::AssetContent,
+        context::AssetContext,
+        file_source::FileSource,
+        module::Module,
+        reference_type::{EcmaScriptModulesReferenceSubType, ReferenceType},
+        source::Source,
+        virtual_source::VirtualSource,
+    },
+    ecmascript::{
+        chunk::{EcmascriptChunkPlaceable, EcmascriptExports},
+        utils::StringifyJs,
+        EcmascriptModuleAsset,
     },
 };
 
-use crate::next_app::AppPage;
+use crate::{loader_tree::metadata_pathname_prefix, next_app::AppPage, util::content_hash};
 
 async fn hash_file_content(path: Vc<FileSystemPath>) -> Result<u64> {
     let original_file_content = path.read().await?;
@@ -34,7 +31,7 @@ async fn hash_file_content(path: Vc<FileSystemPath>) -> Result<u64> {
     Ok(match &*original_file_content {
         FileContent::Content(content) => {
             let content = content.content().to_bytes()?;
-            hash_xxh3_hash64(&*content)
+            content_hash(&content)
         }
         FileContent::NotFound => {
             bail!("metadata file not found: {}", &path.to_string().await?);
@@ -48,6 +45,7 @@ pub async fn dynamic_image_metadata_source(
     path: Vc<FileSystemPath>,
     ty: String,
     page: AppPage,
+    base_path: String,
 ) -> Result<Vc<Box<dyn Source>>> {
     let stem = path.file_stem().await?;
     let stem = stem.as_deref().unwrap_or_default();
@@ -116,7 +114,7 @@ pub async fn dynamic_image_metadata_source(
         "#,
         exported_fields_excluding_default = exported_fields_excluding_default,
         resource_path = StringifyJs(&format!("./{}.{}", stem, ext)),
-        pathname_prefix = StringifyJs(&page.to_string()),
+        pathname_prefix = StringifyJs(&metadata_pathname_prefix(&base_path, &page)),
         page_segment = StringifyJs(stem),
         sizes = sizes,
         hash_query = StringifyJs(&hash_query),
@@ -14,7 +14,7 @@ use turbopack_binding::{
             },
             environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
             free_var_references,
-            resolve::{parse::Request, pattern::Pattern},
+            resolve::{parse::Request, pattern::Pattern, plugin::ResolvePlugin},
         },
         dev::{react_refresh::assert_can_resolve_react_refresh, DevChunkingContext},
         ecmascript::chunk::EcmascriptChunkingContext,
@@ -33,7 +33,7 @@ use turbopack_binding::{
 
 use super::transforms::get_next_client_transforms_rules;
 use crate::{
-    babel::maybe_add_babel_loader,
+    babel::{maybe_add_babel_loader, maybe_add_react_compiler_loader},
     embed_js::next_js_fs,
     mode::NextMode,
     next_build::{get_external_next_compiled_package_mapping, get_postcss_package_mapping},
@@ -44,6 +44,8 @@ use crate::{
         get_next_client_resolved_map, mdx_import_source_file,
     },
     next_shared::{
+        barrel::NextBarrelExportResolvePlugin,
+        package_imports::package_imports_conditions,
         resolve::{
             ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
             UnsupportedModulesResolvePlugin,
@@ -124,6 +126,27 @@ pub fn get_client_compile_time_info(
     .cell()
 }
 
+/// Like [get_client_compile_time_info], but for code running in a service
+/// worker, which has no DOM.
+#[turbo_tasks::function]
+pub fn get_service_worker_compile_time_info(
+    browserslist_query: String,
+    define_env: Vc<EnvMap>,
+) -> Vc<CompileTimeInfo> {
+    CompileTimeInfo::builder(Environment::new(Value::new(ExecutionEnvironment::Browser(
+        BrowserEnvironment {
+            dom: false,
+            web_worker: true,
+            service_worker: true,
+            browserslist_query: browserslist_query.to_owned(),
+        }
+        .into(),
+    ))))
+    .defines(next_client_defines(define_env))
+    .free_var_references(next_client_free_vars(define_env))
+    .cell()
+}
+
 #[turbo_tasks::value(serialization = "auto_for_input")]
 #[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord)]
 pub enum ClientContextType {
@@ -141,23 +164,44 @@ pub async fn get_client_resolve_options_context(
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
 ) -> Result<Vc<ResolveOptionsContext>> {
-    let next_client_import_map =
-        get_next_client_import_map(project_path, ty, mode, next_config, execution_context);
-    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty);
+    // `browser: true` below adds the `browser` condition.
+    let custom_conditions = vec![mode.node_env().to_string()];
+    let next_client_import_map = get_next_client_import_map(
+        project_path,
+        ty,
+        mode,
+        next_config,
+        execution_context,
+        package_imports_conditions(&custom_conditions, true, true),
+    );
+    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty, next_config);
     let next_client_resolved_map = get_next_client_resolved_map(project_path, project_path, mode);
+    let optimize_package_imports = next_config.optimize_package_imports();
+    let mut plugins: Vec<Vc<Box<dyn ResolvePlugin>>> = vec![
+        Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
+        Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
+        Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
+    ];
+    if !optimize_package_imports.await?.is_empty() {
+        plugins.insert(
+            0,
+            Vc::upcast(NextBarrelExportResolvePlugin::new(
+                project_path,
+                optimize_package_imports,
+            )),
+        );
+    }
+
     let module_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
-        custom_conditions: vec![mode.node_env().to_string()],
+        custom_extensions: next_config.resolve_extensions().await?.clone_value(),
+        custom_conditions,
         import_map: Some(next_client_import_map),
         fallback_import_map: Some(next_client_fallback_import_map),
         resolved_map: Some(next_client_resolved_map),
         browser: true,
         module: true,
-        plugins: vec![
-            Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
-            Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
-            Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
-        ],
+        plugins,
         ..Default::default()
     };
     Ok(ResolveOptionsContext {
@@ -181,7 +225,8 @@ pub async fn get_client_module_options_context(
     mode: NextMode,
     next_config: Vc<NextConfig>,
 ) -> Result<Vc<ModuleOptionsContext>> {
-    let custom_rules = get_next_client_transforms_rules(next_config, ty.into_value(), mode).await?;
+    let custom_rules =
+        get_next_client_transforms_rules(project_path, next_config, ty.into_value(), mode).await?;
     let resolve_options_context =
         get_client_resolve_options_context(project_path, ty, mode, next_config, execution_context);
 
@@ -227,6 +272,8 @@ pub async fn get_client_module_options_context(
     // Now creates a webpack rules that applies to all codes.
     let webpack_rules = *foreign_webpack_rules.clone();
     let webpack_rules = *maybe_add_babel_loader(project_path, webpack_rules).await?;
+    let webpack_rules =
+        *maybe_add_react_compiler_loader(project_path, next_config, webpack_rules).await?;
     let enable_webpack_loaders = webpack_rules.map(|rules| {
         WebpackLoadersOptions {
             rules,
@@ -240,8 +287,8 @@ pub async fn get_client_module_options_context(
     let source_transforms = vec![
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
         *get_relay_transform_plugin(next_config).await?,
-        *get_emotion_transform_plugin(next_config).await?,
-        *get_styled_components_transform_plugin(next_config).await?,
+        *get_emotion_transform_plugin(next_config, mode).await?,
+        *get_styled_components_transform_plugin(next_config, mode).await?,
         *get_styled_jsx_transform_plugin().await?,
     ]
     .into_iter()
@@ -316,7 +363,16 @@ pub async fn get_client_chunking_context(
     asset_prefix: Vc<Option<String>>,
     environment: Vc<Environment>,
     mode: NextMode,
+    next_config: Vc<NextConfig>,
 ) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
+    // TODO: chunks and CSS loaded by the Turbopack runtime, e.g. for `import()`,
+    // should get the `?dpl=<id>` query of `experimental.deploymentId`. The
+    // runtime builds their urls from `chunk_base_path` and the chunk path
+    // alone, so there's nothing to put a suffix into. Only the urls written by
+    // Next.js (manifests, HTML, static images) get the query for now.
+    // TODO: the script and link elements the runtime creates for those chunks
+    // should get the `crossorigin` attribute of `crossOrigin`, which the runtime
+    // has no option for either.
     let mut builder = DevChunkingContext::builder(
         project_path,
         client_root,
@@ -328,7 +384,41 @@ pub async fn get_client_chunking_context(
     .asset_base_path(asset_prefix);
 
     if matches!(mode, NextMode::Development) {
-        builder = builder.hot_module_replacement();
+        builder = builder
+            .hot_module_replacement()
+            .reference_chunk_source_maps(*next_config.enable_dev_source_maps().await?);
+    }
+
+    Ok(Vc::upcast(builder.build()))
+}
+
+/// The service worker is loaded with `importScripts`, so its chunks live in a
+/// directory of their own and are never hot reloaded.
+#[turbo_tasks::function]
+pub async fn get_service_worker_chunking_context(
+    project_path: Vc<FileSystemPath>,
+    client_root: Vc<FileSystemPath>,
+    asset_prefix: Vc<Option<String>>,
+    environment: Vc<Environment>,
+    mode: NextMode,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
+    // TODO: `next build` should bundle the worker with a production runtime.
+    // Like the client chunks, it uses the runtime of `DevChunkingContext` in
+    // both modes, as it's the only browser runtime Turbopack provides. Only
+    // its HMR is left out, which a service worker can't use anyway.
+    let mut builder = DevChunkingContext::builder(
+        project_path,
+        client_root,
+        client_root.join("static/chunks/sw".to_string()),
+        get_client_assets_path(client_root),
+        environment,
+    )
+    .chunk_base_path(asset_prefix)
+    .asset_base_path(asset_prefix);
+
+    if matches!(mode, NextMode::Development) {
+        builder = builder.reference_chunk_source_maps(*next_config.enable_dev_source_maps().await?);
     }
 
     Ok(Vc::upcast(builder.build()))
@@ -339,6 +429,26 @@ pub fn get_client_assets_path(client_root: Vc<FileSystemPath>) -> Vc<FileSystemP
     client_root.join("static/media".to_string())
 }
 
+/// Returns the directory that client chunks and assets are emitted into,
+/// `{basePath}/_next` inside the client root. Paths in manifests are relative
+/// to this directory and get prefixed with the computed asset prefix (which
+/// already includes the basePath) when they are requested.
+#[turbo_tasks::function]
+pub async fn get_client_relative_path(
+    client_root: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<FileSystemPath>> {
+    let next_config = next_config.await?;
+    Ok(client_root.join(format!(
+        "{}/_next",
+        next_config
+            .base_path
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/'),
+    )))
+}
+
 #[turbo_tasks::function]
 pub async fn get_client_runtime_entries(
     project_root: Vc<FileSystemPath>,
@@ -1,9 +1,12 @@
 pub(crate) mod context;
+pub(crate) mod node_builtins;
 pub(crate) mod runtime_entry;
 pub(crate) mod transforms;
 
 pub use context::{
-    get_client_chunking_context, get_client_compile_time_info, get_client_module_options_context,
-    get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
+    get_client_assets_path, get_client_chunking_context, get_client_compile_time_info,
+    get_client_module_options_context, get_client_relative_path,
+    get_client_resolve_options_context, get_client_runtime_entries,
+    get_service_worker_chunking_context, get_service_worker_compile_time_info, ClientContextType,
 };
 pub use runtime_entry::{RuntimeEntries, RuntimeEntry};
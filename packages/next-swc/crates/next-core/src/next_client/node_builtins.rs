@@ -0,0 +1,87 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use swc_core::{common::errors::HANDLER, ecma::ast::Program};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use crate::{
+    next_client::context::ClientContextType,
+    next_config::NextConfig,
+    next_import_map::NEXT_ALIASES,
+    next_shared::{
+        module_requests::{is_installed_package, module_requests},
+        transforms::module_rule_match_js_no_url,
+    },
+};
+
+/// Returns a rule which reports imports of Node.js builtins in client code when
+/// `experimental.fallbackNodePolyfills` is `false`, instead of silently
+/// ignoring them. The import maps only know the directory of the importing
+/// module, so the imports are reported here, at the import.
+pub async fn get_node_builtins_in_client_rule(
+    next_config: Vc<NextConfig>,
+    context_ty: ClientContextType,
+) -> Result<Option<ModuleRule>> {
+    if *next_config.enable_fallback_node_polyfills().await? {
+        return Ok(None);
+    }
+
+    // Like the import maps, bare names are only replaced for pages and app code,
+    // `node:` requests also for the fallback context.
+    let bare_names = match context_ty {
+        ClientContextType::Pages { .. } | ClientContextType::App { .. } => true,
+        ClientContextType::Fallback => false,
+        ClientContextType::Other => return Ok(None),
+    };
+
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(
+            Box::new(NodeBuiltinsInClient { bare_names }) as _
+        ));
+    Ok(Some(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )))
+}
+
+const HINT: &str = "`experimental.fallbackNodePolyfills` is disabled, so Node.js modules aren't \
+                    polyfilled in the browser. Remove the import, move it to server-only code or \
+                    enable the polyfills in next.config.js.";
+
+#[derive(Debug)]
+struct NodeBuiltinsInClient {
+    bare_names: bool,
+}
+
+#[async_trait]
+impl CustomTransformer for NodeBuiltinsInClient {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        for (request, span) in module_requests(program) {
+            let (module, is_bare_name) = match request.strip_prefix("node:") {
+                Some(module) => (module, false),
+                None => (request.as_str(), true),
+            };
+            if !NEXT_ALIASES.iter().any(|(original, _)| *original == module) {
+                continue;
+            }
+            // A package named like a Node.js builtin resolves before the fallback
+            // import map, so it's not reported.
+            if is_bare_name
+                && (!self.bare_names
+                    || is_installed_package(ctx.file_path.parent(), module).await?)
+            {
+                continue;
+            }
+
+            let message =
+                format!("The Node.js module '{module}' can't be imported in client code\n{HINT}");
+            HANDLER.with(|handler| handler.struct_span_err(span, &message).emit());
+        }
+        Ok(())
+    }
+}
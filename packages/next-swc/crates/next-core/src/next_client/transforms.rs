@@ -1,22 +1,31 @@
 use anyhow::Result;
 use next_transform_strip_page_exports::ExportFilter;
 use turbo_tasks::Vc;
-use turbopack_binding::turbopack::turbopack::module_options::ModuleRule;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath, turbopack::turbopack::module_options::ModuleRule,
+};
 
 use crate::{
     mode::NextMode,
-    next_client::context::ClientContextType,
+    next_client::{context::ClientContextType, node_builtins::get_node_builtins_in_client_rule},
     next_config::NextConfig,
-    next_shared::transforms::{
-        get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
-        get_next_modularize_imports_rule, get_next_pages_transforms_rule,
-        get_server_actions_transform_rule, server_actions::ActionsTransform,
+    next_shared::{
+        optional_peer_dependencies::get_optional_peer_dependencies_rule,
+        transforms::{
+            get_auto_pure_rule, get_import_meta_transform_rule, get_mdx_frontmatter_rule,
+            get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
+            get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+            get_optimize_package_imports_rule, get_react_server_components_transform_rule,
+            get_remove_console_transform_rule, get_server_actions_transform_rule,
+            server_actions::ActionsTransform,
+        },
     },
 };
 
 /// Returns a list of module rules which apply client-side, Next.js-specific
 /// transforms.
 pub async fn get_next_client_transforms_rules(
+    project_path: Vc<FileSystemPath>,
     next_config: Vc<NextConfig>,
     context_ty: ClientContextType,
     mode: NextMode,
@@ -28,8 +37,17 @@ pub async fn get_next_client_transforms_rules(
         rules.push(get_next_modularize_imports_rule(modularize_imports_config));
     }
 
+    let optimize_package_imports = next_config.optimize_package_imports().await?;
+    if !optimize_package_imports.is_empty() {
+        rules.push(get_optimize_package_imports_rule(&optimize_package_imports));
+    }
+
     rules.push(get_next_font_transform_rule());
 
+    if *next_config.mdx_rs().await? {
+        rules.push(get_mdx_frontmatter_rule());
+    }
+
     let pages_dir = match context_ty {
         ClientContextType::Pages { pages_dir } => {
             rules.push(
@@ -37,7 +55,8 @@ pub async fn get_next_client_transforms_rules(
             );
             Some(pages_dir)
         }
-        ClientContextType::App { .. } => {
+        ClientContextType::App { app_dir } => {
+            rules.push(get_react_server_components_transform_rule(false, app_dir).await?);
             rules.push(get_server_actions_transform_rule(ActionsTransform::Client));
             None
         }
@@ -48,5 +67,21 @@ pub async fn get_next_client_transforms_rules(
 
     rules.push(get_next_image_rule());
 
+    rules.push(get_import_meta_transform_rule(project_path, false).await?);
+
+    if let Some(rule) = get_remove_console_transform_rule(next_config, mode).await? {
+        rules.push(rule);
+    }
+
+    if let Some(rule) = get_auto_pure_rule(mode) {
+        rules.push(rule);
+    }
+
+    if let Some(rule) = get_node_builtins_in_client_rule(next_config, context_ty).await? {
+        rules.push(rule);
+    }
+
+    rules.push(get_optional_peer_dependencies_rule());
+
     Ok(rules)
 }
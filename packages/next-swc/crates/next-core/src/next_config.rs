@@ -1,11 +1,15 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use indexmap::IndexMap;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use turbo_tasks::{trace::TraceRawVcs, Completion, Value, Vc};
 use turbo_tasks_fs::json::parse_json_with_source_context;
 use turbopack_binding::{
-    turbo::{tasks_env::EnvMap, tasks_fs::FileSystemPath},
+    turbo::{
+        tasks_env::EnvMap,
+        tasks_fs::{FileSystemPath, FileSystemPathOption},
+    },
     turbopack::{
         core::{
             changed::any_content_changed_of_module,
@@ -21,10 +25,7 @@ use turbopack_binding::{
             },
             source::Source,
         },
-        ecmascript_plugin::transform::{
-            emotion::EmotionTransformConfig, relay::RelayConfig,
-            styled_components::StyledComponentsTransformConfig,
-        },
+        ecmascript_plugin::transform::{emotion::EmotionLabelKind, relay::RelayConfig},
         node::{
             debug::should_debug,
             evaluate::evaluate,
@@ -38,7 +39,9 @@ use turbopack_binding::{
     },
 };
 
-use crate::{embed_js::next_asset, next_shared::transforms::ModularizeImportPackageConfig};
+use crate::{
+    embed_js::next_asset, mode::NextMode, next_shared::transforms::ModularizeImportPackageConfig,
+};
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,8 +60,6 @@ struct NextConfigAndCustomRoutes {
 #[serde(rename_all = "camelCase")]
 struct CustomRoutesRaw {
     rewrites: Rewrites,
-
-    // unsupported
     headers: Vec<Header>,
     redirects: Vec<Redirect>,
 }
@@ -66,6 +67,8 @@ struct CustomRoutesRaw {
 #[turbo_tasks::value]
 struct CustomRoutes {
     rewrites: Vc<Rewrites>,
+    headers: Vc<Headers>,
+    redirects: Vc<Redirects>,
 }
 
 #[turbo_tasks::value(serialization = "custom", eq = "manual")]
@@ -80,6 +83,7 @@ pub struct NextConfig {
     pub images: ImageConfig,
     pub page_extensions: Vec<String>,
     pub react_strict_mode: Option<bool>,
+    pub react_production_profiling: Option<bool>,
     pub transpile_packages: Option<Vec<String>>,
     pub modularize_imports: Option<IndexMap<String, ModularizeImportPackageConfig>>,
     pub dist_dir: Option<String>,
@@ -94,6 +98,8 @@ pub struct NextConfig {
     pub dev_indicators: Option<DevIndicatorsConfig>,
     pub output: Option<OutputType>,
     pub analytics_id: Option<String>,
+    pub public_runtime_config: IndexMap<String, serde_json::Value>,
+    pub server_runtime_config: IndexMap<String, serde_json::Value>,
 
     ///
     #[serde(rename = "_originalRedirects")]
@@ -104,6 +110,10 @@ pub struct NextConfig {
 
     pub optimize_fonts: Option<bool>,
 
+    /// The ID returned by the `generateBuildId` function, which the config
+    /// loader calls in the node execution context.
+    generate_build_id: Option<serde_json::Value>,
+
     // unsupported
     amp: AmpConfig,
     clean_dist_dir: bool,
@@ -112,16 +122,12 @@ pub struct NextConfig {
     exclude_default_moment_locales: bool,
     // this can be a function in js land
     export_path_map: Option<serde_json::Value>,
-    // this is a function in js land
-    generate_build_id: Option<serde_json::Value>,
     generate_etags: bool,
     http_agent_options: HttpAgentConfig,
     on_demand_entries: OnDemandEntriesConfig,
     output_file_tracing: bool,
     powered_by_header: bool,
     production_browser_source_maps: bool,
-    public_runtime_config: IndexMap<String, serde_json::Value>,
-    server_runtime_config: IndexMap<String, serde_json::Value>,
     static_page_generation_timeout: f64,
     swc_minify: Option<bool>,
     target: Option<String>,
@@ -178,7 +184,9 @@ struct HttpAgentConfig {
 pub struct DomainLocale {
     pub default_locale: String,
     pub domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub http: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub locales: Option<Vec<String>>,
 }
 
@@ -186,7 +194,9 @@ pub struct DomainLocale {
 #[serde(rename_all = "camelCase")]
 pub struct I18NConfig {
     pub default_locale: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub domains: Option<Vec<DomainLocale>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub locale_detection: Option<bool>,
     pub locales: Vec<String>,
 }
@@ -198,7 +208,7 @@ pub enum OutputType {
     Export,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum RouteHas {
     Header {
@@ -241,15 +251,28 @@ pub struct Header {
     pub has: Option<Vec<RouteHas>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub missing: Option<Vec<RouteHas>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub enum RedirectStatus {
-    StatusCode(f64),
+    StatusCode(u16),
     Permanent(bool),
 }
 
+impl RedirectStatus {
+    /// The status code to redirect with, like `getRedirectStatus`.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            RedirectStatus::StatusCode(status_code) => *status_code,
+            RedirectStatus::Permanent(true) => 308,
+            RedirectStatus::Permanent(false) => 307,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct Redirect {
@@ -264,6 +287,12 @@ pub struct Redirect {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub missing: Option<Vec<RouteHas>>,
 
+    /// Whether Next.js added the redirect itself, e.g. for `trailingSlash`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+
     #[serde(flatten)]
     pub status: RedirectStatus,
 }
@@ -281,6 +310,8 @@ pub struct Rewrite {
     pub has: Option<Vec<RouteHas>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub missing: Option<Vec<RouteHas>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
 }
 
 #[turbo_tasks::value(eq = "manual")]
@@ -292,6 +323,21 @@ pub struct Rewrites {
     pub fallback: Vec<Rewrite>,
 }
 
+#[turbo_tasks::value(transparent, eq = "manual")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Headers(Vec<Header>);
+
+#[turbo_tasks::value(transparent, eq = "manual")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Redirects(Vec<Redirect>);
+
+#[turbo_tasks::value(transparent)]
+pub struct ResolveExtensions(Option<Vec<String>>);
+
+#[turbo_tasks::value(transparent, eq = "manual")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptionServerActions(Option<ServerActions>);
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct TypeScriptConfig {
@@ -383,6 +429,23 @@ pub struct ExperimentalTurboConfig {
     pub loaders: Option<JsonValue>,
     pub rules: Option<IndexMap<String, RuleConfigItem>>,
     pub resolve_alias: Option<IndexMap<String, JsonValue>>,
+    pub resolve_extensions: Option<Vec<String>>,
+    pub source_maps: Option<DevSourceMaps>,
+}
+
+/// The source maps generated for client chunks in development.
+///
+/// TODO: A cheap mode mapping only lines, like webpack's `cheap-*` devtools,
+/// needs support from Turbopack's chunking contexts, which generate the maps
+/// of the modules with full fidelity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "kebab-case")]
+pub enum DevSourceMaps {
+    /// Full source maps, mapping every token to its original location.
+    #[default]
+    Full,
+    /// No source maps, which makes rebuilds of large projects faster.
+    None,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -403,6 +466,83 @@ pub enum LoaderItem {
     LoaderOptions(WebpackLoaderItem),
 }
 
+/// Splits the query off a loader request like `svgr-loader?icon=true`, and
+/// parses it into the loader's options like `parseQuery` of `loader-utils`.
+/// The query is either JSON, like `?{"icon":true}`, or a list of `name=value`
+/// pairs separated by `&` or `,`, where a `name` alone or prefixed with `+` is
+/// `true`, and prefixed with `-` is `false`.
+fn parse_loader_request(
+    request: &str,
+) -> Result<(String, Option<serde_json::Map<String, JsonValue>>)> {
+    let Some((loader, query)) = request.split_once('?') else {
+        return Ok((request.to_string(), None));
+    };
+    if query.starts_with('{') && query.ends_with('}') {
+        let options = serde_json::from_str(query)
+            .with_context(|| format!("Invalid JSON query of the loader {request}"))?;
+        return Ok((loader.to_string(), Some(options)));
+    }
+
+    let mut options = serde_json::Map::new();
+    for arg in query.split(['&', ',']).filter(|arg| !arg.is_empty()) {
+        let (name, value) = if let Some((name, value)) = arg.split_once('=') {
+            let value = decode_query_component(value);
+            let value = match value.as_str() {
+                "null" => JsonValue::Null,
+                "true" => JsonValue::Bool(true),
+                "false" => JsonValue::Bool(false),
+                _ => JsonValue::String(value),
+            };
+            (decode_query_component(name), value)
+        } else if let Some(name) = arg.strip_prefix('-') {
+            (decode_query_component(name), JsonValue::Bool(false))
+        } else {
+            let name = arg.strip_prefix('+').unwrap_or(arg);
+            (decode_query_component(name), JsonValue::Bool(true))
+        };
+        if let Some(name) = name.strip_suffix("[]") {
+            match options
+                .entry(name)
+                .or_insert_with(|| JsonValue::Array(vec![]))
+            {
+                JsonValue::Array(values) => values.push(value),
+                existing => *existing = JsonValue::Array(vec![value]),
+            }
+        } else {
+            options.insert(name, value);
+        }
+    }
+    Ok((loader.to_string(), Some(options)))
+}
+
+/// Decodes a component of a loader query, like `decodeURIComponent` after
+/// replacing `+` with spaces.
+fn decode_query_component(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = hex {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                decoded.push(b'%');
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct ExperimentalConfig {
@@ -434,6 +574,11 @@ pub struct ExperimentalConfig {
     pub use_deployment_id_server_actions: Option<bool>,
     pub deployment_id: Option<String>,
     pub manual_client_base_path: Option<bool>,
+    /// If set to `false`, Node.js core modules imported from client code
+    /// resolve to empty modules instead of browser polyfills. Full list of old
+    /// polyfills is accessible here:
+    /// [webpack/webpack#ModuleNotFoundError.js#L13-L42](https://github.com/webpack/webpack/blob/2a0536cf510768111a3a6dceeb14cb79b9f59273/lib/ModuleNotFoundError.js#L13-L42)
+    pub fallback_node_polyfills: Option<bool>,
     pub optimistic_client_cache: Option<bool>,
     pub middleware_prefetch: Option<MiddlewarePrefetchType>,
     /// optimizeCss can be boolean or critters' option object
@@ -444,6 +589,20 @@ pub struct ExperimentalConfig {
     pub web_vitals_attribution: Option<Vec<String>>,
     pub server_actions: Option<ServerActions>,
     pub sri: Option<SubResourceIntegrity>,
+    pub case_sensitive_routes: Option<bool>,
+    /// Generate Route types and enable type checking for Link and Router.push,
+    /// etc. This option requires `appDir` to be enabled first.
+    /// @see https://nextjs.org/docs/app/api-reference/next-config-js/typedRoutes
+    pub typed_routes: Option<bool>,
+    /// Run the `register` function exported by `instrumentation.{ext}` once
+    /// when the server starts, before any route code.
+    pub instrumentation_hook: Option<bool>,
+    /// Bundle `sw.{ext}` as a service worker, served at `/sw.js`.
+    pub service_worker: Option<bool>,
+    /// Compile the components and hooks of client and SSR modules with the
+    /// React Compiler, with the given options of
+    /// `babel-plugin-react-compiler`.
+    pub react_compiler: Option<ReactCompilerConfig>,
 
     // ---
     // UNSUPPORTED
@@ -452,7 +611,6 @@ pub struct ExperimentalConfig {
     adjust_font_fallbacks_with_size_adjust: Option<bool>,
     amp: Option<serde_json::Value>,
     app_document_preloading: Option<bool>,
-    case_sensitive_routes: Option<bool>,
     cpus: Option<f64>,
     cra_compat: Option<bool>,
     disable_optimized_loading: Option<bool>,
@@ -460,16 +618,11 @@ pub struct ExperimentalConfig {
     esm_externals: Option<serde_json::Value>,
     extension_alias: Option<serde_json::Value>,
     external_dir: Option<bool>,
-    /// If set to `false`, webpack won't fall back to polyfill Node.js modules
-    /// in the browser Full list of old polyfills is accessible here:
-    /// [webpack/webpack#Module_notound_error.js#L13-L42](https://github.com/webpack/webpack/blob/2a0536cf510768111a3a6dceeb14cb79b9f59273/lib/Module_not_found_error.js#L13-L42)
-    fallback_node_polyfills: Option<bool>, // false
     force_swc_transforms: Option<bool>,
     fully_specified: Option<bool>,
     gzip_size: Option<bool>,
     /// custom path to a cache handler to use
     incremental_cache_handler_path: Option<String>,
-    instrumentation_hook: Option<bool>,
     large_page_data_bytes: Option<f64>,
     logging: Option<serde_json::Value>,
     memory_based_workers_count: Option<bool>,
@@ -484,20 +637,21 @@ pub struct ExperimentalConfig {
     /// Using this feature will enable the `react@experimental` for the `app`
     /// directory.
     ppr: Option<bool>,
+    /// Enables React's taint APIs (`taintObjectReference`/`taintUniqueValue`),
+    /// which requires the experimental React channel.
     taint: Option<bool>,
     proxy_timeout: Option<f64>,
     /// enables the minification of server code.
     server_minification: Option<bool>,
     /// Enables source maps generation for the server production bundle.
     server_source_maps: Option<bool>,
+    /// The gzipped size in bytes an edge function must not exceed in
+    /// `next build`.
+    edge_function_size_limit: Option<u64>,
     swc_minify: Option<bool>,
     swc_trace_profiling: Option<bool>,
     /// @internal Used by the Next.js internals only.
     trust_host_header: Option<bool>,
-    /// Generate Route types and enable type checking for Link and Router.push,
-    /// etc. This option requires `appDir` to be enabled first.
-    /// @see https://nextjs.org/docs/app/api-reference/next-config-js/typedRoutes
-    typed_routes: Option<bool>,
     url_imports: Option<serde_json::Value>,
     /// This option is to enable running the Webpack build in a worker thread
     /// (doesn't apply to Turbopack).
@@ -516,6 +670,34 @@ pub struct SubResourceIntegrity {
 pub struct ServerActions {
     /// Allows adjusting body parser size limit for server actions.
     pub body_size_limit: Option<SizeLimit>,
+    /// Extra origins, besides the one of the host, which server actions can be
+    /// invoked from.
+    pub allowed_origins: Option<Vec<String>>,
+}
+
+impl ServerActions {
+    fn validate(&self) -> Result<()> {
+        if let Some(body_size_limit) = &self.body_size_limit {
+            if !body_size_limit.bytes().is_some_and(|bytes| bytes >= 1.0) {
+                bail!(
+                    "Server Actions Size Limit must be a valid number or filesize format lager \
+                     than 1MB: https://nextjs.org/docs/app/api-reference/server-actions#size-limitation"
+                );
+            }
+        }
+        if let Some(origin) = self
+            .allowed_origins
+            .iter()
+            .flatten()
+            .find(|origin| origin.is_empty() || origin.contains('/'))
+        {
+            bail!(
+                "experimental.serverActions.allowedOrigins must only contain hosts, like \
+                 \"my-proxy.com\" or \"*.my-proxy.com\", but got {origin:?}"
+            );
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -525,6 +707,32 @@ pub enum SizeLimit {
     WithUnit(String),
 }
 
+impl SizeLimit {
+    /// Returns the limit in bytes, parsing sizes with a unit like the `bytes`
+    /// package the runtime uses does, e.g. `"2mb"` or `"500 KB"`.
+    pub fn bytes(&self) -> Option<f64> {
+        let value = match self {
+            SizeLimit::Number(value) => return Some(*value),
+            SizeLimit::WithUnit(value) => value.trim(),
+        };
+        let unit_start = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(unit_start);
+        let number: f64 = number.parse().ok()?;
+        let exponent = match &*unit.trim().to_ascii_lowercase() {
+            "" | "b" => 0,
+            "kb" => 1,
+            "mb" => 2,
+            "gb" => 3,
+            "tb" => 4,
+            "pb" => 5,
+            _ => return None,
+        };
+        Some(number * 1024f64.powi(exponent))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "kebab-case")]
 pub enum MiddlewarePrefetchType {
@@ -536,14 +744,50 @@ pub enum MiddlewarePrefetchType {
 #[serde(untagged)]
 pub enum EmotionTransformOptionsOrBoolean {
     Boolean(bool),
-    Options(EmotionTransformConfig),
+    Options(EmotionTransformOptions),
+}
+
+/// The `compiler.emotion` options.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct EmotionTransformOptions {
+    /// Only applies in development, where it defaults to `true`.
+    pub source_map: Option<bool>,
+    /// Defaults to `dev-only`.
+    pub auto_label: Option<EmotionLabelKind>,
+    pub label_format: Option<String>,
+    pub import_map: Option<JsonValue>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(untagged)]
 pub enum StyledComponentsTransformOptionsOrBoolean {
     Boolean(bool),
-    Options(StyledComponentsTransformConfig),
+    Options(StyledComponentsTransformOptions),
+}
+
+/// The `compiler.styledComponents` options. Every option is optional, the
+/// ones which aren't set take the defaults of the transform.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct StyledComponentsTransformOptions {
+    /// Defaults to `true` in development and `false` in production.
+    pub display_name: Option<bool>,
+    pub top_level_import_paths: Option<Vec<String>>,
+    pub ssr: Option<bool>,
+    pub file_name: Option<bool>,
+    pub meaningless_file_names: Option<Vec<String>>,
+    /// Not supported by the SWC transform, accepted for compatibility with the
+    /// Babel plugin.
+    pub minify: Option<bool>,
+    /// Not supported by the SWC transform, accepted for compatibility with the
+    /// Babel plugin.
+    pub transpile_template_literals: Option<bool>,
+    pub namespace: Option<String>,
+    /// Not supported by the SWC transform, accepted for compatibility with the
+    /// Babel plugin.
+    pub pure: Option<bool>,
+    pub css_prop: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -570,6 +814,13 @@ pub enum RemoveConsoleConfig {
     Config { exclude: Option<Vec<String>> },
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(untagged)]
+pub enum ReactCompilerConfig {
+    Boolean(bool),
+    Options(serde_json::Value),
+}
+
 #[turbo_tasks::value_impl]
 impl NextConfig {
     #[turbo_tasks::function]
@@ -617,6 +868,67 @@ impl NextConfig {
         Ok(Vc::cell(env))
     }
 
+    /// Returns the compile time defines derived from next.config itself,
+    /// which are merged below the defines passed in by the caller. Only the
+    /// ones `getDefineEnv` doesn't provide belong here, so there's a single
+    /// source for each define.
+    /// `serverRuntimeConfig` is only included when `include_server` is set, so
+    /// it never ends up in client chunks.
+    #[turbo_tasks::function]
+    pub async fn define_env(self: Vc<Self>, include_server: bool) -> Result<Vc<EnvMap>> {
+        let this = self.await?;
+        let server_runtime_config = if include_server {
+            serde_json::to_value(&this.server_runtime_config)?
+        } else {
+            JsonValue::Object(Default::default())
+        };
+        let runtime_config = serde_json::json!({
+            "serverRuntimeConfig": server_runtime_config,
+            "publicRuntimeConfig": this.public_runtime_config,
+        });
+
+        Ok(Vc::cell(IndexMap::from([(
+            "process.env.__NEXT_RUNTIME_CONFIG".to_string(),
+            runtime_config.to_string(),
+        )])))
+    }
+
+    /// Returns the path of the custom incremental cache handler, which is
+    /// configured relative to the project directory.
+    #[turbo_tasks::function]
+    pub async fn incremental_cache_handler_path(
+        self: Vc<Self>,
+        project_path: Vc<FileSystemPath>,
+    ) -> Result<Vc<FileSystemPathOption>> {
+        let Some(path) = &self.await?.experimental.incremental_cache_handler_path else {
+            return Ok(FileSystemPathOption::none());
+        };
+        let handler_path = project_path.try_join(path.clone());
+        if handler_path.await?.is_none() {
+            InvalidIncrementalCacheHandlerIssue {
+                path: project_path,
+                handler_path: path.clone(),
+            }
+            .cell()
+            .emit();
+        }
+        Ok(handler_path)
+    }
+
+    /// Returns the `?dpl=<id>` query that is appended to client asset urls
+    /// when `experimental.deploymentId` is set, or an empty string otherwise.
+    /// The urls of chunks loaded by the Turbopack runtime don't get it yet, see
+    /// [crate::next_client::get_client_chunking_context].
+    #[turbo_tasks::function]
+    pub async fn deployment_id_query(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(match &self.await?.experimental.deployment_id {
+            Some(deployment_id) if !deployment_id.is_empty() => {
+                format!("?dpl={deployment_id}")
+            }
+            _ => String::new(),
+        }))
+    }
+
     #[turbo_tasks::function]
     pub async fn image_config(self: Vc<Self>) -> Result<Vc<ImageConfig>> {
         Ok(self.await?.images.clone().cell())
@@ -627,6 +939,30 @@ impl NextConfig {
         Ok(Vc::cell(self.await?.page_extensions.clone()))
     }
 
+    #[turbo_tasks::function]
+    pub async fn typed_routes(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.experimental.typed_routes.unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn instrumentation_hook(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .instrumentation_hook
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn service_worker(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.experimental.service_worker.unwrap_or(false),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn transpile_packages(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
         Ok(Vc::cell(
@@ -634,6 +970,17 @@ impl NextConfig {
         ))
     }
 
+    #[turbo_tasks::function]
+    pub async fn optimize_package_imports(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .optimize_package_imports
+                .clone()
+                .unwrap_or_default(),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn webpack_rules(self: Vc<Self>) -> Result<Vc<OptionWebpackRules>> {
         let this = self.await?;
@@ -650,29 +997,58 @@ impl NextConfig {
         }
         let mut rules = IndexMap::new();
         for (ext, rule) in turbo_rules {
-            fn transform_loaders(loaders: &[LoaderItem]) -> Vc<WebpackLoaderItems> {
-                Vc::cell(
+            fn transform_loaders(loaders: &[LoaderItem]) -> Result<Vc<WebpackLoaderItems>> {
+                Ok(Vc::cell(
                     loaders
                         .iter()
                         .map(|item| match item {
-                            LoaderItem::LoaderName(name) => WebpackLoaderItem {
-                                loader: name.clone(),
-                                options: Default::default(),
-                            },
-                            LoaderItem::LoaderOptions(options) => options.clone(),
+                            LoaderItem::LoaderName(name) => {
+                                let (loader, options) = parse_loader_request(name)?;
+                                Ok(WebpackLoaderItem {
+                                    loader,
+                                    options: options.unwrap_or_default(),
+                                })
+                            }
+                            LoaderItem::LoaderOptions(item) => {
+                                let (loader, options) = parse_loader_request(&item.loader)?;
+                                if options.is_some() && !item.options.is_empty() {
+                                    bail!(
+                                        "The loader {} of experimental.turbo.rules can't have \
+                                         both a query and options",
+                                        item.loader
+                                    );
+                                }
+                                Ok(WebpackLoaderItem {
+                                    loader,
+                                    options: options.unwrap_or_else(|| item.options.clone()),
+                                })
+                            }
                         })
-                        .collect(),
-                )
+                        .collect::<Result<_>>()?,
+                ))
             }
             let rule = match rule {
                 RuleConfigItem::Loaders(loaders) => LoaderRuleItem {
-                    loaders: transform_loaders(loaders),
+                    loaders: transform_loaders(loaders)?,
                     rename_as: None,
                 },
-                RuleConfigItem::Options { loaders, rename_as } => LoaderRuleItem {
-                    loaders: transform_loaders(loaders),
-                    rename_as: rename_as.clone(),
-                },
+                RuleConfigItem::Options { loaders, rename_as } => {
+                    // The `*` stands for the original file name, and the
+                    // extension after it decides the module type of the
+                    // result, e.g. `*.js` or `*.css`.
+                    if let Some(rename_as) = rename_as {
+                        if !rename_as.contains('*') {
+                            bail!(
+                                "The `as` of the experimental.turbo.rules entry {ext} must \
+                                 contain a `*`, e.g. `*.js`, but is {rename_as}"
+                            );
+                        }
+                    }
+                    LoaderRuleItem {
+                        loaders: transform_loaders(loaders)?,
+                        rename_as: rename_as.clone(),
+                    }
+                }
             };
 
             rules.insert(ext.clone(), rule);
@@ -695,6 +1071,33 @@ impl NextConfig {
         Ok(alias_map.cell())
     }
 
+    /// The extensions tried when resolving requests, in order, replacing the
+    /// default ones.
+    #[turbo_tasks::function]
+    pub async fn resolve_extensions(self: Vc<Self>) -> Result<Vc<ResolveExtensions>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.resolve_extensions.as_ref())
+                .cloned(),
+        ))
+    }
+
+    /// Whether client chunks reference source maps in development.
+    #[turbo_tasks::function]
+    pub async fn enable_dev_source_maps(self: Vc<Self>) -> Result<Vc<bool>> {
+        let source_maps = self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|t| t.source_maps)
+            .unwrap_or_default();
+        Ok(Vc::cell(source_maps == DevSourceMaps::Full))
+    }
+
     #[turbo_tasks::function]
     pub async fn mdx_rs(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(self.await?.experimental.mdx_rs.unwrap_or(false)))
@@ -719,6 +1122,36 @@ impl NextConfig {
         ))
     }
 
+    /// Returns the ID of the build, which is `development` in development. For
+    /// builds, it's the ID returned by `generateBuildId`, which is only
+    /// needed when Next.js doesn't provide the ID it generated itself. Like in
+    /// `next build`, a random ID is generated when it returns `null`.
+    #[turbo_tasks::function]
+    pub async fn build_id(self: Vc<Self>, mode: NextMode) -> Result<Vc<String>> {
+        if mode == NextMode::Development {
+            return Ok(Vc::cell("development".to_string()));
+        }
+        match &self.await?.generate_build_id {
+            Some(JsonValue::String(build_id)) if !build_id.trim().is_empty() => {
+                Ok(Vc::cell(build_id.trim().to_string()))
+            }
+            Some(JsonValue::Null) | None => Ok(Vc::cell(generate_random_build_id())),
+            Some(_) => bail!(
+                "generateBuildId did not return a string. \
+                 https://nextjs.org/docs/messages/generatebuildid-not-a-string"
+            ),
+        }
+    }
+
+    /// Returns the directory the build output is written to, relative to the
+    /// project directory.
+    #[turbo_tasks::function]
+    pub async fn dist_dir(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(normalize_dist_dir(
+            self.await?.dist_dir.as_deref(),
+        )?))
+    }
+
     #[turbo_tasks::function]
     pub async fn skip_trailing_slash_redirect(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(
@@ -726,15 +1159,42 @@ impl NextConfig {
         ))
     }
 
-    /// Returns the final asset prefix. If an assetPrefix is set, it's used.
-    /// Otherwise, the basePath is used.
+    /// Returns the validated `experimental.serverActions` options, which the
+    /// action handler reads at runtime for the body size limit and the
+    /// allowed origins.
+    #[turbo_tasks::function]
+    pub async fn server_actions(self: Vc<Self>) -> Result<Vc<OptionServerActions>> {
+        let server_actions = self.await?.experimental.server_actions.clone();
+        if let Some(server_actions) = &server_actions {
+            server_actions.validate()?;
+        }
+        Ok(Vc::cell(server_actions))
+    }
+
+    /// Returns the `crossorigin` attribute of the script and link tags loading
+    /// client chunks. Like webpack's `crossOriginLoading`, any value other than
+    /// `use-credentials` means `anonymous`. Chunks loaded by the Turbopack
+    /// runtime don't get it yet, see
+    /// [crate::next_client::get_client_chunking_context].
+    #[turbo_tasks::function]
+    pub async fn cross_origin(self: Vc<Self>) -> Result<Vc<Option<String>>> {
+        Ok(Vc::cell(self.await?.cross_origin.as_deref().map(
+            |cross_origin| match cross_origin {
+                "use-credentials" => cross_origin.to_string(),
+                _ => "anonymous".to_string(),
+            },
+        )))
+    }
+
+    /// Returns the final asset prefix. If a non-empty assetPrefix is set, it's
+    /// used. Otherwise, the basePath is used.
     #[turbo_tasks::function]
     pub async fn computed_asset_prefix(self: Vc<Self>) -> Result<Vc<Option<String>>> {
         let this = self.await?;
 
         Ok(Vc::cell(Some(format!(
             "{}/_next/",
-            if let Some(asset_prefix) = &this.asset_prefix {
+            if let Some(asset_prefix) = this.asset_prefix.as_ref().filter(|p| !p.is_empty()) {
                 asset_prefix
             } else if let Some(base_path) = &this.base_path {
                 base_path
@@ -745,6 +1205,13 @@ impl NextConfig {
         ))))
     }
 
+    #[turbo_tasks::function]
+    pub async fn enable_react_production_profiling(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.react_production_profiling.unwrap_or(false),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn enable_ppr(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(self.await?.experimental.ppr.unwrap_or(false)))
@@ -754,6 +1221,44 @@ impl NextConfig {
     pub async fn enable_taint(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(self.await?.experimental.taint.unwrap_or(false)))
     }
+
+    #[turbo_tasks::function]
+    pub async fn server_source_maps(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?.experimental.server_source_maps.unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn edge_function_size_limit(self: Vc<Self>) -> Result<Vc<u64>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .edge_function_size_limit
+                .unwrap_or(1024 * 1024),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn enable_fallback_node_polyfills(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .fallback_node_polyfills
+                .unwrap_or(true),
+        ))
+    }
+
+    /// Returns true if the experimental React channel should be used, which
+    /// is the case whenever an option relying on experimental React APIs is
+    /// enabled.
+    #[turbo_tasks::function]
+    pub async fn enable_experimental_react(self: Vc<Self>) -> Result<Vc<bool>> {
+        let experimental = &self.await?.experimental;
+        Ok(Vc::cell(
+            experimental.ppr.unwrap_or(false) || experimental.taint.unwrap_or(false),
+        ))
+    }
 }
 
 fn next_configs() -> Vc<Vec<String>> {
@@ -765,6 +1270,52 @@ fn next_configs() -> Vc<Vec<String>> {
     )
 }
 
+/// Validates the `distDir` of next.config, and returns it relative to the
+/// project directory, defaulting to `.next`. It must be inside the project, so
+/// builds with different `distDir`s from one checkout don't overwrite each
+/// other's output, and can't be the `public` directory.
+/// Generates an ID of the same form as `nanoid()`, which `next build` uses
+/// when `generateBuildId` doesn't return one: 21 URL-safe characters.
+fn generate_random_build_id() -> String {
+    const ALPHABET: &[u8] = b"useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+
+    let mut rng = rand::thread_rng();
+    (0..21)
+        .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+        .collect()
+}
+
+pub fn normalize_dist_dir(dist_dir: Option<&str>) -> Result<String> {
+    let Some(dist_dir) = dist_dir else {
+        return Ok(".next".to_string());
+    };
+    let normalized = dist_dir
+        .replace('\\', "/")
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>()
+        .join("/");
+    if normalized.is_empty() {
+        bail!(
+            "Invalid distDir provided, distDir can not be an empty string. Please remove this \
+             config or set it to undefined"
+        );
+    }
+    if dist_dir.starts_with('/')
+        || dist_dir.contains(':')
+        || normalized.split('/').any(|segment| segment == "..")
+    {
+        bail!("Invalid distDir provided, {dist_dir:?} must be inside the project directory");
+    }
+    if normalized == "public" {
+        bail!(
+            "The 'public' directory is reserved in Next.js and can not be set as the 'distDir'. \
+             https://nextjs.org/docs/messages/can-not-output-to-public"
+        );
+    }
+    Ok(normalized)
+}
+
 #[turbo_tasks::function]
 pub async fn load_next_config(execution_context: Vc<ExecutionContext>) -> Result<Vc<NextConfig>> {
     Ok(load_config_and_custom_routes(execution_context)
@@ -781,6 +1332,24 @@ pub async fn load_rewrites(execution_context: Vc<ExecutionContext>) -> Result<Vc
         .rewrites)
 }
 
+#[turbo_tasks::function]
+pub async fn load_headers(execution_context: Vc<ExecutionContext>) -> Result<Vc<Headers>> {
+    Ok(load_config_and_custom_routes(execution_context)
+        .await?
+        .custom_routes
+        .await?
+        .headers)
+}
+
+#[turbo_tasks::function]
+pub async fn load_redirects(execution_context: Vc<ExecutionContext>) -> Result<Vc<Redirects>> {
+    Ok(load_config_and_custom_routes(execution_context)
+        .await?
+        .custom_routes
+        .await?
+        .redirects)
+}
+
 #[turbo_tasks::function]
 async fn load_config_and_custom_routes(
     execution_context: Vc<ExecutionContext>,
@@ -858,6 +1427,8 @@ async fn load_next_config_and_custom_routes_internal(
             config: NextConfig::default().cell(),
             custom_routes: CustomRoutes {
                 rewrites: Rewrites::default().cell(),
+                headers: Headers::default().cell(),
+                redirects: Redirects::default().cell(),
             }
             .cell(),
         }
@@ -866,6 +1437,14 @@ async fn load_next_config_and_custom_routes_internal(
     let next_config_and_custom_routes: NextConfigAndCustomRoutesRaw =
         parse_json_with_source_context(val.to_str()?)?;
 
+    if let Some(server_actions) = &next_config_and_custom_routes
+        .config
+        .experimental
+        .server_actions
+    {
+        server_actions.validate()?;
+    }
+
     if let Some(turbo) = next_config_and_custom_routes
         .config
         .experimental
@@ -891,6 +1470,8 @@ async fn load_next_config_and_custom_routes_internal(
         config: next_config_and_custom_routes.config.cell(),
         custom_routes: CustomRoutes {
             rewrites: next_config_and_custom_routes.custom_routes.rewrites.cell(),
+            headers: Vc::cell(next_config_and_custom_routes.custom_routes.headers),
+            redirects: Vc::cell(next_config_and_custom_routes.custom_routes.redirects),
         }
         .cell(),
     }
@@ -971,3 +1552,108 @@ impl Issue for OutdatedConfigIssue {
         Vc::cell(self.description.to_string())
     }
 }
+
+#[turbo_tasks::value]
+struct InvalidIncrementalCacheHandlerIssue {
+    path: Vc<FileSystemPath>,
+    handler_path: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for InvalidIncrementalCacheHandlerIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell("Invalid experimental.incrementalCacheHandlerPath".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The incremental cache handler {:?} is outside of the project directory, so it can't \
+             be bundled and the default cache handler is used instead.",
+            self.handler_path
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{parse_loader_request, ServerActions, SizeLimit};
+
+    #[test]
+    fn parses_loader_queries() {
+        assert_eq!(
+            parse_loader_request("yaml-loader").unwrap(),
+            ("yaml-loader".to_string(), None)
+        );
+        let (loader, options) =
+            parse_loader_request("@svgr/webpack?icon&-svgo,title=Hello+world%21&dims[]=24")
+                .unwrap();
+        assert_eq!(loader, "@svgr/webpack");
+        assert_eq!(
+            serde_json::Value::Object(options.unwrap()),
+            json!({ "icon": true, "svgo": false, "title": "Hello world!", "dims": ["24"] })
+        );
+        let (_, options) = parse_loader_request(r#"graphql-tag/loader?{"esModule":true}"#).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(options.unwrap()),
+            json!({ "esModule": true })
+        );
+    }
+
+    #[test]
+    fn parses_size_limits() {
+        let bytes = |limit: &str| SizeLimit::WithUnit(limit.to_string()).bytes();
+        assert_eq!(bytes("1mb"), Some(1024.0 * 1024.0));
+        assert_eq!(bytes("500 KB"), Some(500.0 * 1024.0));
+        assert_eq!(bytes("1.5gb"), Some(1.5 * 1024.0 * 1024.0 * 1024.0));
+        assert_eq!(bytes("100"), Some(100.0));
+        assert_eq!(SizeLimit::Number(2048.0).bytes(), Some(2048.0));
+        assert_eq!(bytes("mb"), None);
+        assert_eq!(bytes("1 megabyte"), None);
+        assert_eq!(bytes(""), None);
+    }
+
+    #[test]
+    fn validates_server_actions() {
+        let server_actions = |body_size_limit: SizeLimit, allowed_origins: &[&str]| ServerActions {
+            body_size_limit: Some(body_size_limit),
+            allowed_origins: Some(allowed_origins.iter().map(|o| o.to_string()).collect()),
+        };
+        assert!(
+            server_actions(SizeLimit::WithUnit("2mb".to_string()), &["*.my-proxy.com"])
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            server_actions(SizeLimit::WithUnit("2 apples".to_string()), &[])
+                .validate()
+                .is_err()
+        );
+        assert!(server_actions(SizeLimit::Number(0.0), &[])
+            .validate()
+            .is_err());
+        assert!(
+            server_actions(SizeLimit::Number(1024.0), &["https://my-proxy.com"])
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -11,6 +11,7 @@ use turbopack_binding::{
             },
             environment::{EdgeWorkerEnvironment, Environment, ExecutionEnvironment, ServerAddr},
             free_var_references,
+            resolve::plugin::ResolvePlugin,
         },
         dev::DevChunkingContext,
         ecmascript::chunk::EcmascriptChunkingContext,
@@ -21,12 +22,24 @@ use turbopack_binding::{
 
 use crate::{
     mode::NextMode,
+    next_client::get_client_assets_path,
     next_config::NextConfig,
+    next_edge::{
+        unsupported::{
+            get_next_edge_fallback_import_map, unsupported_process_api_free_vars,
+            unsupported_runtime_api_free_vars,
+        },
+        wasm::NextEdgeWasmResolvePlugin,
+    },
     next_import_map::get_next_edge_import_map,
     next_server::context::ServerContextType,
-    next_shared::resolve::{
-        ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
-        UnsupportedModulesResolvePlugin,
+    next_shared::{
+        barrel::NextBarrelExportResolvePlugin,
+        package_imports::package_imports_conditions,
+        resolve::{
+            ModuleFeatureReportResolvePlugin, NextDuplicateReactResolvePlugin,
+            NextSharedRuntimeResolvePlugin, UnsupportedModulesResolvePlugin,
+        },
     },
     util::foreign_code_context_condition,
 };
@@ -59,9 +72,19 @@ async fn next_edge_defines(define_env: Vc<EnvMap>) -> Result<Vc<CompileTimeDefin
 async fn next_edge_free_vars(
     project_path: Vc<FileSystemPath>,
     define_env: Vc<EnvMap>,
+    mode: NextMode,
 ) -> Result<Vc<FreeVarReferences>> {
+    // Only `next build` reports unsupported APIs, development keeps running
+    // the code in the sandbox as is.
+    let unsupported_runtime_apis = match mode {
+        NextMode::Build => Some(unsupported_runtime_api_free_vars()),
+        NextMode::Development => None,
+    };
+
     Ok(free_var_references!(
         ..defines(&*define_env.await?).into_iter(),
+        ..unsupported_runtime_apis.into_iter().flatten(),
+        ..unsupported_process_api_free_vars(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "next/dist/compiled/buffer".to_string(),
             lookup_path: Some(project_path),
@@ -72,6 +95,13 @@ async fn next_edge_free_vars(
             lookup_path: Some(project_path),
             export: Some("default".to_string()),
         },
+        // There's no compiled shim for `async_hooks`, the request resolves to
+        // the module the Edge Runtime provides.
+        AsyncLocalStorage = FreeVarReference::EcmaScriptModule {
+            request: "node:async_hooks".to_string(),
+            lookup_path: Some(project_path),
+            export: Some("AsyncLocalStorage".to_string()),
+        },
     )
     .cell())
 }
@@ -81,12 +111,13 @@ pub fn get_edge_compile_time_info(
     project_path: Vc<FileSystemPath>,
     server_addr: Vc<ServerAddr>,
     define_env: Vc<EnvMap>,
+    mode: NextMode,
 ) -> Vc<CompileTimeInfo> {
     CompileTimeInfo::builder(Environment::new(Value::new(
         ExecutionEnvironment::EdgeWorker(EdgeWorkerEnvironment { server_addr }.into()),
     )))
     .defines(next_edge_defines(define_env))
-    .free_var_references(next_edge_free_vars(project_path, define_env))
+    .free_var_references(next_edge_free_vars(project_path, define_env, mode))
     .cell()
 }
 
@@ -98,8 +129,8 @@ pub async fn get_edge_resolve_options_context(
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
 ) -> Result<Vc<ResolveOptionsContext>> {
-    let next_edge_import_map =
-        get_next_edge_import_map(project_path, ty, mode, next_config, execution_context);
+    let next_edge_fallback_import_map = get_next_edge_fallback_import_map();
+    let optimize_package_imports = next_config.optimize_package_imports();
 
     let ty = ty.into_value();
 
@@ -111,26 +142,58 @@ pub async fn get_edge_resolve_options_context(
     ];
 
     match ty {
-        ServerContextType::AppRSC { .. } => custom_conditions.push("react-server".to_string()),
-        ServerContextType::AppRoute { .. }
-        | ServerContextType::Pages { .. }
+        ServerContextType::AppRSC { .. } | ServerContextType::AppRoute { .. } => {
+            custom_conditions.push("react-server".to_string())
+        }
+        ServerContextType::Pages { .. }
         | ServerContextType::PagesData { .. }
         | ServerContextType::PagesApi { .. }
         | ServerContextType::AppSSR { .. }
-        | ServerContextType::Middleware { .. } => {}
+        | ServerContextType::Middleware { .. }
+        | ServerContextType::Instrumentation => {}
     };
+    let next_edge_import_map = get_next_edge_import_map(
+        project_path,
+        Value::new(ty),
+        mode,
+        next_config,
+        execution_context,
+        package_imports_conditions(&custom_conditions, true, true),
+    );
+
+    let mut plugins: Vec<Vc<Box<dyn ResolvePlugin>>> = vec![
+        Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
+        Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
+        Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
+        Vc::upcast(NextEdgeWasmResolvePlugin::new(project_path)),
+    ];
+    if let ServerContextType::AppSSR { .. }
+    | ServerContextType::AppRSC { .. }
+    | ServerContextType::AppRoute { .. } = ty
+    {
+        plugins.push(Vc::upcast(NextDuplicateReactResolvePlugin::new(
+            project_path,
+        )));
+    }
+    if !optimize_package_imports.await?.is_empty() {
+        plugins.insert(
+            0,
+            Vc::upcast(NextBarrelExportResolvePlugin::new(
+                project_path,
+                optimize_package_imports,
+            )),
+        );
+    }
 
     let resolve_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
+        custom_extensions: next_config.resolve_extensions().await?.clone_value(),
         custom_conditions,
         import_map: Some(next_edge_import_map),
+        fallback_import_map: Some(next_edge_fallback_import_map),
         module: true,
         browser: true,
-        plugins: vec![
-            Vc::upcast(ModuleFeatureReportResolvePlugin::new(project_path)),
-            Vc::upcast(UnsupportedModulesResolvePlugin::new(project_path)),
-            Vc::upcast(NextSharedRuntimeResolvePlugin::new(project_path)),
-        ],
+        plugins,
         ..Default::default()
     };
 
@@ -147,21 +210,35 @@ pub async fn get_edge_resolve_options_context(
 }
 
 #[turbo_tasks::function]
-pub fn get_edge_chunking_context(
+pub async fn get_edge_chunking_context(
     project_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
+    client_root: Vc<FileSystemPath>,
+    asset_prefix: Vc<Option<String>>,
     environment: Vc<Environment>,
-) -> Vc<Box<dyn EcmascriptChunkingContext>> {
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
     let output_root = node_root.join("server/edge".to_string());
-    Vc::upcast(
+    // Static assets referenced from edge code are served alongside the client
+    // assets, so they need to live in the client root and respect the asset
+    // prefix (e.g. for zones served from a distinct prefix).
+    // `experimental.serverSourceMaps` lets error reporting map edge stack
+    // traces in production, like it does for Node.js server code.
+    let source_maps = should_debug("edge") || *next_config.server_source_maps().await?;
+    // TODO: `next build` should use a production chunking context here, with
+    // content hashed chunk names. `BuildChunkingContext` emits chunks loading
+    // each other with `require()`, which the edge runtime can't run, so the
+    // development context is used in both modes until Turbopack provides one.
+    Ok(Vc::upcast(
         DevChunkingContext::builder(
             project_path,
             output_root,
             output_root.join("chunks".to_string()),
-            output_root.join("assets".to_string()),
+            get_client_assets_path(client_root),
             environment,
         )
-        .reference_chunk_source_maps(should_debug("edge"))
+        .asset_base_path(asset_prefix)
+        .reference_chunk_source_maps(source_maps)
         .build(),
-    )
+    ))
 }
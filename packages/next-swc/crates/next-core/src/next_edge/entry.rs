@@ -5,12 +5,14 @@ use turbo_tasks::{Value, Vc};
 use turbo_tasks_fs::{File, FileSystemPath};
 use turbopack_binding::turbopack::{
     core::{
-        asset::AssetContent, context::AssetContext, module::Module, reference_type::ReferenceType,
-        virtual_source::VirtualSource,
+        asset::AssetContent, context::AssetContext, file_source::FileSource, module::Module,
+        reference_type::ReferenceType, virtual_source::VirtualSource,
     },
     ecmascript::utils::StringifyJs,
 };
 
+use crate::next_config::NextConfig;
+
 #[turbo_tasks::function]
 pub async fn wrap_edge_entry(
     context: Vc<Box<dyn AssetContext>>,
@@ -44,3 +46,24 @@ pub async fn wrap_edge_entry(
         Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
     ))
 }
+
+/// Returns the module of the custom incremental cache handler configured with
+/// `experimental.incrementalCacheHandlerPath`, if any. Edge entries can't
+/// require the handler at runtime, so it has to be bundled into them.
+pub async fn get_incremental_cache_handler(
+    context: Vc<Box<dyn AssetContext>>,
+    project_root: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Option<Vc<Box<dyn Module>>>> {
+    let Some(path) = *next_config
+        .incremental_cache_handler_path(project_root)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(context.process(
+        Vc::upcast(FileSource::new(path)),
+        Value::new(ReferenceType::Undefined),
+    )))
+}
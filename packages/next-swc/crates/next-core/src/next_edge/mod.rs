@@ -1,3 +1,6 @@
 pub mod context;
 pub mod entry;
 pub mod route_regex;
+pub mod single_file;
+pub mod unsupported;
+pub mod wasm;
@@ -64,7 +64,7 @@ fn parse_parameter(param: &str) -> ParsedParameter {
     }
 }
 
-fn escape_string_regexp(segment: &str) -> String {
+pub fn escape_string_regexp(segment: &str) -> String {
     regex::escape(segment)
 }
 
@@ -250,8 +250,9 @@ fn get_named_parametrized_route(
 /// prefixed to uniquely identify internally the "prefixRouteKey" arg should
 /// be "true" currently this is only the case when creating the routes-manifest
 /// during the build
-pub fn get_named_route_regex(normalized_route: &str) -> NamedRouteRegex {
-    let (parameterized_route, route_keys) = get_named_parametrized_route(normalized_route, false);
+pub fn get_named_route_regex(normalized_route: &str, prefix_route_keys: bool) -> NamedRouteRegex {
+    let (parameterized_route, route_keys) =
+        get_named_parametrized_route(normalized_route, prefix_route_keys);
     let regex = get_route_regex(normalized_route);
     NamedRouteRegex {
         regex,
@@ -0,0 +1,299 @@
+use anyhow::Result;
+use serde_json::{json, Value as JsonValue};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent, FileSystemPath},
+    turbopack::core::{
+        asset::{Asset, AssetContent},
+        ident::AssetIdent,
+        output::{OutputAsset, OutputAssets},
+    },
+};
+
+/// The JavaScript chunks of an edge function concatenated into one file, for
+/// deployment targets which only take a single file per function.
+///
+/// The Edge Runtime evaluates all files of an edge function in order in the
+/// same context, with no chunk loading, so their concatenation behaves the
+/// same as the files themselves.
+#[turbo_tasks::value(shared)]
+pub struct EdgeSingleFileAsset {
+    path: Vc<FileSystemPath>,
+    chunks: Vc<OutputAssets>,
+}
+
+/// The concatenated code of an [EdgeSingleFileAsset] and its source map, if any
+/// of the chunks has one.
+#[turbo_tasks::value]
+struct EdgeSingleFileCode {
+    code: Vc<FileContent>,
+    source_map: Option<Vc<FileContent>>,
+}
+
+#[turbo_tasks::value_impl]
+impl EdgeSingleFileAsset {
+    #[turbo_tasks::function]
+    pub fn new(path: Vc<FileSystemPath>, chunks: Vc<OutputAssets>) -> Vc<Self> {
+        EdgeSingleFileAsset { path, chunks }.cell()
+    }
+
+    /// Concatenates the JavaScript chunks byte for byte. The `sourceMappingURL`
+    /// comments of the chunks are dropped and their source maps are merged
+    /// into an index map with a section per chunk.
+    #[turbo_tasks::function]
+    async fn code(&self) -> Result<Vc<EdgeSingleFileCode>> {
+        let mut code = String::new();
+        let mut line = 0;
+        let mut sections = Vec::new();
+        for chunk in self.chunks.await?.iter() {
+            let chunk_path = chunk.ident().path().await?;
+            if chunk_path.extension_ref() != Some("js") {
+                continue;
+            }
+            let AssetContent::File(content) = *chunk.content().await? else {
+                continue;
+            };
+            let FileContent::Content(content) = &*content.await? else {
+                continue;
+            };
+
+            let map_path = format!("{}.map", chunk_path.path);
+            for reference in chunk.references().await?.iter() {
+                if reference.ident().path().await?.path != map_path {
+                    continue;
+                }
+                if let Some(map) = read_json(reference.content()).await? {
+                    add_sections(&mut sections, map, line);
+                }
+            }
+
+            line += append_chunk(&mut code, &content.content().to_str()?);
+        }
+
+        let source_map = if sections.is_empty() {
+            None
+        } else {
+            let path = self.path.await?;
+            code.push_str(&format!(
+                "//# sourceMappingURL={}.map\n",
+                urlencoding::encode(path.file_name())
+            ));
+            let source_map = json!({
+                "version": 3,
+                "sections": sections,
+            });
+            Some(File::from(source_map.to_string()).into())
+        };
+
+        Ok(EdgeSingleFileCode {
+            code: File::from(code).into(),
+            source_map,
+        }
+        .cell())
+    }
+}
+
+/// Appends the code of a chunk to `code`, without its trailing
+/// `sourceMappingURL` comment, and returns the number of lines it adds. The
+/// code is kept as it is, including its line terminators, so the offsets of the
+/// chunk's source map stay valid.
+fn append_chunk(code: &mut String, chunk: &str) -> usize {
+    let chunk = match chunk.rfind("//# sourceMappingURL=") {
+        Some(index)
+            if (index == 0 || chunk[..index].ends_with(['\n', '\r']))
+                && !chunk[index..].trim_end().contains(['\n', '\r']) =>
+        {
+            &chunk[..index]
+        }
+        _ => chunk,
+    };
+    code.push_str(chunk);
+    let mut lines = count_line_terminators(chunk);
+    if !chunk.is_empty() && !chunk.ends_with(['\n', '\r']) {
+        code.push('\n');
+        lines += 1;
+    }
+    // A chunk might not end with a semicolon.
+    code.push_str(";\n");
+    lines + 1
+}
+
+/// Counts the line terminators of JavaScript, which source map lines are
+/// separated by. `\r\n` is a single one.
+fn count_line_terminators(code: &str) -> usize {
+    let mut count = 0;
+    let mut chars = code.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                count += 1;
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Reads an asset as JSON, if it's a file with valid JSON content.
+async fn read_json(content: Vc<AssetContent>) -> Result<Option<JsonValue>> {
+    let AssetContent::File(content) = *content.await? else {
+        return Ok(None);
+    };
+    let FileContent::Content(file) = &*content.await? else {
+        return Ok(None);
+    };
+    Ok(serde_json::from_str(&file.content().to_str()?).ok())
+}
+
+/// Adds the source map of a chunk starting at `line` to the sections of an
+/// index map. Index maps can't be nested, so the sections of a chunk's index
+/// map are added with their offsets moved instead.
+fn add_sections(sections: &mut Vec<JsonValue>, map: JsonValue, line: usize) {
+    let JsonValue::Object(mut map) = map else {
+        return;
+    };
+    let Some(JsonValue::Array(chunk_sections)) = map.remove("sections") else {
+        sections.push(json!({
+            "offset": { "line": line, "column": 0 },
+            "map": map,
+        }));
+        return;
+    };
+    for mut section in chunk_sections {
+        let Some(offset) = section.get_mut("offset") else {
+            continue;
+        };
+        let section_line = offset.get("line").and_then(|l| l.as_u64()).unwrap_or(0);
+        offset["line"] = (line as u64 + section_line).into();
+        sections.push(section);
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for EdgeSingleFileAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        AssetIdent::from_path(self.path)
+    }
+
+    /// The merged source map and the assets referenced by the chunks, e.g.
+    /// wasm files. The non-JavaScript chunks aren't concatenated, so they're
+    /// emitted as they are.
+    #[turbo_tasks::function]
+    async fn references(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+        let mut references: Vec<Vc<Box<dyn OutputAsset>>> = Vec::new();
+        if self.code().await?.source_map.is_some() {
+            references.push(Vc::upcast(EdgeSingleFileSourceMapAsset::new(self)));
+        }
+        for chunk in this.chunks.await?.iter() {
+            let chunk_path = chunk.ident().path().await?;
+            if chunk_path.extension_ref() != Some("js") {
+                references.push(*chunk);
+                continue;
+            }
+            // The source maps of the chunks are merged into the one above.
+            let map_path = format!("{}.map", chunk_path.path);
+            for reference in chunk.references().await?.iter() {
+                if reference.ident().path().await?.path != map_path {
+                    references.push(*reference);
+                }
+            }
+        }
+        Ok(Vc::cell(references))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for EdgeSingleFileAsset {
+    #[turbo_tasks::function]
+    async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        Ok(AssetContent::file(self.code().await?.code))
+    }
+}
+
+/// The source map of an [EdgeSingleFileAsset].
+#[turbo_tasks::value(shared)]
+struct EdgeSingleFileSourceMapAsset {
+    asset: Vc<EdgeSingleFileAsset>,
+}
+
+#[turbo_tasks::value_impl]
+impl EdgeSingleFileSourceMapAsset {
+    #[turbo_tasks::function]
+    fn new(asset: Vc<EdgeSingleFileAsset>) -> Vc<Self> {
+        EdgeSingleFileSourceMapAsset { asset }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for EdgeSingleFileSourceMapAsset {
+    #[turbo_tasks::function]
+    async fn ident(&self) -> Result<Vc<AssetIdent>> {
+        Ok(AssetIdent::from_path(
+            self.asset.await?.path.append(".map".to_string()),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for EdgeSingleFileSourceMapAsset {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        Ok(AssetContent::file(
+            self.asset
+                .code()
+                .await?
+                .source_map
+                .unwrap_or_else(|| FileContent::NotFound.cell()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{add_sections, append_chunk};
+
+    #[test]
+    fn appends_chunks_byte_for_byte() {
+        let mut code = String::new();
+        let lines = append_chunk(
+            &mut code,
+            "a();\r\nb();\r\n//# sourceMappingURL=a.js.map\r\n",
+        );
+        assert_eq!(code, "a();\r\nb();\r\n;\n");
+        assert_eq!(lines, 3);
+
+        let lines = append_chunk(&mut code, "c()");
+        assert_eq!(code, "a();\r\nb();\r\n;\nc()\n;\n");
+        assert_eq!(lines, 2);
+    }
+
+    #[test]
+    fn keeps_source_mapping_urls_inside_the_code() {
+        let mut code = String::new();
+        let chunk = "const s = \"//# sourceMappingURL=x\";\nf();\n";
+        append_chunk(&mut code, chunk);
+        assert_eq!(code, format!("{chunk};\n"));
+    }
+
+    #[test]
+    fn moves_the_sections_of_index_maps() {
+        let mut sections = Vec::new();
+        add_sections(&mut sections, json!({ "version": 3, "mappings": "" }), 2);
+        add_sections(
+            &mut sections,
+            json!({
+                "version": 3,
+                "sections": [{ "offset": { "line": 1, "column": 0 }, "map": {} }],
+            }),
+            5,
+        );
+        assert_eq!(sections[0]["offset"]["line"], 2);
+        assert_eq!(sections[1]["offset"]["line"], 6);
+    }
+}
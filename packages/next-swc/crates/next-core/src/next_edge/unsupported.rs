@@ -0,0 +1,780 @@
+use anyhow::Result;
+use indoc::formatdoc;
+use swc_core::{
+    common::{source_map::Pos, Span},
+    ecma::{
+        ast::{AssignExpr, Expr, Lit, MemberExpr, MemberProp},
+        visit::{Visit, VisitWith},
+    },
+};
+use turbo_tasks::{
+    graph::{GraphTraversal, NonDeterministic},
+    Vc,
+};
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent, FileSystemPath},
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            compile_time_info::FreeVarReference,
+            issue::{Issue, IssueExt, IssueSeverity, IssueSource, OptionIssueSource},
+            module::Module,
+            reference::primary_referenced_modules,
+            resolve::{
+                options::{ImportMap, ImportMapResult, ImportMapping, ImportMappingReplacement},
+                parse::Request,
+                ResolveResult,
+            },
+            virtual_source::VirtualSource,
+        },
+        ecmascript::{
+            analyzer::{graph::EvalContext, JsValue},
+            parse::ParseResult,
+            EcmascriptModuleAsset,
+        },
+    },
+};
+
+use crate::{
+    embed_js::next_js_file_path,
+    mode::NextMode,
+    next_shared::{
+        module_requests::module_requests,
+        optional_peer_dependencies::insert_optional_peer_dependency_alias,
+    },
+};
+
+/// Node.js builtins which are available in the Edge Runtime.
+/// https://github.com/vercel/next.js/blob/ddb1f7f0ed1f6fec2c6d1a1e5c732e8a5167ba20/packages/next/src/build/webpack/plugins/middleware-plugin.ts#L765-L771
+const SUPPORTED_NATIVE_MODULES: [&str; 5] = ["buffer", "events", "assert", "util", "async_hooks"];
+
+/// All other Node.js builtins, which don't exist in the Edge Runtime.
+const UNSUPPORTED_NATIVE_MODULES: [&str; 41] = [
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "dns/promises",
+    "domain",
+    "fs",
+    "fs/promises",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "path/posix",
+    "path/win32",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "stream/promises",
+    "stream/web",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "v8",
+    "vm",
+    "worker_threads",
+];
+
+/// Globals of Node.js which don't exist in the Edge Runtime.
+/// https://github.com/vercel/next.js/blob/ddb1f7f0ed1f6fec2c6d1a1e5c732e8a5167ba20/packages/next/src/build/webpack/plugins/middleware-plugin.ts#L733-L750
+const UNSUPPORTED_RUNTIME_APIS: [&str; 16] = [
+    "clearImmediate",
+    "setImmediate",
+    "BroadcastChannel",
+    "ByteLengthQueuingStrategy",
+    "CompressionStream",
+    "CountQueuingStrategy",
+    "DecompressionStream",
+    "DomException",
+    "MessageChannel",
+    "MessageEvent",
+    "MessagePort",
+    "ReadableByteStreamController",
+    "ReadableStreamBYOBRequest",
+    "ReadableStreamDefaultController",
+    "TransformStreamDefaultController",
+    "WritableStreamDefaultController",
+];
+
+/// The request references to an unsupported API are replaced with. It never
+/// resolves from `node_modules`, so it's handled by the fallback import map.
+fn unsupported_runtime_api_request(api: &str) -> String {
+    format!("__next_edge_unsupported_api__/{api}")
+}
+
+/// Returns the free variables replacing references to APIs which don't exist
+/// in the Edge Runtime. The requests are resolved from the referencing module,
+/// so the issue can point to it.
+pub fn unsupported_runtime_api_free_vars() -> impl Iterator<Item = (Vec<String>, FreeVarReference)>
+{
+    UNSUPPORTED_RUNTIME_APIS.into_iter().map(|api| {
+        (
+            vec![api.to_string()],
+            FreeVarReference::EcmaScriptModule {
+                request: unsupported_runtime_api_request(api),
+                lookup_path: None,
+                export: Some("default".to_string()),
+            },
+        )
+    })
+}
+
+/// Properties of Node.js' `process` besides `env`, which don't exist in the
+/// Edge Runtime, but are provided by the `process` polyfill. Accesses to any
+/// property but `env` are reported by [check_edge_runtime_module_graph].
+const UNSUPPORTED_PROCESS_APIS: [&str; 53] = [
+    "abort",
+    "allowedNodeEnvironmentFlags",
+    "arch",
+    "argv",
+    "argv0",
+    "channel",
+    "chdir",
+    "config",
+    "connected",
+    "cpuUsage",
+    "cwd",
+    "debugPort",
+    "disconnect",
+    "dlopen",
+    "emitWarning",
+    "execArgv",
+    "execPath",
+    "exit",
+    "exitCode",
+    "features",
+    "getActiveResourcesInfo",
+    "getegid",
+    "geteuid",
+    "getgid",
+    "getgroups",
+    "getuid",
+    "hasUncaughtExceptionCaptureCallback",
+    "hrtime",
+    "kill",
+    "memoryUsage",
+    "nextTick",
+    "pid",
+    "platform",
+    "ppid",
+    "release",
+    "report",
+    "resourceUsage",
+    "send",
+    "setegid",
+    "seteuid",
+    "setgid",
+    "setgroups",
+    "setSourceMapsEnabled",
+    "setuid",
+    "setUncaughtExceptionCaptureCallback",
+    "stderr",
+    "stdin",
+    "stdout",
+    "title",
+    "umask",
+    "uptime",
+    "version",
+    "versions",
+];
+
+fn unsupported_process_api_request(property: &str) -> String {
+    format!("__next_edge_unsupported_api__/process.{property}")
+}
+
+/// Returns the free variables replacing `process.*` accesses other than
+/// `process.env`, like webpack's `warnForUnsupportedProcessApi`.
+pub fn unsupported_process_api_free_vars() -> impl Iterator<Item = (Vec<String>, FreeVarReference)>
+{
+    UNSUPPORTED_PROCESS_APIS.into_iter().map(|property| {
+        (
+            vec!["process".to_string(), property.to_string()],
+            FreeVarReference::EcmaScriptModule {
+                request: unsupported_process_api_request(property),
+                lookup_path: None,
+                export: Some("default".to_string()),
+            },
+        )
+    })
+}
+
+/// The name of the module replacing imports of `module`, which is used to find
+/// them in the module graph, see [check_edge_runtime_module_graph].
+fn unsupported_module_file_name(module: &str) -> String {
+    format!("{}.js", module.replace('/', "_"))
+}
+
+/// Resolves the `node:` requests of the Node.js builtins provided by the Edge
+/// Runtime to the runtime's modules. Unlike bare names, these can't refer to a
+/// package, so they don't need to wait for the fallback import map.
+pub fn insert_next_edge_node_builtin_aliases(import_map: &mut ImportMap) {
+    for module in SUPPORTED_NATIVE_MODULES {
+        import_map.insert_exact_alias(
+            format!("node:{module}"),
+            ImportMapping::External(Some(format!("node:{module}"))).cell(),
+        );
+    }
+}
+
+/// Computes the fallback import map of the edge contexts. It only applies to
+/// requests which don't resolve otherwise, so a package shadowing a Node.js
+/// builtin still takes precedence, like in webpack.
+#[turbo_tasks::function]
+pub fn get_next_edge_fallback_import_map() -> Vc<ImportMap> {
+    let mut import_map = ImportMap::empty();
+
+    // The `node:` requests are part of the primary import map, see
+    // [insert_next_edge_node_builtin_aliases].
+    for module in SUPPORTED_NATIVE_MODULES {
+        import_map.insert_exact_alias(
+            module,
+            ImportMapping::External(Some(format!("node:{module}"))).cell(),
+        );
+    }
+
+    for module in UNSUPPORTED_NATIVE_MODULES {
+        let unsupported = ImportMapping::Dynamic(Vc::upcast(
+            NextEdgeUnsupportedModuleReplacer::new(module.to_string()),
+        ))
+        .cell();
+        import_map.insert_exact_alias(format!("node:{module}"), unsupported);
+        import_map.insert_exact_alias(module, unsupported);
+    }
+
+    for api in UNSUPPORTED_RUNTIME_APIS {
+        import_map.insert_exact_alias(
+            unsupported_runtime_api_request(api),
+            ImportMapping::Dynamic(Vc::upcast(NextEdgeUnsupportedApiReplacer::new(
+                api.to_string(),
+            )))
+            .cell(),
+        );
+    }
+
+    for property in UNSUPPORTED_PROCESS_APIS {
+        import_map.insert_exact_alias(
+            unsupported_process_api_request(property),
+            ImportMapping::Dynamic(Vc::upcast(NextEdgeUnsupportedProcessApiReplacer::new(
+                property.to_string(),
+            )))
+            .cell(),
+        );
+    }
+
+    insert_optional_peer_dependency_alias(&mut import_map);
+
+    import_map.cell()
+}
+
+/// Replaces imports of Node.js builtins in edge code with a module throwing
+/// at runtime. They're reported for each edge entry by
+/// [check_edge_runtime_module_graph], as the import map is shared by all of
+/// them and only knows the directory of the importing module.
+#[turbo_tasks::value(shared)]
+struct NextEdgeUnsupportedModuleReplacer {
+    module: String,
+}
+
+#[turbo_tasks::value_impl]
+impl NextEdgeUnsupportedModuleReplacer {
+    #[turbo_tasks::function]
+    fn new(module: String) -> Vc<Self> {
+        Self::cell(NextEdgeUnsupportedModuleReplacer { module })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextEdgeUnsupportedModuleReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        _context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let module = &self.module;
+        let content = formatdoc! {
+            r#"
+                module.exports = globalThis.__import_unsupported({});
+            "#,
+            serde_json::to_string(module)?
+        };
+        let source = VirtualSource::new(
+            next_js_file_path(format!(
+                "internal/edge-unsupported/{}",
+                unsupported_module_file_name(module)
+            )),
+            AssetContent::file(FileContent::Content(File::from(content)).cell()),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(source)).into()).into())
+    }
+}
+
+/// Replaces a reference to an API which doesn't exist in the Edge Runtime with
+/// the global, which is `undefined` at runtime, and reports it as an error.
+///
+/// Packages commonly feature-detect these APIs, so references from
+/// `node_modules` aren't reported.
+#[turbo_tasks::value(shared)]
+struct NextEdgeUnsupportedApiReplacer {
+    api: String,
+}
+
+#[turbo_tasks::value_impl]
+impl NextEdgeUnsupportedApiReplacer {
+    #[turbo_tasks::function]
+    fn new(api: String) -> Vc<Self> {
+        Self::cell(NextEdgeUnsupportedApiReplacer { api })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextEdgeUnsupportedApiReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let api = &self.api;
+
+        if !context.await?.path.contains("node_modules") {
+            UnsupportedApiInEdgeRuntimeIssue {
+                context,
+                api: api.clone(),
+                severity: IssueSeverity::Error.cell(),
+            }
+            .cell()
+            .emit();
+        }
+
+        let content = formatdoc! {
+            r#"
+                export default globalThis.{api};
+            "#
+        };
+        let source = VirtualSource::new(
+            next_js_file_path(format!("internal/edge-unsupported-api/{api}.js")),
+            AssetContent::file(FileContent::Content(File::from(content)).cell()),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(source)).into()).into())
+    }
+}
+
+/// Replaces an access to a `process` property which doesn't exist in the Edge
+/// Runtime with the property of the `process` polyfill. Accesses are reported
+/// by [check_edge_runtime_module_graph].
+#[turbo_tasks::value(shared)]
+struct NextEdgeUnsupportedProcessApiReplacer {
+    property: String,
+}
+
+#[turbo_tasks::value_impl]
+impl NextEdgeUnsupportedProcessApiReplacer {
+    #[turbo_tasks::function]
+    fn new(property: String) -> Vc<Self> {
+        Self::cell(NextEdgeUnsupportedProcessApiReplacer { property })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextEdgeUnsupportedProcessApiReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        _context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let property = &self.property;
+
+        // Methods are bound, as the access is replaced with a plain value.
+        let content = formatdoc! {
+            r#"
+                import process from "next/dist/build/polyfills/process";
+                const value = process.{property};
+                export default typeof value === "function" ? value.bind(process) : value;
+            "#
+        };
+        let source = VirtualSource::new(
+            next_js_file_path(format!(
+                "internal/edge-unsupported-api/process.{property}.js"
+            )),
+            AssetContent::file(FileContent::Content(File::from(content)).cell()),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(source)).into()).into())
+    }
+}
+
+async fn get_referenced_modules(
+    parent: Vc<Box<dyn Module>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn Module>>> + Send> {
+    primary_referenced_modules(parent)
+        .await
+        .map(|modules| modules.clone_value().into_iter())
+}
+
+/// Checks the module graph of the edge entry `page` for code which doesn't
+/// work in the Edge Runtime, and reports it at the module:
+///
+/// - Imports of Node.js modules which don't exist in the Edge Runtime. Imports
+///   in `node_modules` are only a warning, as packages commonly import them in
+///   code which doesn't run in the Edge Runtime.
+/// - Accesses and assignments to properties of `process` other than `env`, like
+///   webpack's `warnForUnsupportedProcessApi`. These are only a warning in
+///   development, as the code can still run until the property is used, and
+///   aren't reported in `node_modules`.
+pub async fn check_edge_runtime_module_graph(
+    page: &str,
+    mode: NextMode,
+    entries: impl IntoIterator<Item = Vc<Box<dyn Module>>>,
+) -> Result<()> {
+    let modules = NonDeterministic::new()
+        .skip_duplicates()
+        .visit(entries, get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner();
+
+    for module in modules {
+        let Some(ecmascript_asset) =
+            Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+        else {
+            continue;
+        };
+        let ParseResult::Ok {
+            program,
+            eval_context,
+            ..
+        } = &*ecmascript_asset.parse().await?
+        else {
+            continue;
+        };
+
+        let mut unsupported = Vec::new();
+        for referenced in primary_referenced_modules(module).await?.iter() {
+            let path = referenced.ident().path().await?;
+            if !path.path.contains("internal/edge-unsupported/") {
+                continue;
+            }
+            if let Some(name) = UNSUPPORTED_NATIVE_MODULES
+                .into_iter()
+                .find(|name| path.file_name() == unsupported_module_file_name(name))
+            {
+                unsupported.push(name);
+            }
+        }
+
+        let path = module.ident().path();
+        let in_node_modules = path.await?.path.contains("node_modules");
+        let source = ecmascript_asset.await?.source;
+        let issue_source = |span: Span| {
+            IssueSource::from_byte_offset(source, span.lo.to_usize(), span.hi.to_usize())
+        };
+
+        if !unsupported.is_empty() {
+            let severity = if in_node_modules {
+                IssueSeverity::Warning
+            } else {
+                IssueSeverity::Error
+            };
+            for (request, span) in module_requests(program) {
+                let name = request.strip_prefix("node:").unwrap_or(&request);
+                if !unsupported.contains(&name) {
+                    continue;
+                }
+                NodeModuleInEdgeRuntimeIssue {
+                    path,
+                    module: name.to_string(),
+                    page: page.to_string(),
+                    severity: severity.cell(),
+                    source: issue_source(span),
+                }
+                .cell()
+                .emit();
+            }
+        }
+
+        if !in_node_modules {
+            let severity = match mode {
+                NextMode::Development => IssueSeverity::Warning,
+                NextMode::Build => IssueSeverity::Error,
+            };
+            let mut visitor = ProcessApisVisitor {
+                eval_context,
+                is_assignment: false,
+                accesses: Vec::new(),
+            };
+            program.visit_with(&mut visitor);
+            for (api, is_assignment, span) in visitor.accesses {
+                UnsupportedProcessApiInEdgeRuntimeIssue {
+                    path,
+                    api,
+                    is_assignment,
+                    severity: severity.cell(),
+                    source: issue_source(span),
+                }
+                .cell()
+                .emit();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds accesses and assignments to properties of the global `process` other
+/// than `env`.
+struct ProcessApisVisitor<'a> {
+    eval_context: &'a EvalContext,
+    /// Whether the visited member expression is the target of an assignment.
+    is_assignment: bool,
+    accesses: Vec<(String, bool, Span)>,
+}
+
+impl ProcessApisVisitor<'_> {
+    /// Returns the accessed API if `member` accesses a property of the global
+    /// `process` other than `env`.
+    fn process_api(&self, member: &MemberExpr) -> Option<String> {
+        let Expr::Ident(obj) = &*member.obj else {
+            return None;
+        };
+        if &*obj.sym != "process"
+            || !matches!(
+                self.eval_context.eval(&member.obj),
+                JsValue::FreeVar(name) if &*name == "process"
+            )
+        {
+            return None;
+        }
+        match &member.prop {
+            MemberProp::Ident(prop) if &*prop.sym == "env" => None,
+            MemberProp::Ident(prop) => Some(format!("process.{}", prop.sym)),
+            MemberProp::Computed(prop) => match &*prop.expr {
+                Expr::Lit(Lit::Str(prop)) if &*prop.value == "env" => None,
+                Expr::Lit(Lit::Str(prop)) => Some(format!("process.{}", prop.value)),
+                _ => Some("process[…]".to_string()),
+            },
+            MemberProp::PrivateName(_) => None,
+        }
+    }
+}
+
+impl Visit for ProcessApisVisitor<'_> {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        self.is_assignment = true;
+        assign.left.visit_with(self);
+        self.is_assignment = false;
+        assign.right.visit_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        // Only the outermost member expression of the target is assigned.
+        let is_assignment = std::mem::take(&mut self.is_assignment);
+        if let Some(api) = self.process_api(member) {
+            self.accesses.push((api, is_assignment, member.span));
+        }
+        member.visit_children_with(self);
+    }
+}
+
+/// Describes how to fix a Node.js module being imported into the edge entry
+/// `page`.
+fn edge_entry_hint(page: &str) -> String {
+    match page {
+        "/middleware" => "Remove the import, or move the code using it to an API route or a route \
+                          handler running in the Node.js runtime."
+            .to_string(),
+        "/instrumentation" => "Only import the module when `process.env.NEXT_RUNTIME` is \
+                               `\"nodejs\"`, as the instrumentation hook is also compiled for the \
+                               Edge Runtime."
+            .to_string(),
+        _ => format!(
+            "Remove the import or switch `{page}` to the Node.js runtime. It runs in the Edge \
+             Runtime as it, or for an app page one of its layouts, exports `runtime = \"edge\"`."
+        ),
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct NodeModuleInEdgeRuntimeIssue {
+    path: Vc<FileSystemPath>,
+    module: String,
+    page: String,
+    severity: Vc<IssueSeverity>,
+    source: Vc<IssueSource>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NodeModuleInEdgeRuntimeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "A Node.js module is loaded ('{}') which is not supported in the Edge Runtime",
+            self.module
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The module is part of `{}`, which runs in the Edge Runtime. {}\nThe Edge Runtime \
+             only supports these Node.js modules: {}.",
+            self.page,
+            edge_entry_hint(&self.page),
+            SUPPORTED_NATIVE_MODULES.join(", ")
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell("https://nextjs.org/docs/messages/node-module-in-edge-runtime".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn source(&self) -> Vc<OptionIssueSource> {
+        Vc::cell(Some(self.source))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnsupportedProcessApiInEdgeRuntimeIssue {
+    path: Vc<FileSystemPath>,
+    api: String,
+    is_assignment: bool,
+    severity: Vc<IssueSeverity>,
+    source: Vc<IssueSource>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedProcessApiInEdgeRuntimeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "A Node.js API is {} ({}) which is not supported in the Edge Runtime",
+            if self.is_assignment {
+                "assigned"
+            } else {
+                "used"
+            },
+            self.api
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell("`process` only provides `process.env` in the Edge Runtime.".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell("https://nextjs.org/docs/api-reference/edge-runtime".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn source(&self) -> Vc<OptionIssueSource> {
+        Vc::cell(Some(self.source))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnsupportedApiInEdgeRuntimeIssue {
+    context: Vc<FileSystemPath>,
+    api: String,
+    severity: Vc<IssueSeverity>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedApiInEdgeRuntimeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "A Node.js API is used ({}) which is not supported in the Edge Runtime",
+            self.api
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell("https://nextjs.org/docs/api-reference/edge-runtime".to_string())
+    }
+}
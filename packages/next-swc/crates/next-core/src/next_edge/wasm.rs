@@ -0,0 +1,147 @@
+use anyhow::{bail, Result};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{glob::Glob, File, FileContent, FileSystemPath},
+    turbopack::{
+        core::{
+            asset::{Asset, AssetContent},
+            file_source::FileSource,
+            ident::AssetIdent,
+            module::Module,
+            resolve::{
+                parse::Request,
+                plugin::{ResolvePlugin, ResolvePluginCondition},
+                ResolveResult, ResolveResultOption,
+            },
+            source::Source,
+        },
+        ecmascript::EcmascriptModuleAsset,
+    },
+};
+
+use crate::util::content_hash;
+
+fn modifier() -> Vc<String> {
+    Vc::cell("edge wasm binding".to_string())
+}
+
+/// The name of the global variable the Edge Runtime provides the compiled
+/// `WebAssembly.Module` of `wasm` in. It's also the name of the binding in
+/// the `wasm` field of the middleware manifest.
+#[turbo_tasks::function]
+pub async fn edge_wasm_binding_name(wasm: Vc<Box<dyn Source>>) -> Result<Vc<String>> {
+    let AssetContent::File(content) = *wasm.content().await? else {
+        bail!("Input source is not a file and can't be bound as a WebAssembly module");
+    };
+    let FileContent::Content(content) = &*content.await? else {
+        bail!(
+            "WebAssembly module {} not found",
+            wasm.ident().to_string().await?
+        );
+    };
+    let hash = content_hash(&content.content().to_bytes()?);
+    Ok(Vc::cell(format!("wasm_{hash:016x}")))
+}
+
+/// A JavaScript module exporting the `WebAssembly.Module` of a `.wasm` file,
+/// like the `next-middleware-wasm-loader` of webpack. The Edge Runtime can't
+/// compile WebAssembly from code, so the module only reads the global the
+/// runtime compiled it into.
+#[turbo_tasks::value(shared)]
+pub struct NextEdgeWasmBindingSource {
+    pub wasm: Vc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value_impl]
+impl Source for NextEdgeWasmBindingSource {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.wasm
+            .ident()
+            .with_modifier(modifier())
+            .rename_as("*.mjs".to_string())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for NextEdgeWasmBindingSource {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let name = edge_wasm_binding_name(self.wasm).await?;
+        Ok(AssetContent::file(
+            FileContent::Content(File::from(format!("export default globalThis.{name};\n"))).cell(),
+        ))
+    }
+}
+
+/// Resolves `.wasm` files imported by edge code to a
+/// [NextEdgeWasmBindingSource].
+#[turbo_tasks::value]
+pub(crate) struct NextEdgeWasmResolvePlugin {
+    root: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextEdgeWasmResolvePlugin {
+    #[turbo_tasks::function]
+    pub fn new(root: Vc<FileSystemPath>) -> Vc<Self> {
+        NextEdgeWasmResolvePlugin { root }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for NextEdgeWasmResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> Vc<ResolvePluginCondition> {
+        ResolvePluginCondition::new(self.root.root(), Glob::new("**/*.wasm".to_string()))
+    }
+
+    #[turbo_tasks::function]
+    fn after_resolve(
+        &self,
+        fs_path: Vc<FileSystemPath>,
+        _context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Vc<ResolveResultOption> {
+        let source = NextEdgeWasmBindingSource {
+            wasm: Vc::upcast(FileSource::new(fs_path)),
+        }
+        .cell();
+        ResolveResultOption::some(ResolveResult::source(Vc::upcast(source)).cell())
+    }
+}
+
+/// A `.wasm` file bound to a global variable of the Edge Runtime.
+#[turbo_tasks::value(shared)]
+pub struct EdgeWasmBinding {
+    pub name: String,
+    pub wasm: Vc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionEdgeWasmBinding(Option<Vc<EdgeWasmBinding>>);
+
+/// Returns the binding of `module` if it's the module of a
+/// [NextEdgeWasmBindingSource].
+#[turbo_tasks::function]
+pub async fn edge_wasm_binding(module: Vc<Box<dyn Module>>) -> Result<Vc<OptionEdgeWasmBinding>> {
+    let Some(ecmascript_asset) =
+        Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+    else {
+        return Ok(Vc::cell(None));
+    };
+    let Some(source) =
+        Vc::try_resolve_downcast_type::<NextEdgeWasmBindingSource>(ecmascript_asset.await?.source)
+            .await?
+    else {
+        return Ok(Vc::cell(None));
+    };
+    let wasm = source.await?.wasm;
+    Ok(Vc::cell(Some(
+        EdgeWasmBinding {
+            name: edge_wasm_binding_name(wasm).await?.clone_value(),
+            wasm,
+        }
+        .cell(),
+    )))
+}
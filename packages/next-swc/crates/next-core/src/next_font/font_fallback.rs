@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use turbo_tasks::Vc;
 use turbopack_binding::turbo::tasks::trace::TraceRawVcs;
 
-pub(crate) struct DefaultFallbackFont {
+pub struct DefaultFallbackFont {
     pub name: String,
     pub capsize_key: String,
     pub az_avg_width: f64,
@@ -11,21 +11,19 @@ pub(crate) struct DefaultFallbackFont {
 }
 
 // From https://github.com/vercel/next.js/blob/a3893bf69c83fb08e88c87bf8a21d987a0448c8e/packages/font/src/utils.ts#L4
-pub(crate) static DEFAULT_SANS_SERIF_FONT: Lazy<DefaultFallbackFont> =
-    Lazy::new(|| DefaultFallbackFont {
-        name: "Arial".to_owned(),
-        capsize_key: "arial".to_owned(),
-        az_avg_width: 934.5116279069767,
-        units_per_em: 2048,
-    });
+pub static DEFAULT_SANS_SERIF_FONT: Lazy<DefaultFallbackFont> = Lazy::new(|| DefaultFallbackFont {
+    name: "Arial".to_owned(),
+    capsize_key: "arial".to_owned(),
+    az_avg_width: 934.5116279069767,
+    units_per_em: 2048,
+});
 
-pub(crate) static DEFAULT_SERIF_FONT: Lazy<DefaultFallbackFont> =
-    Lazy::new(|| DefaultFallbackFont {
-        name: "Times New Roman".to_owned(),
-        capsize_key: "timesNewRoman".to_owned(),
-        az_avg_width: 854.3953488372093,
-        units_per_em: 2048,
-    });
+pub static DEFAULT_SERIF_FONT: Lazy<DefaultFallbackFont> = Lazy::new(|| DefaultFallbackFont {
+    name: "Times New Roman".to_owned(),
+    capsize_key: "timesNewRoman".to_owned(),
+    az_avg_width: 854.3953488372093,
+    units_per_em: 2048,
+});
 
 /// An automatically generated fallback font generated by next/font.
 #[turbo_tasks::value(shared)]
@@ -58,7 +56,7 @@ pub(crate) struct FontFallbacks(Vec<Vc<FontFallback>>);
 /// the main webfont. Rendered as e.g. `ascent-override: 56.8%;` in the
 /// stylesheet
 #[derive(Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
-pub(crate) struct FontAdjustment {
+pub struct FontAdjustment {
     pub ascent: f64,
     pub descent: f64,
     pub line_gap: f64,
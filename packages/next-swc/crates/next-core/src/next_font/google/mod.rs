@@ -39,7 +39,7 @@ use self::{
     font_fallback::get_font_fallback,
     options::{options_from_request, FontDataEntry, FontWeights, NextFontGoogleOptions},
     stylesheet::build_stylesheet,
-    util::{get_font_axes, get_stylesheet_url},
+    util::{find_font_files_in_css, get_font_axes, get_stylesheet_url},
 };
 use super::{
     font_fallback::FontFallback,
@@ -47,7 +47,11 @@ use super::{
         get_request_hash, get_request_id, get_scoped_font_family, FontCssProperties, FontFamilyType,
     },
 };
-use crate::{embed_js::next_js_file_path, util::load_next_js_templateon};
+use crate::{
+    embed_js::next_js_file_path,
+    mode::NextMode,
+    util::{content_hash, load_next_js_templateon},
+};
 
 pub mod font_fallback;
 pub mod options;
@@ -55,6 +59,7 @@ pub mod request;
 pub mod stylesheet;
 pub mod util;
 
+pub const GOOGLE_FONTS_FONT_FILE_REQUEST: &str = "@vercel/turbopack-next/internal/font/google/font";
 pub const GOOGLE_FONTS_STYLESHEET_URL: &str = "https://fonts.googleapis.com/css2";
 // Always sending this user agent ensures consistent results from Google Fonts.
 // Google Fonts will vary responses based on user agent, e.g. only returning
@@ -233,16 +238,16 @@ impl ImportMappingReplacement for NextFontGoogleCssModuleReplacer {
             )
             .await?;
 
+        let font_fallback = get_font_fallback(self.project_path, options, request_hash);
         let stylesheet = match stylesheet_str {
             Some(s) => Some(
-                update_google_stylesheet(s, options, scoped_font_family)
+                update_google_stylesheet(s, options, scoped_font_family, font_fallback)
                     .await?
                     .clone_value(),
             ),
             None => None,
         };
 
-        let font_fallback = get_font_fallback(self.project_path, options, request_hash);
         let css_asset = VirtualSource::new(
             css_virtual_path,
             AssetContent::file(
@@ -263,6 +268,114 @@ impl ImportMappingReplacement for NextFontGoogleCssModuleReplacer {
     }
 }
 
+#[derive(Debug)]
+struct NextFontGoogleFontFileOptions {
+    url: String,
+    preload: bool,
+    has_size_adjust: bool,
+}
+
+#[turbo_tasks::value(shared)]
+pub struct NextFontGoogleFontFileReplacer {
+    mode: NextMode,
+}
+
+#[turbo_tasks::value_impl]
+impl NextFontGoogleFontFileReplacer {
+    #[turbo_tasks::function]
+    pub fn new(mode: NextMode) -> Vc<Self> {
+        Self::cell(NextFontGoogleFontFileReplacer { mode })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextFontGoogleFontFileReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    /// Intercepts requests for the font files referenced in the stylesheet
+    /// generated by the above replacer. Downloads the font file from Google
+    /// Fonts and returns it as a VirtualSource, so it's emitted like any other
+    /// asset. Like for webpack, the file name is marked with `-s` when the font
+    /// uses an adjusted fallback and with `.p` when it should be preloaded.
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        _context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let request = &*request.await?;
+        let Request::Module {
+            module: _,
+            path: _,
+            query: query_vc,
+        } = request
+        else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        let options = font_file_options_from_query_map(&query_vc.await?)?;
+        let ext = options
+            .url
+            .rsplit_once('.')
+            .map(|(_, ext)| ext)
+            .filter(|ext| ["woff", "woff2", "eot", "ttf", "otf"].contains(ext))
+            .with_context(|| format!("Unexpected font file url {}", options.url))?;
+        let name = format!(
+            "{:x?}{}{}",
+            content_hash(options.url.as_bytes()),
+            if options.has_size_adjust { "-s" } else { "" },
+            if options.preload { ".p" } else { "" },
+        );
+        let font_virtual_path =
+            next_js_file_path("internal/font/google".to_string()).join(format!("{name}.{ext}"));
+
+        // When running Next.js integration tests, the font files of the mocked
+        // responses aren't downloaded. Like for webpack, their url is used as the
+        // content to keep the files unique.
+        let env = Vc::upcast::<Box<dyn ProcessEnv>>(CommandLineProcessEnv::new());
+        let font = if env
+            .read("NEXT_FONT_GOOGLE_MOCKED_RESPONSES".to_string())
+            .await?
+            .is_some()
+        {
+            File::from(options.url.clone())
+        } else if let Some(font) =
+            fetch_real_font_file(&options.url, font_virtual_path, self.mode).await?
+        {
+            font
+        } else if self.mode == NextMode::Development {
+            // In development, a font that failed to download is emitted as an
+            // empty file after the warning, so the browser uses the fallback font
+            // instead of the stylesheet failing to resolve.
+            File::from("")
+        } else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        let font_source = VirtualSource::new(
+            font_virtual_path,
+            AssetContent::file(FileContent::Content(font).cell()),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(font_source)).into()).into())
+    }
+}
+
+fn font_file_options_from_query_map(query: &str) -> Result<NextFontGoogleFontFileOptions> {
+    let query_map = qstring::QString::from(query);
+    Ok(NextFontGoogleFontFileOptions {
+        url: query_map
+            .get("url")
+            .context("next/font/google font file requests must have a url")?
+            .to_owned(),
+        preload: query_map.get("preload") == Some("true"),
+        has_size_adjust: query_map.get("has_size_adjust") == Some("true"),
+    })
+}
+
 #[turbo_tasks::function]
 async fn load_font_data(project_root: Vc<FileSystemPath>) -> Result<Vc<FontData>> {
     let data: FontData = load_next_js_templateon(
@@ -275,19 +388,49 @@ async fn load_font_data(project_root: Vc<FileSystemPath>) -> Result<Vc<FontData>
 }
 
 /// Updates references to the unscoped font family from Google to use scoped
-/// font family names.
+/// font family names, and references to the font files to requests of
+/// [NextFontGoogleFontFileReplacer], which self-hosts them.
 #[turbo_tasks::function]
 async fn update_google_stylesheet(
     stylesheet: Vc<String>,
     options: Vc<NextFontGoogleOptions>,
     scoped_font_family: Vc<String>,
+    font_fallback: Vc<FontFallback>,
 ) -> Result<Vc<String>> {
+    let options = &*options.await?;
+
     // Update font-family definitions to the scoped name
     // TODO: Do this more resiliently, e.g. transforming an swc ast
-    Ok(Vc::cell(stylesheet.await?.replace(
-        &format!("font-family: '{}';", &*options.await?.font_family),
+    let mut stylesheet = stylesheet.await?.replace(
+        &format!("font-family: '{}';", &*options.font_family),
         &format!("font-family: '{}';", &*scoped_font_family.await?),
-    )))
+    );
+
+    let has_size_adjust = match &*font_fallback.await? {
+        FontFallback::Automatic(fallback) => fallback.await?.adjustment.is_some(),
+        FontFallback::Manual(_) | FontFallback::Error => false,
+    };
+    let subsets_to_preload = if options.preload {
+        options.subsets.clone().unwrap_or_default()
+    } else {
+        vec![]
+    };
+    for font_file in find_font_files_in_css(&stylesheet, &subsets_to_preload) {
+        let query = qstring::QString::new(vec![
+            ("url", font_file.url.as_str()),
+            ("preload", if font_file.preload { "true" } else { "false" }),
+            (
+                "has_size_adjust",
+                if has_size_adjust { "true" } else { "false" },
+            ),
+        ]);
+        stylesheet = stylesheet.replace(
+            &format!("url({})", font_file.url),
+            &format!("url({GOOGLE_FONTS_FONT_FILE_REQUEST}?{query})"),
+        );
+    }
+
+    Ok(Vc::cell(stylesheet))
 }
 
 #[turbo_tasks::function]
@@ -422,6 +565,29 @@ async fn fetch_real_stylesheet(
     })
 }
 
+async fn fetch_real_font_file(
+    url: &str,
+    font_virtual_path: Vc<FileSystemPath>,
+    mode: NextMode,
+) -> Result<Option<File>> {
+    let font = fetch(Vc::cell(url.to_owned()), Vc::cell(None)).await?;
+
+    Ok(match &*font {
+        Ok(r) => Some(File::from(r.await?.body.await?.0.as_slice())),
+        Err(err) => {
+            // Like for the stylesheet, this shouldn't prevent rendering in development,
+            // but production builds fail instead of shipping without the font.
+            let severity = match mode {
+                NextMode::Development => IssueSeverity::Warning,
+                NextMode::Build => IssueSeverity::Error,
+            };
+            err.to_issue(severity.into(), font_virtual_path).emit();
+
+            None
+        }
+    })
+}
+
 async fn get_mock_stylesheet(
     stylesheet_url: Vc<String>,
     mocked_responses_path: &str,
@@ -2,6 +2,8 @@ use std::cmp::Ordering;
 
 use anyhow::{anyhow, bail, Context, Result};
 use indexmap::{indexset, IndexSet};
+use once_cell::sync::Lazy;
+use regex::Regex;
 
 use super::options::{FontData, FontWeights};
 
@@ -227,6 +229,39 @@ pub(super) fn get_stylesheet_url(
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub(super) struct FontFile {
+    pub(super) url: String,
+    pub(super) preload: bool,
+}
+
+static SUBSET_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"/\* (.+?) \*/").unwrap());
+static FONT_FILE_URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"src: url\((.+?)\)").unwrap());
+
+// Derived from https://github.com/vercel/next.js/blob/9e098da0915a2a4581bebe2270953a1216be1ba4/packages/font/src/google/find-font-files-in-css.ts
+/// Finds the font files referenced in a stylesheet from Google Fonts, and
+/// whether they should be preloaded. The subset of each `@font-face` is in a
+/// comment above it, so the current subset is tracked from top to bottom.
+pub(super) fn find_font_files_in_css(css: &str, subsets_to_preload: &[String]) -> Vec<FontFile> {
+    let mut font_files: Vec<FontFile> = vec![];
+    let mut current_subset = "";
+    for line in css.lines() {
+        if let Some(subset) = SUBSET_COMMENT.captures(line).and_then(|c| c.get(1)) {
+            current_subset = subset.as_str();
+        } else if let Some(url) = FONT_FILE_URL.captures(line).and_then(|c| c.get(1)) {
+            let url = url.as_str();
+            if !font_files.iter().any(|file| file.url == url) {
+                font_files.push(FontFile {
+                    url: url.to_owned(),
+                    preload: subsets_to_preload.iter().any(|s| s == current_subset),
+                });
+            }
+        }
+    }
+
+    font_files
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
@@ -236,7 +271,7 @@ mod tests {
     use super::get_font_axes;
     use crate::next_font::google::{
         options::{FontData, FontWeights},
-        util::{get_stylesheet_url, FontAxes, FontStyle},
+        util::{find_font_files_in_css, get_stylesheet_url, FontAxes, FontFile, FontStyle},
         GOOGLE_FONTS_STYLESHEET_URL,
     };
 
@@ -548,4 +583,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_find_font_files_in_css() {
+        let css = r#"
+/* cyrillic */
+@font-face {
+  font-family: 'Inter';
+  font-style: normal;
+  font-weight: 400;
+  src: url(https://fonts.gstatic.com/s/inter/v12/cyrillic.woff2) format('woff2');
+}
+/* latin */
+@font-face {
+  font-family: 'Inter';
+  font-style: normal;
+  font-weight: 400;
+  src: url(https://fonts.gstatic.com/s/inter/v12/latin.woff2) format('woff2');
+}
+/* latin */
+@font-face {
+  font-family: 'Inter';
+  font-style: normal;
+  font-weight: 700;
+  src: url(https://fonts.gstatic.com/s/inter/v12/latin.woff2) format('woff2');
+}
+"#;
+
+        assert_eq!(
+            find_font_files_in_css(css, &["latin".to_owned()]),
+            vec![
+                FontFile {
+                    url: "https://fonts.gstatic.com/s/inter/v12/cyrillic.woff2".to_owned(),
+                    preload: false,
+                },
+                FontFile {
+                    url: "https://fonts.gstatic.com/s/inter/v12/latin.woff2".to_owned(),
+                    preload: true,
+                },
+            ]
+        );
+    }
 }
@@ -89,10 +89,21 @@ async fn get_font_adjustment(
     };
 
     let font_file_binary = font_file_rope.to_bytes()?;
-    let scope = allsorts::binary::read::ReadScope::new(&font_file_binary);
+    calculate_font_adjustment(&font_file_binary, &main_descriptor.path, fallback_font)
+}
+
+/// Calculates the adjustment to apply to `fallback_font` to approximate the
+/// metrics of the font in `font_file_binary`. `font_path` is only used in
+/// error messages.
+pub fn calculate_font_adjustment(
+    font_file_binary: &[u8],
+    font_path: &str,
+    fallback_font: &DefaultFallbackFont,
+) -> Result<FontAdjustment> {
+    let scope = allsorts::binary::read::ReadScope::new(font_file_binary);
     let mut font = Font::new(scope.read::<FontData>()?.table_provider(0)?)?.context(format!(
         "Unable to read font metrics from font file at {}",
-        &main_descriptor.path,
+        font_path,
     ))?;
 
     let az_avg_width = calc_average_width(&mut font);
@@ -100,7 +111,7 @@ async fn get_font_adjustment(
         .head_table()?
         .context(format!(
             "Unable to read font scale from font file at {}",
-            &main_descriptor.path
+            font_path
         ))?
         .units_per_em as f64;
 
@@ -1,4 +1,4 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use indoc::formatdoc;
 use turbo_tasks::Vc;
 use turbopack_binding::{
@@ -24,9 +24,13 @@ use self::{
     util::build_font_family_string,
 };
 use super::{font_fallback::FontFallbacks, util::FontCssProperties};
-use crate::next_font::{
-    local::options::FontWeight,
-    util::{get_request_hash, get_request_id},
+use crate::{
+    embed_js::next_js_file_path,
+    next_font::{
+        local::options::FontWeight,
+        util::{get_request_hash, get_request_id},
+    },
+    util::content_hash,
 };
 
 pub mod font_fallback;
@@ -35,6 +39,8 @@ pub mod request;
 pub mod stylesheet;
 pub mod util;
 
+pub const LOCAL_FONT_FILE_REQUEST: &str = "@vercel/turbopack-next/internal/font/local/font";
+
 #[turbo_tasks::value(shared)]
 pub(crate) struct NextFontLocalReplacer {
     project_path: Vc<FileSystemPath>,
@@ -188,6 +194,83 @@ impl ImportMappingReplacement for NextFontLocalCssModuleReplacer {
     }
 }
 
+#[turbo_tasks::value(shared)]
+pub struct NextFontLocalFontFileReplacer;
+
+#[turbo_tasks::value_impl]
+impl NextFontLocalFontFileReplacer {
+    #[turbo_tasks::function]
+    pub fn new() -> Vc<Self> {
+        Self::cell(NextFontLocalFontFileReplacer)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextFontLocalFontFileReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    /// Intercepts requests for the font files referenced in the css module
+    /// generated by the above replacer. Returns a VirtualSource with the
+    /// content of the font file, which is emitted like any other asset. Like
+    /// for webpack, the file name is marked with `-s` when the font uses an
+    /// adjusted fallback and with `.p` when it should be preloaded.
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Request::Module {
+            module: _,
+            path: _,
+            query: query_vc,
+        } = &*request.await?
+        else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        let query_map = qstring::QString::from(&**query_vc.await?);
+        let path = query_map
+            .get("path")
+            .context("next/font/local font file requests must have a path")?;
+        let ext = path
+            .rsplit_once('.')
+            .map(|(_, ext)| ext)
+            .with_context(|| format!("Font file path {path} must have an extension"))?;
+
+        let font_path = context.join(path.to_owned());
+        let font_content = font_path.read();
+        // Like webpack's `[hash]`, the name is derived from the content so that it
+        // changes whenever the font file does.
+        let FileContent::Content(font_file) = &*font_content.await? else {
+            bail!("Font file {path} could not be read");
+        };
+        let name = format!(
+            "{:x?}{}{}",
+            content_hash(&font_file.content().to_bytes()?),
+            if query_map.get("has_size_adjust") == Some("true") {
+                "-s"
+            } else {
+                ""
+            },
+            if query_map.get("preload") == Some("true") {
+                ".p"
+            } else {
+                ""
+            },
+        );
+        let font_source = VirtualSource::new(
+            next_js_file_path("internal/font/local".to_string()).join(format!("{name}.{ext}")),
+            AssetContent::file(font_content),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(font_source)).into()).into())
+    }
+}
+
 #[turbo_tasks::function]
 async fn get_font_css_properties(
     options_vc: Vc<NextFontLocalOptions>,
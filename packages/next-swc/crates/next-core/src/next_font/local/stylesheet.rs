@@ -2,9 +2,12 @@ use anyhow::{bail, Result};
 use indoc::formatdoc;
 use turbo_tasks::Vc;
 
-use super::options::{FontDescriptors, NextFontLocalOptions};
+use super::{
+    options::{FontDescriptors, NextFontLocalOptions},
+    LOCAL_FONT_FILE_REQUEST,
+};
 use crate::next_font::{
-    font_fallback::FontFallbacks,
+    font_fallback::{FontFallback, FontFallbacks},
     stylesheet::{build_fallback_definition, build_font_class_rules},
     util::{get_scoped_font_family, FontCssProperties, FontFamilyType},
 };
@@ -22,23 +25,34 @@ pub(super) async fn build_stylesheet(
         request_hash,
     );
 
+    let mut has_size_adjust = false;
+    for fallback in &*fallbacks.await? {
+        if let FontFallback::Automatic(fallback) = &*fallback.await? {
+            has_size_adjust |= fallback.await?.adjustment.is_some();
+        }
+    }
+
     Ok(Vc::cell(formatdoc!(
         r#"
         {}
         {}
         {}
     "#,
-        *build_font_face_definitions(scoped_font_family, options).await?,
+        *build_font_face_definitions(scoped_font_family, options, has_size_adjust).await?,
         (*build_fallback_definition(fallbacks).await?),
         *build_font_class_rules(css_properties).await?
     )))
 }
 
-/// Builds a string of `@font-face` definitions for each local font file
+/// Builds a string of `@font-face` definitions for each local font file. The
+/// font files are referenced through requests of
+/// [super::NextFontLocalFontFileReplacer], which marks their names for
+/// preloading.
 #[turbo_tasks::function]
 pub(super) async fn build_font_face_definitions(
     scoped_font_family: Vc<String>,
     options: Vc<NextFontLocalOptions>,
+    has_size_adjust: bool,
 ) -> Result<Vc<String>> {
     let options = &*options.await?;
 
@@ -49,17 +63,26 @@ pub(super) async fn build_font_face_definitions(
     };
 
     for font in fonts {
+        let query = qstring::QString::new(vec![
+            ("path", font.path.as_str()),
+            ("preload", if options.preload { "true" } else { "false" }),
+            (
+                "has_size_adjust",
+                if has_size_adjust { "true" } else { "false" },
+            ),
+        ]);
         definitions.push_str(&formatdoc!(
             r#"
             @font-face {{
                 font-family: '{}';
-                src: url('{}') format('{}');
+                src: url('{}?{}') format('{}');
                 font-display: {};
                 {}{}
             }}
         "#,
             *scoped_font_family.await?,
-            &font.path,
+            LOCAL_FONT_FILE_REQUEST,
+            query,
             ext_to_format(&font.ext)?,
             options.display,
             &font
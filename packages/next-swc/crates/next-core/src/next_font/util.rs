@@ -1,6 +1,7 @@
 use anyhow::Result;
 use turbo_tasks::Vc;
-use turbopack_binding::turbo::tasks_hash::hash_xxh3_hash64;
+
+use crate::util::content_hash;
 
 /// CSS properties and values for a given font variation. These are rendered as
 /// values in both the returned JavaScript object and in the referenced css
@@ -19,16 +20,17 @@ pub(crate) struct FontCssProperties {
 #[turbo_tasks::function]
 pub(crate) async fn get_request_hash(query_vc: Vc<String>) -> Result<Vc<u32>> {
     let query = qstring::QString::from(&**query_vc.await?);
+    // The lengths are fixed to 64 bits, so the hash is the same on every platform.
     let mut to_hash = vec![];
-    for (k, v) in query {
-        to_hash.push(k);
-        to_hash.push(v);
+    for part in query.into_iter().flat_map(|(k, v)| [k, v]) {
+        to_hash.extend((part.len() as u64).to_le_bytes());
+        to_hash.extend(part.as_bytes());
     }
 
     Ok(Vc::cell(
         // Truncate the hash to u32. These hashes are ultimately displayed as 6- or 8-character
         // hexadecimal values.
-        hash_xxh3_hash64(to_hash) as u32,
+        content_hash(&to_hash) as u32,
     ))
 }
 
@@ -1,6 +1,9 @@
 pub(crate) mod content_source;
 pub(crate) mod module;
+pub(crate) mod orientation;
 pub(crate) mod source_asset;
 
 pub use content_source::NextImageContentSource;
 pub use module::StructuredImageModuleType;
+pub use orientation::exif_orientation;
+pub use source_asset::blur_options;
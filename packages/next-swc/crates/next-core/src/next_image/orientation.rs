@@ -0,0 +1,218 @@
+/// The `Exif\0\0` header in front of the TIFF structure of EXIF data.
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// The TIFF tag of the orientation.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// The TIFF type of unsigned 16 bit values.
+const SHORT_TYPE: u16 = 3;
+
+/// Returns the EXIF orientation (1 to 8) of a JPEG, PNG or WebP image, if it
+/// has one. The dimensions of an image are the ones it's stored with, so with
+/// the orientations 5 to 8 its width and height are swapped when displayed.
+pub fn exif_orientation(content: &[u8]) -> Option<u8> {
+    let exif = if content.starts_with(&[0xff, 0xd8]) {
+        jpeg_exif(content)?
+    } else if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png_exif(content)?
+    } else if content.get(..4) == Some(b"RIFF") && content.get(8..12) == Some(b"WEBP") {
+        webp_exif(content)?
+    } else {
+        return None;
+    };
+    tiff_orientation(exif.strip_prefix(EXIF_HEADER).unwrap_or(exif))
+}
+
+/// Returns the APP1 segment holding the EXIF data of a JPEG, which comes
+/// before the image data.
+fn jpeg_exif(content: &[u8]) -> Option<&[u8]> {
+    let mut offset = 2;
+    loop {
+        let [0xff, marker] = *content.get(offset..offset + 2)? else {
+            return None;
+        };
+        match marker {
+            // Fill bytes in front of a marker.
+            0xff => {
+                offset += 1;
+                continue;
+            }
+            // The start of the image data or the end of the image.
+            0xda | 0xd9 => return None,
+            _ => {}
+        }
+        // The length of a segment includes the length itself.
+        let length = u16::from_be_bytes(content.get(offset + 2..offset + 4)?.try_into().ok()?);
+        let length = usize::from(length);
+        if length < 2 {
+            return None;
+        }
+        let segment = content.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xe1 && segment.starts_with(EXIF_HEADER) {
+            return Some(segment);
+        }
+        offset += 2 + length;
+    }
+}
+
+/// Returns the data of the `eXIf` chunk of a PNG.
+fn png_exif(content: &[u8]) -> Option<&[u8]> {
+    let mut offset = 8;
+    loop {
+        let length = u32::from_be_bytes(content.get(offset..offset + 4)?.try_into().ok()?);
+        let length = usize::try_from(length).ok()?;
+        let data = content.get(offset + 8..(offset + 8).checked_add(length)?)?;
+        match content.get(offset + 4..offset + 8)? {
+            b"eXIf" => return Some(data),
+            b"IEND" => return None,
+            _ => {}
+        }
+        // The chunk type, length and CRC.
+        offset += 12 + length;
+    }
+}
+
+/// Returns the data of the `EXIF` chunk of a WebP.
+fn webp_exif(content: &[u8]) -> Option<&[u8]> {
+    let mut offset = 12;
+    loop {
+        let size = u32::from_le_bytes(content.get(offset + 4..offset + 8)?.try_into().ok()?);
+        let size = usize::try_from(size).ok()?;
+        let data = content.get(offset + 8..(offset + 8).checked_add(size)?)?;
+        if content.get(offset..offset + 4)? == b"EXIF" {
+            return Some(data);
+        }
+        // Chunks are padded to an even size.
+        offset += 8 + size + size % 2;
+    }
+}
+
+/// Reads the orientation from the first IFD of a TIFF structure.
+fn tiff_orientation(tiff: &[u8]) -> Option<u8> {
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| {
+        let bytes = tiff.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| {
+        let bytes = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    if read_u16(2)? != 42 {
+        return None;
+    }
+    let ifd = usize::try_from(read_u32(4)?).ok()?;
+    for index in 0..usize::from(read_u16(ifd)?) {
+        let entry = ifd + 2 + index * 12;
+        if read_u16(entry)? != ORIENTATION_TAG {
+            continue;
+        }
+        if read_u16(entry + 2)? != SHORT_TYPE {
+            return None;
+        }
+        // A single SHORT is stored in the first bytes of the value field.
+        let orientation = read_u16(entry + 8)?;
+        return u8::try_from(orientation)
+            .ok()
+            .filter(|orientation| (1..=8).contains(orientation));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::exif_orientation;
+
+    /// A TIFF structure with an IFD of a single orientation entry.
+    fn tiff(big_endian: bool, orientation: u16) -> Vec<u8> {
+        let u16_bytes = |value: u16| {
+            if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        };
+        let u32_bytes = |value: u32| {
+            if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        };
+        let mut tiff = Vec::new();
+        tiff.extend(if big_endian { b"MM" } else { b"II" });
+        tiff.extend(u16_bytes(42));
+        tiff.extend(u32_bytes(8));
+        tiff.extend(u16_bytes(1));
+        tiff.extend(u16_bytes(0x0112));
+        tiff.extend(u16_bytes(3));
+        tiff.extend(u32_bytes(1));
+        tiff.extend(u16_bytes(orientation));
+        tiff.extend([0, 0]);
+        tiff.extend(u32_bytes(0));
+        tiff
+    }
+
+    fn jpeg(exif: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xff, 0xd8];
+        // An APP0 segment in front of the EXIF data.
+        jpeg.extend([0xff, 0xe0, 0x00, 0x04, 0x00, 0x00]);
+        jpeg.extend([0xff, 0xe1]);
+        jpeg.extend(u16::try_from(exif.len() + 8).unwrap().to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(exif);
+        jpeg.extend([0xff, 0xda, 0x00, 0x02, 0xff, 0xd9]);
+        jpeg
+    }
+
+    #[test]
+    fn reads_the_orientation_of_jpegs() {
+        assert_eq!(exif_orientation(&jpeg(&tiff(true, 6))), Some(6));
+        assert_eq!(exif_orientation(&jpeg(&tiff(false, 3))), Some(3));
+    }
+
+    #[test]
+    fn reads_the_orientation_of_pngs() {
+        let exif = tiff(false, 8);
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(u32::try_from(exif.len()).unwrap().to_be_bytes());
+        png.extend(b"eXIf");
+        png.extend(&exif);
+        png.extend([0; 4]);
+        assert_eq!(exif_orientation(&png), Some(8));
+    }
+
+    #[test]
+    fn reads_the_orientation_of_webps() {
+        let exif = tiff(false, 5);
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend(b"VP8X");
+        webp.extend(10u32.to_le_bytes());
+        webp.extend([0; 10]);
+        webp.extend(b"EXIF");
+        webp.extend(u32::try_from(exif.len()).unwrap().to_le_bytes());
+        webp.extend(&exif);
+        assert_eq!(exif_orientation(&webp), Some(5));
+    }
+
+    #[test]
+    fn ignores_missing_and_invalid_orientations() {
+        assert_eq!(exif_orientation(&[0xff, 0xd8, 0xff, 0xd9]), None);
+        assert_eq!(exif_orientation(&jpeg(&tiff(true, 9))), None);
+        assert_eq!(exif_orientation(&jpeg(&tiff(true, 6))[..20]), None);
+        assert_eq!(exif_orientation(b"GIF89a"), None);
+    }
+}
@@ -21,8 +21,10 @@ fn modifier() -> Vc<String> {
     Vc::cell("structured image object".to_string())
 }
 
+/// The options used to generate blur placeholders for statically imported
+/// images.
 #[turbo_tasks::function]
-fn blur_options() -> Vc<BlurPlaceholderOptions> {
+pub fn blur_options() -> Vc<BlurPlaceholderOptions> {
     BlurPlaceholderOptions {
         quality: 70,
         size: 8,
@@ -58,7 +60,14 @@ impl Asset for StructuredImageFileSource {
             bail!("Input source is not a file and can't be transformed into image information");
         };
         let mut result = RopeBuilder::from("");
-        writeln!(result, "import src from \"IMAGE\";",)?;
+        writeln!(result, "import url from \"IMAGE\";",)?;
+        // The chunking context has no way to add a query to the urls of static
+        // assets, so the deployment id is added to the url of the image here.
+        writeln!(
+            result,
+            "const src = process.env.NEXT_DEPLOYMENT_ID ? \
+             `${{url}}?dpl=${{process.env.NEXT_DEPLOYMENT_ID}}` : url;"
+        )?;
         let blur_options = blur_options();
         match self.blur_placeholder_mode {
             BlurPlaceholderMode::NextImageUrl => {
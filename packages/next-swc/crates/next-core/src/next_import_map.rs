@@ -25,11 +25,23 @@ use crate::{
     mode::NextMode,
     next_client::context::ClientContextType,
     next_config::NextConfig,
+    next_edge::unsupported::insert_next_edge_node_builtin_aliases,
     next_font::{
-        google::{NextFontGoogleCssModuleReplacer, NextFontGoogleReplacer},
-        local::{NextFontLocalCssModuleReplacer, NextFontLocalReplacer},
+        google::{
+            NextFontGoogleCssModuleReplacer, NextFontGoogleFontFileReplacer,
+            NextFontGoogleReplacer, GOOGLE_FONTS_FONT_FILE_REQUEST,
+        },
+        local::{
+            NextFontLocalCssModuleReplacer, NextFontLocalFontFileReplacer, NextFontLocalReplacer,
+            LOCAL_FONT_FILE_REQUEST,
+        },
     },
     next_server::context::ServerContextType,
+    next_shared::{
+        invalid_imports::next_router_import_mapping,
+        optional_peer_dependencies::insert_optional_peer_dependency_alias,
+        package_imports::insert_package_imports_alias,
+    },
     util::NextRuntime,
 };
 
@@ -42,9 +54,12 @@ pub async fn get_next_client_import_map(
     mode: NextMode,
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
+    package_imports_conditions: Vc<Vec<String>>,
 ) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
+    insert_package_imports_alias(&mut import_map, package_imports_conditions);
+
     insert_next_shared_aliases(
         &mut import_map,
         project_path,
@@ -64,6 +79,11 @@ pub async fn get_next_client_import_map(
     )
     .await?;
 
+    // Like webpack, production builds swap in the profiling build of react-dom
+    // so the React profiler works in them.
+    let react_dom_profiling =
+        mode == NextMode::Build && *next_config.enable_react_production_profiling().await?;
+
     match ty.into_value() {
         ClientContextType::Pages { pages_dir } => {
             insert_alias_to_alternatives(
@@ -90,14 +110,15 @@ pub async fn get_next_client_import_map(
                     request_to_import_mapping(pages_dir, "next/error"),
                 ],
             );
+            if react_dom_profiling {
+                import_map.insert_exact_alias(
+                    "react-dom",
+                    request_to_import_mapping(pages_dir, "react-dom/profiling"),
+                );
+            }
         }
         ClientContextType::App { app_dir } => {
-            let react_flavor =
-                if *next_config.enable_ppr().await? || *next_config.enable_taint().await? {
-                    "-experimental"
-                } else {
-                    ""
-                };
+            let react_flavor = react_channel(next_config).await?;
 
             import_map.insert_exact_alias(
                 "react",
@@ -117,7 +138,11 @@ pub async fn get_next_client_import_map(
                 "react-dom",
                 request_to_import_mapping(
                     app_dir,
-                    &format!("next/dist/compiled/react-dom{react_flavor}"),
+                    &if react_dom_profiling {
+                        format!("next/dist/compiled/react-dom{react_flavor}/profiling")
+                    } else {
+                        format!("next/dist/compiled/react-dom{react_flavor}")
+                    },
                 ),
             );
             import_map.insert_exact_alias(
@@ -183,15 +208,19 @@ pub async fn get_next_client_import_map(
             "next/dist/compiled/client-only" => "next/dist/compiled/client-only/index".to_string(),
         },
     );
-
     match ty.into_value() {
         ClientContextType::Pages { .. }
         | ClientContextType::App { .. }
         | ClientContextType::Fallback => {
+            let fallback_node_polyfills = *next_config.enable_fallback_node_polyfills().await?;
             for (original, alias) in NEXT_ALIASES {
                 import_map.insert_exact_alias(
                     format!("node:{original}"),
-                    request_to_import_mapping(project_path, alias),
+                    if fallback_node_polyfills {
+                        request_to_import_mapping(project_path, alias)
+                    } else {
+                        ImportMapping::Ignore.cell()
+                    },
                 );
             }
         }
@@ -203,6 +232,17 @@ pub async fn get_next_client_import_map(
     Ok(import_map.cell())
 }
 
+/// Computes the Next-specific server-side fallback import map, which applies to
+/// requests which don't resolve otherwise.
+#[turbo_tasks::function]
+pub fn get_next_server_fallback_import_map() -> Vc<ImportMap> {
+    let mut import_map = ImportMap::empty();
+
+    insert_optional_peer_dependency_alias(&mut import_map);
+
+    import_map.cell()
+}
+
 /// Computes the Next-specific client import map.
 #[turbo_tasks::function]
 pub fn get_next_build_import_map() -> Vc<ImportMap> {
@@ -225,9 +265,15 @@ pub fn get_next_build_import_map() -> Vc<ImportMap> {
 }
 
 /// Computes the Next-specific client fallback import map, which provides
-/// polyfills to Node.js externals.
+/// polyfills to Node.js externals. When `experimental.fallbackNodePolyfills`
+/// is `false`, they're ignored instead, and the client transforms report the
+/// imports as an error. Optional peer dependencies which aren't installed are
+/// replaced with a module throwing at runtime.
 #[turbo_tasks::function]
-pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Vc<ImportMap> {
+pub async fn get_next_client_fallback_import_map(
+    ty: Value<ClientContextType>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
     match ty.into_value() {
@@ -237,9 +283,17 @@ pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Vc<I
         | ClientContextType::App {
             app_dir: context_dir,
         } => {
-            for (original, alias) in NEXT_ALIASES {
-                import_map
-                    .insert_exact_alias(original, request_to_import_mapping(context_dir, alias));
+            if *next_config.enable_fallback_node_polyfills().await? {
+                for (original, alias) in NEXT_ALIASES {
+                    import_map.insert_exact_alias(
+                        original,
+                        request_to_import_mapping(context_dir, alias),
+                    );
+                }
+            } else {
+                for (original, _) in NEXT_ALIASES {
+                    import_map.insert_exact_alias(original, ImportMapping::Ignore.cell());
+                }
             }
         }
         ClientContextType::Fallback => {}
@@ -247,8 +301,9 @@ pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Vc<I
     }
 
     insert_turbopack_dev_alias(&mut import_map);
+    insert_optional_peer_dependency_alias(&mut import_map);
 
-    import_map.cell()
+    Ok(import_map.cell())
 }
 
 /// Computes the Next-specific server-side import map.
@@ -259,9 +314,12 @@ pub async fn get_next_server_import_map(
     mode: NextMode,
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
+    package_imports_conditions: Vc<Vec<String>>,
 ) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
+    insert_package_imports_alias(&mut import_map, package_imports_conditions);
+
     insert_next_shared_aliases(
         &mut import_map,
         project_path,
@@ -275,7 +333,7 @@ pub async fn get_next_server_import_map(
         &mut import_map,
         project_path,
         next_config.resolve_alias_options(),
-        [],
+        ["node"],
     )
     .await?;
 
@@ -309,7 +367,7 @@ pub async fn get_next_server_import_map(
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
         }
-        ServerContextType::Middleware => {}
+        ServerContextType::Middleware | ServerContextType::Instrumentation => {}
     }
 
     insert_next_server_special_aliases(
@@ -332,9 +390,12 @@ pub async fn get_next_edge_import_map(
     mode: NextMode,
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
+    package_imports_conditions: Vc<Vec<String>>,
 ) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
+    insert_package_imports_alias(&mut import_map, package_imports_conditions);
+
     // https://github.com/vercel/next.js/blob/786ef25e529e1fb2dda398aebd02ccbc8d0fb673/packages/next/src/build/webpack-config.ts#L815-L861
 
     // Alias next/dist imports to next/dist/esm assets
@@ -379,7 +440,19 @@ pub async fn get_next_edge_import_map(
             "next/dist/shared/lib/dynamic" => "next/dist/esm/shared/lib/dynamic".to_string(),
             "next/dist/shared/lib/head" => "next/dist/esm/shared/lib/head".to_string(),
             "next/dist/shared/lib/image-external" => "next/dist/esm/shared/lib/image-external".to_string(),
-            "dist/server/og/image-response" => "next/dist/esm/server/og/image-response".to_string(),
+            "next/dist/server/og/image-response" => "next/dist/esm/server/og/image-response".to_string(),
+        },
+    );
+
+    // Open Graph image routes need the wasm build of satori and resvg on the edge.
+    // `ImageResponse` selects it with a `process.env.NEXT_RUNTIME` check, which
+    // would still include the node build.
+    insert_exact_alias_map(
+        &mut import_map,
+        project_path,
+        indexmap! {
+            "@vercel/og" => "next/dist/esm/server/og/image-response".to_string(),
+            "next/dist/compiled/@vercel/og/index.node.js" => "next/dist/compiled/@vercel/og/index.edge.js".to_string(),
         },
     );
 
@@ -392,13 +465,15 @@ pub async fn get_next_edge_import_map(
     )
     .await?;
 
+    insert_next_edge_node_builtin_aliases(&mut import_map);
+
     insert_optimized_module_aliases(&mut import_map, project_path).await?;
 
     insert_alias_option(
         &mut import_map,
         project_path,
         next_config.resolve_alias_options(),
-        [],
+        ["edge-light"],
     )
     .await?;
 
@@ -419,7 +494,7 @@ pub async fn get_next_edge_import_map(
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
         }
-        ServerContextType::Middleware => {}
+        ServerContextType::Middleware | ServerContextType::Instrumentation => {}
     }
 
     insert_next_server_special_aliases(
@@ -465,7 +540,7 @@ pub fn get_next_client_resolved_map(
     .cell()
 }
 
-static NEXT_ALIASES: [(&str, &str); 23] = [
+pub(crate) static NEXT_ALIASES: [(&str, &str); 23] = [
     ("assert", "next/dist/compiled/assert"),
     ("buffer", "next/dist/compiled/buffer"),
     ("constants", "next/dist/compiled/constants-browserify"),
@@ -557,7 +632,7 @@ async fn insert_next_server_special_aliases(
 
             rsc_aliases(import_map, project_path, ty, runtime, next_config).await?;
         }
-        ServerContextType::Middleware => {}
+        ServerContextType::Middleware | ServerContextType::Instrumentation => {}
     }
 
     // see https://github.com/vercel/next.js/blob/8013ef7372fc545d49dbd060461224ceb563b454/packages/next/src/build/webpack-config.ts#L1449-L1531
@@ -579,7 +654,8 @@ async fn insert_next_server_special_aliases(
         // TODO: should include `ServerContextType::PagesApi` routes, but that type doesn't exist.
         ServerContextType::AppRSC { .. }
         | ServerContextType::AppRoute { .. }
-        | ServerContextType::Middleware => {
+        | ServerContextType::Middleware
+        | ServerContextType::Instrumentation => {
             insert_exact_alias_map(
                 import_map,
                 project_path,
@@ -605,6 +681,22 @@ async fn insert_next_server_special_aliases(
         }
     }
 
+    // The router of `next/router` is never mounted in the App Router, so report
+    // its imports in Client Components at build time. The React Server Components
+    // transform reports them in Server Components.
+    if let ServerContextType::AppSSR { .. } = ty {
+        import_map.insert_exact_alias(
+            "next/router",
+            next_router_import_mapping(
+                project_path,
+                match runtime {
+                    NextRuntime::Edge => "next/dist/esm/client/router",
+                    NextRuntime::NodeJs => "next/dist/client/router",
+                },
+            ),
+        );
+    }
+
     // Potential the bundle introduced into middleware and api can be poisoned by
     // client-only but not being used, so we disabled the `client-only` erroring
     // on these layers. `server-only` is still available.
@@ -620,10 +712,13 @@ async fn insert_next_server_special_aliases(
         );
     }
 
-    import_map.insert_exact_alias(
-        "@vercel/og",
-        external_if_node(project_path, "next/dist/server/og/image-response"),
-    );
+    // The edge import map aliases it to the edge build.
+    if runtime == NextRuntime::NodeJs {
+        import_map.insert_exact_alias(
+            "@vercel/og",
+            external_if_node(project_path, "next/dist/server/og/image-response"),
+        );
+    }
 
     Ok(())
 }
@@ -635,9 +730,7 @@ async fn rsc_aliases(
     runtime: NextRuntime,
     next_config: Vc<NextConfig>,
 ) -> Result<()> {
-    let ppr = *next_config.enable_ppr().await?;
-    let taint = *next_config.enable_taint().await?;
-    let react_channel = if ppr || taint { "-experimental" } else { "" };
+    let react_channel = react_channel(next_config).await?;
 
     let mut alias = indexmap! {
         "react" => format!("next/dist/compiled/react{react_channel}"),
@@ -673,6 +766,10 @@ async fn rsc_aliases(
             })
         }
 
+        // Like webpack, only the Server Components layer uses the React vendored
+        // by the app-page route module, which would otherwise be loaded along with
+        // every route handler. Those resolve the compiled React with the
+        // `react-server` condition instead.
         if let ServerContextType::AppRSC { .. } = ty {
             alias.extend(indexmap! {
                 "react/jsx-runtime" => format!("next/dist/server/future/route-modules/app-page/vendored/rsc/react-jsx-runtime"),
@@ -688,7 +785,10 @@ async fn rsc_aliases(
     }
 
     if runtime == NextRuntime::Edge {
-        if matches!(ty, ServerContextType::AppRSC { .. }) {
+        if matches!(
+            ty,
+            ServerContextType::AppRSC { .. } | ServerContextType::AppRoute { .. }
+        ) {
             alias["react"] = format!("next/dist/compiled/react{react_channel}/react.shared-subset");
         }
         // Use server rendering stub for RSC and SSR
@@ -699,9 +799,33 @@ async fn rsc_aliases(
 
     insert_exact_alias_map(import_map, project_path, alias);
 
+    // The other entrypoints of the packages need to come from the same channel,
+    // so a runtime never mixes builds of React.
+    insert_wildcard_alias_map(
+        import_map,
+        project_path,
+        indexmap! {
+            "react/" => format!("next/dist/compiled/react{react_channel}/*"),
+            "react-dom/" => format!("next/dist/compiled/react-dom{react_channel}/*"),
+            "react-server-dom-webpack/" => format!("next/dist/compiled/react-server-dom-turbopack{react_channel}/*"),
+            "react-server-dom-turbopack/" => format!("next/dist/compiled/react-server-dom-turbopack{react_channel}/*"),
+        },
+    );
+
     Ok(())
 }
 
+/// The suffix of the vendored React packages of the channel `next_config`
+/// selects. `experimental.ppr` and `experimental.taint` need the experimental
+/// builds of React, otherwise the stable ones are used.
+async fn react_channel(next_config: Vc<NextConfig>) -> Result<&'static str> {
+    Ok(if *next_config.enable_experimental_react().await? {
+        "-experimental"
+    } else {
+        ""
+    })
+}
+
 pub fn mdx_import_source_file() -> String {
     format!("{VIRTUAL_PACKAGE_NAME}/mdx-import-source")
 }
@@ -789,6 +913,12 @@ async fn insert_next_shared_aliases(
         .into(),
     );
 
+    import_map.insert_alias(
+        // Request path from the css module above
+        AliasPattern::exact(GOOGLE_FONTS_FONT_FILE_REQUEST),
+        ImportMapping::Dynamic(Vc::upcast(NextFontGoogleFontFileReplacer::new(mode))).into(),
+    );
+
     import_map.insert_alias(
         // Request path from js via next-font swc transform
         AliasPattern::exact("next/font/local/target.css"),
@@ -809,6 +939,12 @@ async fn insert_next_shared_aliases(
         .into(),
     );
 
+    import_map.insert_alias(
+        // Request path from the css module above
+        AliasPattern::exact(LOCAL_FONT_FILE_REQUEST),
+        ImportMapping::Dynamic(Vc::upcast(NextFontLocalFontFileReplacer::new())).into(),
+    );
+
     import_map.insert_singleton_alias("@swc/helpers", get_next_package(project_path));
     import_map.insert_singleton_alias("styled-jsx", get_next_package(project_path));
     import_map.insert_singleton_alias("next", project_path);
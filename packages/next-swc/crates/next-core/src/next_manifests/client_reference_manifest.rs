@@ -31,6 +31,8 @@ impl ClientReferenceManifest {
         client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
         ssr_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
         asset_prefix: Vc<Option<String>>,
+        cross_origin: Vc<Option<String>>,
+        deployment_id_query: Vc<String>,
         runtime: NextRuntime,
     ) -> Result<Vc<Box<dyn OutputAsset>>> {
         let mut entry_manifest: ClientReferenceManifest = Default::default();
@@ -39,7 +41,8 @@ impl ClientReferenceManifest {
             .as_ref()
             .map(|p| p.to_owned())
             .unwrap_or_default();
-        entry_manifest.module_loading.cross_origin = None;
+        entry_manifest.module_loading.cross_origin = cross_origin.await?.clone_value();
+        let deployment_id_query = deployment_id_query.await?;
         let client_references_chunks = client_references_chunks.await?;
         let client_relative_path = client_relative_path.await?;
         let node_root_ref = node_root.await?;
@@ -163,6 +166,7 @@ impl ClientReferenceManifest {
                         // It's possible that a chunk also emits CSS files, that will
                         // be handled separatedly.
                         .filter(|path| path.ends_with(".js"))
+                        .map(|path| format!("{path}{deployment_id_query}"))
                         .collect::<Vec<_>>();
 
                     let ssr_chunks_paths = ssr_chunks
@@ -2,17 +2,18 @@
 
 pub(crate) mod client_reference_manifest;
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use serde::{Deserialize, Serialize};
+use rand::Rng;
+use serde::{Deserialize, Serialize, Serializer};
 use turbo_tasks::{trace::TraceRawVcs, TaskInput};
 
-use crate::next_config::Rewrites;
+use crate::next_config::{Header, I18NConfig, Redirect, Rewrite, Rewrites};
 
 #[derive(Serialize, Default, Debug)]
 pub struct PagesManifest {
     #[serde(flatten)]
-    pub pages: HashMap<String, String>,
+    pub pages: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -23,8 +24,12 @@ pub struct BuildManifest {
     pub polyfill_files: Vec<String>,
     pub low_priority_files: Vec<String>,
     pub root_main_files: Vec<String>,
-    pub pages: HashMap<String, Vec<String>>,
+    pub pages: BTreeMap<String, Vec<String>>,
     pub amp_first_pages: Vec<String>,
+    /// External scripts of `next/script` elements with the
+    /// `beforeInteractive` strategy, by page.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub before_interactive_scripts: BTreeMap<String, Vec<String>>,
 }
 
 #[derive(Serialize, Debug)]
@@ -90,9 +95,8 @@ pub struct EdgeFunctionDefinition {
     pub name: String,
     pub page: String,
     pub matchers: Vec<MiddlewareMatcher>,
-    // TODO: AssetBinding[]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wasm: Option<Vec<()>>,
+    pub wasm: Option<Vec<AssetBinding>>,
     // TODO: AssetBinding[]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assets: Option<Vec<()>>,
@@ -100,6 +104,13 @@ pub struct EdgeFunctionDefinition {
     pub regions: Option<Regions>,
 }
 
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBinding {
+    pub name: String,
+    pub file_path: String,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(untagged)]
 pub enum Regions {
@@ -107,18 +118,30 @@ pub enum Regions {
     Single(String),
 }
 
+/// The edge chunks of the instrumentation hook, which are loaded before the
+/// files of every edge function.
+#[derive(Serialize, Default, Debug)]
+pub struct InstrumentationDefinition {
+    pub files: Vec<String>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<Vec<AssetBinding>>,
+}
+
 #[derive(Serialize, Default, Debug)]
 pub struct MiddlewaresManifestV2 {
     pub sorted_middleware: Vec<String>,
-    pub middleware: HashMap<String, EdgeFunctionDefinition>,
-    pub functions: HashMap<String, EdgeFunctionDefinition>,
+    pub middleware: BTreeMap<String, EdgeFunctionDefinition>,
+    pub functions: BTreeMap<String, EdgeFunctionDefinition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instrumentation: Option<InstrumentationDefinition>,
 }
 
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ReactLoadableManifest {
     #[serde(flatten)]
-    pub manifest: HashMap<String, ReactLoadableManifestEntry>,
+    pub manifest: BTreeMap<String, ReactLoadableManifestEntry>,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -131,8 +154,8 @@ pub struct ReactLoadableManifestEntry {
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct NextFontManifest {
-    pub pages: HashMap<String, Vec<String>>,
-    pub app: HashMap<String, Vec<String>>,
+    pub pages: BTreeMap<String, Vec<String>>,
+    pub app: BTreeMap<String, Vec<String>>,
     pub app_using_size_adjust: bool,
     pub pages_using_size_adjust: bool,
 }
@@ -161,9 +184,9 @@ pub struct LoadableManifest {
 #[serde(rename_all = "camelCase")]
 pub struct ServerReferenceManifest {
     /// A map from hashed action name to the runtime module we that exports it.
-    pub node: HashMap<String, ActionManifestEntry>,
+    pub node: BTreeMap<String, ActionManifestEntry>,
     /// A map from hashed action name to the runtime module we that exports it.
-    pub edge: HashMap<String, ActionManifestEntry>,
+    pub edge: BTreeMap<String, ActionManifestEntry>,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -171,9 +194,9 @@ pub struct ServerReferenceManifest {
 pub struct ActionManifestEntry {
     /// A mapping from the page that uses the server action to the runtime
     /// module that exports it.
-    pub workers: HashMap<String, ActionManifestWorkerEntry>,
+    pub workers: BTreeMap<String, ActionManifestWorkerEntry>,
 
-    pub layer: HashMap<String, ActionLayer>,
+    pub layer: BTreeMap<String, ActionLayer>,
 }
 
 #[derive(Serialize, Debug)]
@@ -213,16 +236,16 @@ pub struct ClientReferenceManifest {
     pub client_modules: ManifestNode,
     /// Mapping of client module ID to corresponding SSR module ID and required
     /// SSR chunks.
-    pub ssr_module_mapping: HashMap<ModuleId, ManifestNode>,
+    pub ssr_module_mapping: BTreeMap<ModuleId, ManifestNode>,
     /// Same as `ssr_module_mapping`, but for Edge SSR.
     #[serde(rename = "edgeSSRModuleMapping")]
-    pub edge_ssr_module_mapping: HashMap<ModuleId, ManifestNode>,
+    pub edge_ssr_module_mapping: BTreeMap<ModuleId, ManifestNode>,
     /// Mapping of server component path to required CSS client chunks.
     #[serde(rename = "entryCSSFiles")]
-    pub entry_css_files: HashMap<String, Vec<String>>,
+    pub entry_css_files: BTreeMap<String, Vec<String>>,
     /// Mapping of server component path to required JS client chunks.
     #[serde(rename = "entryJSFiles")]
-    pub entry_js_files: HashMap<String, Vec<String>>,
+    pub entry_js_files: BTreeMap<String, Vec<String>>,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -237,7 +260,7 @@ pub struct ModuleLoading {
 pub struct ManifestNode {
     /// Mapping of export name to manifest node entry.
     #[serde(flatten)]
-    pub module_exports: HashMap<String, ManifestNodeEntry>,
+    pub module_exports: BTreeMap<String, ManifestNodeEntry>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -253,7 +276,7 @@ pub struct ManifestNodeEntry {
     pub r#async: bool,
 }
 
-#[derive(Serialize, Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Serialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum ModuleId {
@@ -275,7 +298,7 @@ pub struct FontManifestEntry {
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AppBuildManifest {
-    pub pages: HashMap<String, Vec<String>>,
+    pub pages: BTreeMap<String, Vec<String>>,
 }
 
 // TODO(alexkirsz) Unify with the one for dev.
@@ -288,5 +311,161 @@ pub struct ClientBuildManifest<'a> {
     pub sorted_pages: &'a [String],
 
     #[serde(flatten)]
-    pub pages: HashMap<String, Vec<&'a str>>,
+    pub pages: BTreeMap<String, Vec<&'a str>>,
+}
+
+/// The `prerender-manifest.json` the Next.js server reads the revalidate
+/// periods of the prerendered routes and the preview mode keys from.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderManifest {
+    pub version: u32,
+    pub routes: BTreeMap<String, PrerenderManifestRoute>,
+    /// The paths of dynamic routes are only known after prerendering them,
+    /// so none are listed.
+    pub dynamic_routes: BTreeMap<String, ()>,
+    pub not_found_routes: Vec<String>,
+    pub preview: PrerenderManifestPreview,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderManifestRoute {
+    pub initial_revalidate_seconds: InitialRevalidateSeconds,
+    pub src_route: Option<String>,
+    pub data_route: Option<String>,
+}
+
+/// The revalidate period of a prerendered route, serialized as `false` when
+/// the route is never revalidated.
+#[derive(Debug)]
+pub enum InitialRevalidateSeconds {
+    Never,
+    Seconds(u32),
+}
+
+impl Serialize for InitialRevalidateSeconds {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            InitialRevalidateSeconds::Never => serializer.serialize_bool(false),
+            InitialRevalidateSeconds::Seconds(seconds) => serializer.serialize_u32(*seconds),
+        }
+    }
+}
+
+/// The keys of the preview and draft modes. `next build` generates them for
+/// every build and passes them in, as the server's preview mode ID is inlined
+/// from the same keys.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderManifestPreview {
+    pub preview_mode_id: String,
+    pub preview_mode_signing_key: String,
+    pub preview_mode_encryption_key: String,
+}
+
+impl PrerenderManifestPreview {
+    pub fn generate() -> Self {
+        fn random_hex(bytes: usize) -> String {
+            let mut rng = rand::thread_rng();
+            (0..bytes)
+                .map(|_| format!("{:02x}", rng.gen::<u8>()))
+                .collect()
+        }
+        PrerenderManifestPreview {
+            preview_mode_id: random_hex(16),
+            preview_mode_signing_key: random_hex(32),
+            preview_mode_encryption_key: random_hex(32),
+        }
+    }
+}
+
+/// The `routes-manifest.json` the Next.js server reads the custom routes and
+/// the routes of the pages of a build from.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifest<'a> {
+    pub version: u32,
+    pub pages404: bool,
+    pub case_sensitive: bool,
+    pub base_path: &'a str,
+    /// The redirects, with their status as a `statusCode`.
+    pub redirects: Vec<Redirect>,
+    pub headers: &'a [Header],
+    pub rewrites: RoutesManifestRewrites<'a>,
+    pub static_routes: Vec<RoutesManifestRoute>,
+    pub dynamic_routes: Vec<RoutesManifestRoute>,
+    pub data_routes: Vec<RoutesManifestDataRoute>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<&'a I18NConfig>,
+    pub rsc: RoutesManifestRsc,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_middleware_url_normalize: Option<bool>,
+}
+
+/// The rewrites of the routes manifest, which are a plain list when there are
+/// only `afterFiles` rewrites.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum RoutesManifestRewrites<'a> {
+    AfterFiles(&'a [Rewrite]),
+    Phases(&'a Rewrites),
+}
+
+impl<'a> From<&'a Rewrites> for RoutesManifestRewrites<'a> {
+    fn from(rewrites: &'a Rewrites) -> Self {
+        if rewrites.before_files.is_empty() && rewrites.fallback.is_empty() {
+            RoutesManifestRewrites::AfterFiles(&rewrites.after_files)
+        } else {
+            RoutesManifestRewrites::Phases(rewrites)
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifestRoute {
+    pub page: String,
+    pub regex: String,
+    pub route_keys: BTreeMap<String, String>,
+    pub named_regex: String,
+}
+
+/// The route of the `/_next/data` JSON of a page with `getStaticProps` or
+/// `getServerSideProps`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifestDataRoute {
+    pub page: String,
+    pub data_route_regex: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route_keys: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub named_data_route_regex: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifestRsc {
+    pub header: &'static str,
+    pub vary_header: &'static str,
+    pub prefetch_header: &'static str,
+    pub did_postpone_header: &'static str,
+    pub content_type_header: &'static str,
+    pub suffix: &'static str,
+    pub prefetch_suffix: &'static str,
+}
+
+impl Default for RoutesManifestRsc {
+    fn default() -> Self {
+        Self {
+            header: "RSC",
+            vary_header: "RSC, Next-Router-State-Tree, Next-Router-Prefetch, Next-Url",
+            prefetch_header: "Next-Router-Prefetch",
+            did_postpone_header: "x-nextjs-postponed",
+            content_type_header: "text/x-component",
+            suffix: ".rsc",
+            prefetch_suffix: ".prefetch.rsc",
+        }
+    }
 }
@@ -24,8 +24,9 @@ use turbopack_binding::{
 };
 
 use crate::{
+    mode::NextMode,
     next_config::NextConfig,
-    next_edge::entry::wrap_edge_entry,
+    next_edge::entry::{get_incremental_cache_handler, wrap_edge_entry},
     util::{file_content_rope, load_next_js_template, NextRuntime},
 };
 
@@ -39,6 +40,8 @@ pub async fn create_page_ssr_entry_module(
     next_original_name: Vc<String>,
     runtime: NextRuntime,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
+    mode: NextMode,
 ) -> Result<Vc<Box<dyn EcmascriptChunkPlaceable>>> {
     let definition_page = &*next_original_name.await?;
     let definition_pathname = &*pathname.await?;
@@ -132,6 +135,8 @@ pub async fn create_page_ssr_entry_module(
                 definition_page.clone(),
                 definition_pathname.clone(),
                 next_config,
+                build_id,
+                mode,
             );
         } else {
             ssr_module = wrap_edge_entry(
@@ -160,14 +165,28 @@ async fn wrap_edge_page(
     page: String,
     pathname: String,
     next_config: Vc<NextConfig>,
+    build_id: Vc<String>,
+    mode: NextMode,
 ) -> Result<Vc<Box<dyn Module>>> {
     const INNER: &str = "INNER_PAGE_ENTRY";
+    const INNER_INCREMENTAL_CACHE_HANDLER: &str = "INNER_INCREMENTAL_CACHE_HANDLER";
+
+    let mut inner_assets = indexmap! {
+        INNER.to_string() => entry
+    };
+
+    let incremental_cache_handler =
+        get_incremental_cache_handler(context, project_root, next_config).await?;
+    if let Some(incremental_cache_handler) = incremental_cache_handler {
+        inner_assets.insert(
+            INNER_INCREMENTAL_CACHE_HANDLER.to_string(),
+            incremental_cache_handler,
+        );
+    }
 
     let next_config = &*next_config.await?;
 
-    // TODO(WEB-1824): add build support
-    let build_id = "development";
-    let dev = true;
+    let dev = mode == NextMode::Development;
 
     let sri_enabled = !dev
         && next_config
@@ -183,7 +202,7 @@ async fn wrap_edge_page(
         indexmap! {
             "VAR_USERLAND" => INNER.to_string(),
             "VAR_PAGE" => page.clone(),
-            "VAR_BUILD_ID" => build_id.to_string(),
+            "VAR_BUILD_ID" => build_id.await?.clone_value(),
             "VAR_MODULE_DOCUMENT" => "@vercel/turbopack-next/pages/_document".to_string(),
             "VAR_MODULE_APP" => "@vercel/turbopack-next/pages/_app".to_string(),
             "VAR_MODULE_GLOBAL_ERROR" => "@vercel/turbopack-next/pages/_error".to_string(),
@@ -198,17 +217,14 @@ async fn wrap_edge_page(
             "user500RouteModuleOptions" => serde_json::to_string(&get_route_module_options("/500".to_string(), "/500".to_string()))?,
         },
         indexmap! {
+            "incrementalCacheHandler" => incremental_cache_handler
+                .map(|_| INNER_INCREMENTAL_CACHE_HANDLER.to_string()),
             // TODO
-            "incrementalCacheHandler" => None,
             "userland500Page" => None,
         },
     )
     .await?;
 
-    let inner_assets = indexmap! {
-        INNER.to_string() => entry
-    };
-
     let wrapped = context.process(
         Vc::upcast(source),
         Value::new(ReferenceType::Internal(Vc::cell(inner_assets))),
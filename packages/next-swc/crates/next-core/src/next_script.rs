@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use swc_core::ecma::{
+    ast::{
+        CallExpr, Expr, Id, ImportSpecifier, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue,
+        JSXElementName, JSXOpeningElement, Lit, Module, ModuleDecl, ModuleItem, Prop, PropName,
+        PropOrSpread,
+    },
+    visit::{Visit, VisitWith},
+};
+use turbo_tasks::{
+    graph::{AdjacencyMap, GraphTraversal},
+    Vc,
+};
+use turbopack_binding::turbopack::{
+    core::{
+        module::{Module as TurbopackModule, Modules},
+        reference::primary_referenced_modules,
+    },
+    ecmascript::{parse::ParseResult, EcmascriptModuleAsset},
+};
+
+const BEFORE_INTERACTIVE: &str = "beforeInteractive";
+
+/// Collects the `beforeInteractive` scripts of all project modules reachable
+/// from `entries`, e.g. from a page and the `_app` and `_document` it's
+/// rendered with, as [find_before_interactive_scripts] does for a single
+/// module. Modules in `node_modules` and their imports are skipped.
+#[turbo_tasks::function]
+pub async fn find_before_interactive_scripts_in_graph(
+    entries: Vc<Modules>,
+) -> Result<Vc<Vec<String>>> {
+    let modules = AdjacencyMap::new()
+        .skip_duplicates()
+        .visit(entries.await?.iter().copied(), get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner()
+        .into_reverse_topological();
+
+    let mut scripts = IndexSet::new();
+    for module in modules {
+        scripts.extend(
+            find_before_interactive_scripts(module)
+                .await?
+                .iter()
+                .cloned(),
+        );
+    }
+    Ok(Vc::cell(scripts.into_iter().collect()))
+}
+
+/// The modules referenced by `parent` which are project code. `next/script`
+/// elements of packages in `node_modules` aren't collected, so the packages
+/// aren't parsed and traversed.
+async fn get_referenced_modules(
+    parent: Vc<Box<dyn TurbopackModule>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn TurbopackModule>>> + Send> {
+    let mut modules = Vec::new();
+    for &module in primary_referenced_modules(parent).await?.iter() {
+        if !module.ident().path().await?.path.contains("node_modules") {
+            modules.push(module);
+        }
+    }
+    Ok(modules.into_iter())
+}
+
+/// Collects the `src` of every `next/script` element using the
+/// `beforeInteractive` strategy in the given module, so they can be emitted
+/// into the initial HTML ahead of the hydration chunks.
+///
+/// Only statically known `src` values are collected, inline scripts are left
+/// to the runtime.
+#[turbo_tasks::function]
+pub async fn find_before_interactive_scripts(
+    module: Vc<Box<dyn TurbopackModule>>,
+) -> Result<Vc<Vec<String>>> {
+    let Some(ecmascript_asset) =
+        Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(module).await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+    let ParseResult::Ok { program, .. } = &*ecmascript_asset.parse().await? else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let mut visitor = BeforeInteractiveScriptsVisitor::default();
+    program.visit_with(&mut visitor);
+    Ok(Vc::cell(visitor.scripts))
+}
+
+#[derive(Default)]
+struct BeforeInteractiveScriptsVisitor {
+    /// Local bindings of the `next/script` default export.
+    script_components: HashSet<Id>,
+    scripts: Vec<String>,
+}
+
+impl BeforeInteractiveScriptsVisitor {
+    fn add_script(&mut self, strategy: Option<&str>, src: Option<&str>) {
+        if let (Some(BEFORE_INTERACTIVE), Some(src)) = (strategy, src) {
+            if !self.scripts.iter().any(|s| s == src) {
+                self.scripts.push(src.to_string());
+            }
+        }
+    }
+}
+
+impl Visit for BeforeInteractiveScriptsVisitor {
+    fn visit_module(&mut self, module: &Module) {
+        // Imports are hoisted, so collect them before visiting any usage.
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+                continue;
+            };
+            if &*import.src.value != "next/script" {
+                continue;
+            }
+            for specifier in &import.specifiers {
+                if let ImportSpecifier::Default(default) = specifier {
+                    self.script_components.insert(default.local.to_id());
+                }
+            }
+        }
+
+        module.visit_children_with(self);
+    }
+
+    fn visit_jsx_opening_element(&mut self, element: &JSXOpeningElement) {
+        if let JSXElementName::Ident(ident) = &element.name {
+            if self.script_components.contains(&ident.to_id()) {
+                let mut strategy = None;
+                let mut src = None;
+                for attr in &element.attrs {
+                    let JSXAttrOrSpread::JSXAttr(JSXAttr {
+                        name: JSXAttrName::Ident(name),
+                        value: Some(JSXAttrValue::Lit(Lit::Str(value))),
+                        ..
+                    }) = attr
+                    else {
+                        continue;
+                    };
+                    match &*name.sym {
+                        "strategy" => strategy = Some(&*value.value),
+                        "src" => src = Some(&*value.value),
+                        _ => {}
+                    }
+                }
+                self.add_script(strategy, src);
+            }
+        }
+
+        element.visit_children_with(self);
+    }
+
+    /// Handles JSX that has already been compiled, e.g.
+    /// `jsx(Script, { strategy: "beforeInteractive", src: "..." })`.
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let [component, props, ..] = &*call.args {
+            if let (Expr::Ident(ident), Expr::Object(props)) = (&*component.expr, &*props.expr) {
+                if component.spread.is_none() && self.script_components.contains(&ident.to_id()) {
+                    let mut strategy = None;
+                    let mut src = None;
+                    for prop in &props.props {
+                        let PropOrSpread::Prop(prop) = prop else {
+                            continue;
+                        };
+                        let Prop::KeyValue(key_value) = &**prop else {
+                            continue;
+                        };
+                        let key = match &key_value.key {
+                            PropName::Ident(ident) => &*ident.sym,
+                            PropName::Str(str) => &*str.value,
+                            _ => continue,
+                        };
+                        let Expr::Lit(Lit::Str(value)) = &*key_value.value else {
+                            continue;
+                        };
+                        match key {
+                            "strategy" => strategy = Some(&*value.value),
+                            "src" => src = Some(&*value.value),
+                            _ => {}
+                        }
+                    }
+                    self.add_script(strategy, src);
+                }
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+}
@@ -38,18 +38,23 @@ use super::{
     transforms::{get_next_server_internal_transforms_rules, get_next_server_transforms_rules},
 };
 use crate::{
-    babel::maybe_add_babel_loader,
+    babel::{maybe_add_babel_loader, maybe_add_react_compiler_loader},
     embed_js::next_js_fs,
     mode::NextMode,
     next_build::{get_external_next_compiled_package_mapping, get_postcss_package_mapping},
-    next_client::{RuntimeEntries, RuntimeEntry},
+    next_client::{get_client_assets_path, RuntimeEntries, RuntimeEntry},
     next_config::NextConfig,
-    next_import_map::{get_next_server_import_map, mdx_import_source_file},
+    next_import_map::{
+        get_next_server_fallback_import_map, get_next_server_import_map, mdx_import_source_file,
+    },
     next_server::resolve::ExternalPredicate,
     next_shared::{
+        barrel::NextBarrelExportResolvePlugin,
+        package_imports::package_imports_conditions,
         resolve::{
-            ModuleFeatureReportResolvePlugin, NextExternalResolvePlugin,
-            NextNodeSharedRuntimeResolvePlugin, UnsupportedModulesResolvePlugin,
+            ModuleFeatureReportResolvePlugin, NextDuplicateReactResolvePlugin,
+            NextExternalResolvePlugin, NextNodeSharedRuntimeResolvePlugin,
+            UnsupportedModulesResolvePlugin,
         },
         transforms::{
             emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
@@ -90,6 +95,7 @@ pub enum ServerContextType {
         app_dir: Vc<FileSystemPath>,
     },
     Middleware,
+    Instrumentation,
 }
 
 #[turbo_tasks::function]
@@ -100,8 +106,6 @@ pub async fn get_server_resolve_options_context(
     next_config: Vc<NextConfig>,
     execution_context: Vc<ExecutionContext>,
 ) -> Result<Vc<ResolveOptionsContext>> {
-    let next_server_import_map =
-        get_next_server_import_map(project_path, ty, mode, next_config, execution_context);
     let foreign_code_context_condition =
         foreign_code_context_condition(next_config, project_path).await?;
     let root_dir = project_path.root().resolve().await?;
@@ -125,32 +129,60 @@ pub async fn get_server_resolve_options_context(
     let server_component_externals_plugin = ExternalCjsModulesResolvePlugin::new(
         project_path,
         project_path.root(),
-        ExternalPredicate::Only(Vc::cell(external_packages)).cell(),
+        ExternalPredicate::Only(Vc::cell(external_packages.clone())).cell(),
     );
     let ty = ty.into_value();
 
     let mut custom_conditions = vec![mode.node_env().to_string(), "node".to_string()];
 
     match ty {
-        ServerContextType::AppRSC { .. } => custom_conditions.push("react-server".to_string()),
-        ServerContextType::AppRoute { .. }
-        | ServerContextType::Pages { .. }
+        ServerContextType::AppRSC { .. } | ServerContextType::AppRoute { .. } => {
+            custom_conditions.push("react-server".to_string())
+        }
+        ServerContextType::Pages { .. }
         | ServerContextType::PagesData { .. }
         | ServerContextType::PagesApi { .. }
         | ServerContextType::AppSSR { .. }
-        | ServerContextType::Middleware { .. } => {}
+        | ServerContextType::Middleware { .. }
+        | ServerContextType::Instrumentation => {}
+    };
+    let next_server_import_map = get_next_server_import_map(
+        project_path,
+        Value::new(ty),
+        mode,
+        next_config,
+        execution_context,
+        package_imports_conditions(&custom_conditions, false, true),
+    );
+    let transpile_packages = next_config.transpile_packages();
+    let optimize_package_imports = next_config.optimize_package_imports().await?;
+    // The barrel files of external packages can't be optimized: their named
+    // imports would be bundled, but the default and namespace imports would be
+    // loaded from the external package, i.e. twice.
+    let barrel_packages = match ty {
+        ServerContextType::Pages { .. }
+        | ServerContextType::PagesData { .. }
+        | ServerContextType::PagesApi { .. } => {
+            let transpile_packages = transpile_packages.await?;
+            bundled_barrel_packages(&optimize_package_imports, |package| {
+                !transpile_packages.contains(package)
+            })
+        }
+        _ => bundled_barrel_packages(&optimize_package_imports, |package| {
+            external_packages.contains(package)
+        }),
     };
     let external_cjs_modules_plugin = ExternalCjsModulesResolvePlugin::new(
         project_path,
         project_path.root(),
-        ExternalPredicate::AllExcept(next_config.transpile_packages()).cell(),
+        ExternalPredicate::AllExcept(transpile_packages).cell(),
     );
 
     let next_external_plugin = NextExternalResolvePlugin::new(project_path);
     let next_node_shared_runtime_plugin =
         NextNodeSharedRuntimeResolvePlugin::new(project_path, Value::new(ty));
 
-    let plugins = match ty {
+    let mut plugins = match ty {
         ServerContextType::Pages { .. }
         | ServerContextType::PagesData { .. }
         | ServerContextType::PagesApi { .. } => {
@@ -164,8 +196,17 @@ pub async fn get_server_resolve_options_context(
         }
         ServerContextType::AppSSR { .. }
         | ServerContextType::AppRSC { .. }
-        | ServerContextType::AppRoute { .. }
-        | ServerContextType::Middleware { .. } => {
+        | ServerContextType::AppRoute { .. } => {
+            vec![
+                Vc::upcast(module_feature_report_resolve_plugin),
+                Vc::upcast(server_component_externals_plugin),
+                Vc::upcast(unsupported_modules_resolve_plugin),
+                Vc::upcast(NextDuplicateReactResolvePlugin::new(project_path)),
+                Vc::upcast(next_external_plugin),
+                Vc::upcast(next_node_shared_runtime_plugin),
+            ]
+        }
+        ServerContextType::Middleware { .. } | ServerContextType::Instrumentation => {
             vec![
                 Vc::upcast(module_feature_report_resolve_plugin),
                 Vc::upcast(server_component_externals_plugin),
@@ -175,13 +216,25 @@ pub async fn get_server_resolve_options_context(
             ]
         }
     };
+    if !barrel_packages.is_empty() {
+        plugins.insert(
+            0,
+            Vc::upcast(NextBarrelExportResolvePlugin::new(
+                project_path,
+                Vc::cell(barrel_packages),
+            )),
+        );
+    }
+
     let resolve_options_context = ResolveOptionsContext {
         enable_node_modules: Some(root_dir),
+        custom_extensions: next_config.resolve_extensions().await?.clone_value(),
         enable_node_externals: true,
         enable_node_native_modules: true,
         module: true,
         custom_conditions,
         import_map: Some(next_server_import_map),
+        fallback_import_map: Some(get_next_server_fallback_import_map()),
         plugins,
         ..Default::default()
     };
@@ -198,6 +251,19 @@ pub async fn get_server_resolve_options_context(
     .cell())
 }
 
+/// Returns the `optimizePackageImports` packages which aren't external, so
+/// their barrel files can be optimized.
+fn bundled_barrel_packages(
+    optimize_package_imports: &[String],
+    is_external: impl Fn(&String) -> bool,
+) -> Vec<String> {
+    optimize_package_imports
+        .iter()
+        .filter(|package| !is_external(package))
+        .cloned()
+        .collect()
+}
+
 fn defines(define_env: &IndexMap<String, String>) -> CompileTimeDefines {
     let mut defines = IndexMap::new();
 
@@ -249,7 +315,8 @@ pub async fn get_server_module_options_context(
     mode: NextMode,
     next_config: Vc<NextConfig>,
 ) -> Result<Vc<ModuleOptionsContext>> {
-    let custom_rules = get_next_server_transforms_rules(next_config, ty.into_value(), mode).await?;
+    let custom_rules =
+        get_next_server_transforms_rules(project_path, next_config, ty.into_value(), mode).await?;
     let internal_custom_rules = get_next_server_internal_transforms_rules(ty.into_value()).await?;
 
     let foreign_code_context_condition =
@@ -281,6 +348,16 @@ pub async fn get_server_module_options_context(
     // Now creates a webpack rules that applies to all codes.
     let webpack_rules = *foreign_webpack_rules.clone();
     let webpack_rules = *maybe_add_babel_loader(project_path, webpack_rules).await?;
+    // Server Components aren't compiled with the React Compiler, as its output
+    // uses hooks. Only the SSR of pages and of Client Components is.
+    let webpack_rules = if matches!(
+        ty.into_value(),
+        ServerContextType::Pages { .. } | ServerContextType::AppSSR { .. }
+    ) {
+        *maybe_add_react_compiler_loader(project_path, next_config, webpack_rules).await?
+    } else {
+        webpack_rules
+    };
     let enable_webpack_loaders = webpack_rules.map(|rules| {
         WebpackLoadersOptions {
             rules,
@@ -293,7 +370,7 @@ pub async fn get_server_module_options_context(
 
     // EcmascriptTransformPlugins for custom transforms
     let styled_components_transform_plugin =
-        *get_styled_components_transform_plugin(next_config).await?;
+        *get_styled_components_transform_plugin(next_config, mode).await?;
     let styled_jsx_transform_plugin = *get_styled_jsx_transform_plugin().await?;
 
     // ModuleOptionsContext related options
@@ -315,7 +392,7 @@ pub async fn get_server_module_options_context(
     let source_transforms: Vec<Vc<TransformPlugin>> = vec![
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
         *get_relay_transform_plugin(next_config).await?,
-        *get_emotion_transform_plugin(next_config).await?,
+        *get_emotion_transform_plugin(next_config, mode).await?,
     ]
     .into_iter()
     .flatten()
@@ -572,7 +649,7 @@ pub async fn get_server_module_options_context(
                 ..module_options_context
             }
         }
-        ServerContextType::Middleware => {
+        ServerContextType::Middleware | ServerContextType::Instrumentation => {
             let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
                 styled_components_transform_plugin,
                 styled_jsx_transform_plugin,
@@ -678,10 +755,42 @@ pub fn get_server_chunking_context(
         node_root,
         client_root,
         node_root.join("server/chunks".to_string()),
-        client_root.join("static/media".to_string()),
+        get_client_assets_path(client_root),
         environment,
     )
     .asset_prefix(asset_prefix)
     .minify_type(MinifyType::NoMinify)
     .build()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::bundled_barrel_packages;
+
+    fn packages(packages: &[&str]) -> Vec<String> {
+        packages.iter().map(|package| package.to_string()).collect()
+    }
+
+    #[test]
+    fn skips_external_barrel_packages() {
+        let optimize_package_imports = packages(&["lucide-react", "my-lib", "date-fns"]);
+
+        // Pages contexts bundle the transpiled packages only.
+        let transpile_packages = packages(&["my-lib"]);
+        assert_eq!(
+            bundled_barrel_packages(&optimize_package_imports, |package| {
+                !transpile_packages.contains(package)
+            }),
+            packages(&["my-lib"])
+        );
+
+        // App Router contexts bundle everything but the listed externals.
+        let external_packages = packages(&["date-fns"]);
+        assert_eq!(
+            bundled_barrel_packages(&optimize_package_imports, |package| {
+                external_packages.contains(package)
+            }),
+            packages(&["lucide-react", "my-lib"])
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde_json::Value as JsonValue;
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::{
     turbo::tasks_fs::{glob::Glob, FileJsonContent, FileSystemPath},
@@ -126,11 +127,15 @@ impl ResolvePlugin for ExternalCjsModulesResolvePlugin {
             ExternalPredicate::Only(externals) => {
                 let external_glob = packages_glob(*externals).await?;
 
-                if let Some(external_glob) = *external_glob {
-                    if !external_glob.await?.execute(&raw_fs_path.path) {
-                        return Ok(ResolveResultOption::none());
-                    }
+                let is_listed = if let Some(external_glob) = *external_glob {
+                    external_glob.await?.execute(&raw_fs_path.path)
                 } else {
+                    false
+                };
+
+                // Packages loading native addons can't be bundled, as they locate their
+                // binaries relative to the package at runtime.
+                if !is_listed && !*is_native_addon_package(fs_path).await? {
                     return Ok(ResolveResultOption::none());
                 }
             }
@@ -176,9 +181,14 @@ impl ResolvePlugin for ExternalCjsModulesResolvePlugin {
         // check if we can resolve the package from the project dir with node.js resolve
         // options (might be hidden by pnpm)
         if is_cjs_resolveable {
-            // mark as external
+            // mark as external, with the request, so that the files it requires at runtime
+            // can be traced
+            let item = match request.await?.request() {
+                Some(request_str) => ResolveResultItem::OriginalReferenceTypeExternal(request_str),
+                None => ResolveResultItem::OriginalReferenceExternal,
+            };
             return Ok(ResolveResultOption::some(
-                ResolveResult::primary(ResolveResultItem::OriginalReferenceExternal).cell(),
+                ResolveResult::primary(item).cell(),
             ));
         }
 
@@ -206,6 +216,48 @@ impl ResolvePlugin for ExternalCjsModulesResolvePlugin {
     }
 }
 
+/// Packages that are commonly used to locate and load `.node` binaries.
+const NATIVE_ADDON_LOADERS: &[&str] = &[
+    "bindings",
+    "node-gyp-build",
+    "node-pre-gyp",
+    "@mapbox/node-pre-gyp",
+    "prebuild-install",
+];
+
+/// Checks whether the package containing `fs_path` loads a native addon.
+#[turbo_tasks::function]
+async fn is_native_addon_package(fs_path: Vc<FileSystemPath>) -> Result<Vc<bool>> {
+    let FindContextFileResult::Found(package_json, _) =
+        *find_context_file(fs_path.parent(), package_json()).await?
+    else {
+        return Ok(Vc::cell(false));
+    };
+    let FileJsonContent::Content(package) = &*package_json.read_json().await? else {
+        return Ok(Vc::cell(false));
+    };
+
+    Ok(Vc::cell(package_json_has_native_addon(package)))
+}
+
+/// A package loads a native addon when it's built with node-gyp or depends on
+/// one of the well-known addon loaders.
+fn package_json_has_native_addon(package: &JsonValue) -> bool {
+    if package["gypfile"].as_bool() == Some(true) || package["binary"].is_object() {
+        return true;
+    }
+
+    ["dependencies", "optionalDependencies"]
+        .iter()
+        .any(|field| {
+            package[field].as_object().map_or(false, |dependencies| {
+                NATIVE_ADDON_LOADERS
+                    .iter()
+                    .any(|loader| dependencies.contains_key(*loader))
+            })
+        })
+}
+
 // TODO move that to turbo
 #[turbo_tasks::value(transparent)]
 pub struct OptionGlob(Option<Vc<Glob>>);
@@ -222,3 +274,33 @@ async fn packages_glob(packages: Vc<Vec<String>>) -> Result<Vc<OptionGlob>> {
             .await?,
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::package_json_has_native_addon;
+
+    #[test]
+    fn detects_native_addon_packages() {
+        assert!(package_json_has_native_addon(&json!({ "gypfile": true })));
+        assert!(package_json_has_native_addon(&json!({
+            "binary": { "module_name": "argon2" }
+        })));
+        assert!(package_json_has_native_addon(&json!({
+            "dependencies": { "bindings": "^1.5.0" }
+        })));
+        assert!(package_json_has_native_addon(&json!({
+            "optionalDependencies": { "node-gyp-build": "^4.0.0" }
+        })));
+    }
+
+    #[test]
+    fn ignores_plain_packages() {
+        assert!(!package_json_has_native_addon(&json!({})));
+        assert!(!package_json_has_native_addon(&json!({
+            "gypfile": false,
+            "dependencies": { "react": "^18.0.0" }
+        })));
+    }
+}
@@ -1,23 +1,32 @@
 use anyhow::Result;
 use next_transform_strip_page_exports::ExportFilter;
 use turbo_tasks::Vc;
-use turbopack_binding::turbopack::turbopack::module_options::ModuleRule;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath, turbopack::turbopack::module_options::ModuleRule,
+};
 
 use crate::{
     mode::NextMode,
     next_client_reference::css_client_reference::css_client_reference_rule::get_next_css_client_reference_transforms_rule,
     next_config::NextConfig,
     next_server::context::ServerContextType,
-    next_shared::transforms::{
-        get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
-        get_next_modularize_imports_rule, get_next_pages_transforms_rule,
-        get_server_actions_transform_rule, server_actions::ActionsTransform,
+    next_shared::{
+        optional_peer_dependencies::get_optional_peer_dependencies_rule,
+        transforms::{
+            get_auto_pure_rule, get_cjs_optimizer_rule, get_import_meta_transform_rule,
+            get_mdx_frontmatter_rule, get_next_dynamic_transform_rule,
+            get_next_font_transform_rule, get_next_image_rule, get_next_modularize_imports_rule,
+            get_next_pages_transforms_rule, get_optimize_package_imports_rule,
+            get_react_server_components_transform_rule, get_remove_console_transform_rule,
+            get_server_actions_transform_rule, server_actions::ActionsTransform,
+        },
     },
 };
 
 /// Returns a list of module rules which apply server-side, Next.js-specific
 /// transforms.
 pub async fn get_next_server_transforms_rules(
+    project_path: Vc<FileSystemPath>,
     next_config: Vc<NextConfig>,
     context_ty: ServerContextType,
     mode: NextMode,
@@ -28,7 +37,17 @@ pub async fn get_next_server_transforms_rules(
     if let Some(modularize_imports_config) = modularize_imports_config {
         rules.push(get_next_modularize_imports_rule(modularize_imports_config));
     }
+
+    let optimize_package_imports = next_config.optimize_package_imports().await?;
+    if !optimize_package_imports.is_empty() {
+        rules.push(get_optimize_package_imports_rule(&optimize_package_imports));
+    }
     rules.push(get_next_font_transform_rule());
+    rules.push(get_cjs_optimizer_rule());
+
+    if *next_config.mdx_rs().await? {
+        rules.push(get_mdx_frontmatter_rule());
+    }
 
     let (is_server_components, pages_dir) = match context_ty {
         ServerContextType::Pages { pages_dir } | ServerContextType::PagesApi { pages_dir } => {
@@ -40,14 +59,18 @@ pub async fn get_next_server_transforms_rules(
             );
             (false, Some(pages_dir))
         }
-        ServerContextType::AppSSR { .. } => {
+        ServerContextType::AppSSR { app_dir } => {
             // Yah, this is SSR, but this is still treated as a Client transform layer.
+            rules.push(get_react_server_components_transform_rule(false, app_dir).await?);
             rules.push(get_server_actions_transform_rule(ActionsTransform::Client));
             (false, None)
         }
         ServerContextType::AppRSC {
-            client_transition, ..
+            app_dir,
+            client_transition,
+            ..
         } => {
+            rules.push(get_react_server_components_transform_rule(true, app_dir).await?);
             rules.push(get_server_actions_transform_rule(ActionsTransform::Server));
             if let Some(client_transition) = client_transition {
                 rules.push(get_next_css_client_reference_transforms_rule(
@@ -56,14 +79,29 @@ pub async fn get_next_server_transforms_rules(
             }
             (true, None)
         }
-        ServerContextType::AppRoute { .. } => (false, None),
-        ServerContextType::Middleware { .. } => (false, None),
+        ServerContextType::AppRoute { app_dir } => {
+            rules.push(get_react_server_components_transform_rule(true, app_dir).await?);
+            (false, None)
+        }
+        ServerContextType::Middleware { .. } | ServerContextType::Instrumentation => (false, None),
     };
 
     rules.push(get_next_dynamic_transform_rule(true, is_server_components, pages_dir, mode).await?);
 
     rules.push(get_next_image_rule());
 
+    rules.push(get_import_meta_transform_rule(project_path, true).await?);
+
+    if let Some(rule) = get_remove_console_transform_rule(next_config, mode).await? {
+        rules.push(rule);
+    }
+
+    if let Some(rule) = get_auto_pure_rule(mode) {
+        rules.push(rule);
+    }
+
+    rules.push(get_optional_peer_dependencies_rule());
+
     Ok(rules)
 }
 
@@ -91,6 +129,7 @@ pub async fn get_next_server_internal_transforms_rules(
         }
         ServerContextType::AppRoute { .. } => {}
         ServerContextType::Middleware { .. } => {}
+        ServerContextType::Instrumentation => {}
     };
 
     Ok(rules)
@@ -0,0 +1,224 @@
+use anyhow::Result;
+use next_swc::{
+    optimize_barrel::{optimize_barrel, Config},
+    split_named_imports::BARREL_EXPORT_QUERY,
+};
+use swc_core::{
+    common::GLOBALS,
+    ecma::{
+        ast::{Decl, Expr, Lit, ModuleDecl, ModuleItem, Program, Stmt},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::{Value, Vc};
+use turbo_tasks_fs::{glob::Glob, File, FileSystemPath};
+use turbopack_binding::turbopack::{
+    core::{
+        asset::AssetContent,
+        file_source::FileSource,
+        resolve::{
+            parse::Request,
+            plugin::{ResolvePlugin, ResolvePluginCondition},
+            ResolveResult, ResolveResultOption,
+        },
+        virtual_source::VirtualSource,
+    },
+    ecmascript::{
+        parse::{parse, ParseResult},
+        utils::StringifyJs,
+        EcmascriptModuleAssetType,
+    },
+};
+
+/// The exports of a barrel file, as found by the `optimize_barrel` transform.
+#[turbo_tasks::value(shared)]
+struct BarrelExports {
+    /// Directives like "use client", which the modules re-exporting from the
+    /// barrel need to keep.
+    directives: Vec<String>,
+    /// The `(name, source, original name)` of every named export.
+    exports: Vec<(String, String, String)>,
+    /// The sources of the `export *` declarations.
+    wildcards: Vec<String>,
+}
+
+#[turbo_tasks::value(transparent)]
+struct OptionBarrelExports(Option<Vc<BarrelExports>>);
+
+/// Returns the exports of the file at `path`, or `None` if it isn't a barrel
+/// file, i.e. doesn't only re-export from other modules.
+#[turbo_tasks::function]
+async fn barrel_exports(path: Vc<FileSystemPath>) -> Result<Vc<OptionBarrelExports>> {
+    let ParseResult::Ok {
+        program, globals, ..
+    } = &*parse(
+        Vc::upcast(FileSource::new(path)),
+        Value::new(EcmascriptModuleAssetType::Ecmascript),
+        Vc::cell(vec![]),
+    )
+    .await?
+    else {
+        return Ok(Vc::cell(None));
+    };
+
+    let Program::Module(module) = GLOBALS.set(globals, || {
+        program
+            .clone()
+            .fold_with(&mut optimize_barrel(Config { wildcard: false }))
+    }) else {
+        return Ok(Vc::cell(None));
+    };
+
+    let mut barrel = BarrelExports {
+        directives: vec![],
+        exports: vec![],
+        wildcards: vec![],
+    };
+    let mut is_barrel = false;
+    for item in module.body {
+        match item {
+            ModuleItem::Stmt(Stmt::Expr(expr)) => {
+                if let Expr::Lit(Lit::Str(directive)) = &*expr.expr {
+                    barrel.directives.push(directive.value.to_string());
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                let Decl::Var(var) = export.decl else {
+                    continue;
+                };
+                let Some(Expr::Lit(Lit::Str(export_map))) =
+                    var.decls.first().and_then(|decl| decl.init.as_deref())
+                else {
+                    continue;
+                };
+                barrel.exports = serde_json::from_str(&export_map.value)?;
+                is_barrel = true;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
+                let src = export_all.src.value.to_string();
+                barrel.wildcards.push(
+                    src.strip_prefix("__barrel_optimize__?names=__PLACEHOLDER__!=!")
+                        .unwrap_or(&src)
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    // The transform returns no items at all for files which aren't barrels.
+    Ok(Vc::cell(is_barrel.then(|| barrel.cell())))
+}
+
+/// Appends the export query to requests of the package's own modules, so
+/// that barrels re-exporting from barrels get optimized too.
+fn with_export_query(src: &str, name: &str) -> String {
+    if src.starts_with('.') && name != "*" {
+        format!("{src}?{BARREL_EXPORT_QUERY}={name}")
+    } else {
+        src.to_string()
+    }
+}
+
+/// Resolves the imports split by the `split_named_imports` transform, which
+/// name the export they import in their query, to a module re-exporting it
+/// from the module the package's barrel file re-exports it from. Only the
+/// modules behind the imported exports are then part of the module graph,
+/// instead of every module of the barrel, like the `__barrel_optimize__`
+/// loader of webpack.
+#[turbo_tasks::value]
+pub(crate) struct NextBarrelExportResolvePlugin {
+    root: Vc<FileSystemPath>,
+    packages: Vc<Vec<String>>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextBarrelExportResolvePlugin {
+    #[turbo_tasks::function]
+    pub fn new(root: Vc<FileSystemPath>, packages: Vc<Vec<String>>) -> Vc<Self> {
+        NextBarrelExportResolvePlugin { root, packages }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for NextBarrelExportResolvePlugin {
+    #[turbo_tasks::function]
+    async fn after_resolve_condition(&self) -> Result<Vc<ResolvePluginCondition>> {
+        Ok(ResolvePluginCondition::new(
+            self.root.root(),
+            Glob::new(format!(
+                "**/node_modules/{{{}}}/**",
+                self.packages.await?.join(",")
+            )),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    async fn after_resolve(
+        &self,
+        fs_path: Vc<FileSystemPath>,
+        _context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ResolveResultOption>> {
+        let (Request::Module { query, .. } | Request::Relative { query, .. }) = &*request.await?
+        else {
+            return Ok(ResolveResultOption::none());
+        };
+        let query = qstring::QString::from(&**query.await?);
+        let Some(name) = query.get(BARREL_EXPORT_QUERY) else {
+            return Ok(ResolveResultOption::none());
+        };
+
+        // Every import of a module which can't be optimized resolves to the
+        // module itself, without the query, so it's only instantiated once.
+        let unoptimized = ResolveResultOption::some(
+            ResolveResult::source(Vc::upcast(FileSource::new(fs_path))).cell(),
+        );
+
+        let Some(barrel) = *barrel_exports(fs_path).await? else {
+            return Ok(unoptimized);
+        };
+        let barrel = barrel.await?;
+
+        let reexport = if let Some((_, src, orig)) =
+            barrel.exports.iter().find(|(export, ..)| export == name)
+        {
+            let src = with_export_query(src, orig);
+            let src = StringifyJs(&src);
+            if orig == "*" {
+                format!("export * as {name} from {src};")
+            } else {
+                format!("export {{ {orig} as {name} }} from {src};")
+            }
+        } else if let [wildcard] = &barrel.wildcards[..] {
+            // The export can only come from the one `export *`.
+            format!(
+                "export {{ {name} }} from {};",
+                StringifyJs(&with_export_query(wildcard, name))
+            )
+        } else {
+            return Ok(unoptimized);
+        };
+
+        let mut code = barrel
+            .directives
+            .iter()
+            .map(|directive| format!("{};\n", StringifyJs(directive)))
+            .collect::<String>();
+        code.push_str(&reexport);
+        code.push('\n');
+
+        // The module lives next to the barrel file, so that its imports resolve
+        // the same.
+        let file_stem = fs_path.await?.file_stem().unwrap_or_default().to_string();
+        let source = VirtualSource::new(
+            fs_path
+                .parent()
+                .join(format!("{file_stem}.__barrel_export__.{name}.js")),
+            AssetContent::file(File::from(code).into()),
+        );
+        Ok(ResolveResultOption::some(
+            ResolveResult::source(Vc::upcast(source)).cell(),
+        ))
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::core::{
+        issue::{Issue, IssueExt, IssueSeverity},
+        resolve::{
+            options::{ImportMapResult, ImportMapping, ImportMappingReplacement},
+            parse::Request,
+            pattern::Pattern,
+        },
+    },
+};
+
+/// Returns an import mapping which reports imports of `next/router` in Client
+/// Components of the App Router as an error, and still resolves them to
+/// `target`, whose router fails at runtime as it's never mounted.
+///
+/// Imports from `node_modules` aren't reported, as packages commonly support
+/// both routers.
+pub(crate) fn next_router_import_mapping(
+    project_path: Vc<FileSystemPath>,
+    target: &str,
+) -> Vc<ImportMapping> {
+    ImportMapping::Dynamic(Vc::upcast(NextRouterImportReplacer::new(
+        project_path,
+        target.to_string(),
+    )))
+    .cell()
+}
+
+#[turbo_tasks::value(shared)]
+struct NextRouterImportReplacer {
+    project_path: Vc<FileSystemPath>,
+    target: String,
+}
+
+#[turbo_tasks::value_impl]
+impl NextRouterImportReplacer {
+    #[turbo_tasks::function]
+    fn new(project_path: Vc<FileSystemPath>, target: String) -> Vc<Self> {
+        Self::cell(NextRouterImportReplacer {
+            project_path,
+            target,
+        })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextRouterImportReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        if !context
+            .await?
+            .path
+            .split('/')
+            .any(|segment| segment == "node_modules")
+        {
+            NextRouterImportIssue { context }.cell().emit();
+        }
+
+        Ok(ImportMapResult::Alias(
+            Request::parse(Value::new(Pattern::Constant(self.target.clone()))),
+            Some(self.project_path),
+        )
+        .into())
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct NextRouterImportIssue {
+    context: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NextRouterImportIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(
+            "You have a Client Component in the App Router that imports next/router. Use \
+             next/navigation instead."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "The router of the Pages Router isn't mounted in the App Router, so it would only \
+             fail at runtime. Use the hooks of next/navigation, like useRouter, usePathname and \
+             useSearchParams, instead."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell("https://nextjs.org/docs/app/api-reference/functions/use-router".to_string())
+    }
+}
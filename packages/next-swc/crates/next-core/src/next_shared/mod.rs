@@ -1,2 +1,7 @@
+pub(crate) mod barrel;
+pub(crate) mod invalid_imports;
+pub(crate) mod module_requests;
+pub(crate) mod optional_peer_dependencies;
+pub(crate) mod package_imports;
 pub(crate) mod resolve;
 pub(crate) mod transforms;
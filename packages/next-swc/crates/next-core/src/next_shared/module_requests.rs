@@ -0,0 +1,119 @@
+use anyhow::Result;
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::{CallExpr, Callee, ExportAll, Expr, ImportDecl, Lit, NamedExport, Program},
+        visit::{Visit, VisitWith},
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbo::tasks_fs::{FileSystemEntryType, FileSystemPath};
+
+/// Returns the statically known requests of a module with the spans of their
+/// specifiers: the sources of imports and re-exports, and the string arguments
+/// of `require()` and `import()` calls. Type-only imports are skipped, as they
+/// don't end up in the output.
+pub(crate) fn module_requests(program: &Program) -> Vec<(String, Span)> {
+    let mut visitor = ModuleRequestsVisitor::default();
+    program.visit_with(&mut visitor);
+    visitor.requests
+}
+
+#[derive(Default)]
+struct ModuleRequestsVisitor {
+    requests: Vec<(String, Span)>,
+}
+
+impl Visit for ModuleRequestsVisitor {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        if !import.type_only {
+            self.requests
+                .push((import.src.value.to_string(), import.src.span));
+        }
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if let Some(src) = export.src.as_ref().filter(|_| !export.type_only) {
+            self.requests.push((src.value.to_string(), src.span));
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        if !export.type_only {
+            self.requests
+                .push((export.src.value.to_string(), export.src.span));
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let is_request = match &call.callee {
+            Callee::Import(_) => true,
+            Callee::Expr(callee) => {
+                matches!(&**callee, Expr::Ident(ident) if &*ident.sym == "require")
+            }
+            Callee::Super(_) => false,
+        };
+        if is_request {
+            if let Some(Expr::Lit(Lit::Str(request))) = call.args.first().map(|arg| &*arg.expr) {
+                self.requests
+                    .push((request.value.to_string(), request.span));
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+/// Returns the package name of a bare module request, e.g. `@scope/a` for
+/// `@scope/a/b`, or `None` for relative, absolute, `node:` and url requests.
+pub(crate) fn package_name(request: &str) -> Option<&str> {
+    if request.starts_with(['.', '/']) || request.contains(':') {
+        return None;
+    }
+    let mut segments = request.splitn(3, '/');
+    let first = segments.next().filter(|segment| !segment.is_empty())?;
+    if !first.starts_with('@') {
+        return Some(first);
+    }
+    let second = segments.next().filter(|segment| !segment.is_empty())?;
+    Some(&request[..first.len() + 1 + second.len()])
+}
+
+/// Whether `package` is installed in a `node_modules` directory of `dir` or one
+/// of its parents, where modules in `dir` resolve it from.
+pub(crate) async fn is_installed_package(dir: Vc<FileSystemPath>, package: &str) -> Result<bool> {
+    let mut dir = dir.resolve().await?;
+    loop {
+        let package_json = dir.join(format!("node_modules/{package}/package.json"));
+        if *package_json.get_type().await? == FileSystemEntryType::File {
+            return Ok(true);
+        }
+        let parent = dir.parent().resolve().await?;
+        if parent == dir {
+            return Ok(false);
+        }
+        dir = parent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::package_name;
+
+    #[test]
+    fn returns_the_package_of_bare_requests() {
+        assert_eq!(package_name("pg-native"), Some("pg-native"));
+        assert_eq!(package_name("lodash/get"), Some("lodash"));
+        assert_eq!(package_name("@scope/a"), Some("@scope/a"));
+        assert_eq!(package_name("@scope/a/b"), Some("@scope/a"));
+    }
+
+    #[test]
+    fn ignores_other_requests() {
+        assert_eq!(package_name("./a"), None);
+        assert_eq!(package_name("/a"), None);
+        assert_eq!(package_name("node:fs"), None);
+        assert_eq!(package_name("https://a.com/b.js"), None);
+        assert_eq!(package_name("@scope"), None);
+        assert_eq!(package_name(""), None);
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use indoc::formatdoc;
+use serde_json::Value as JsonValue;
+use swc_core::{common::errors::HANDLER, ecma::ast::Program};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent, FileJsonContent, FileSystemPath},
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            resolve::{
+                find_context_file,
+                options::{ImportMap, ImportMapResult, ImportMapping, ImportMappingReplacement},
+                package_json,
+                parse::Request,
+                FindContextFileResult, ResolveResult,
+            },
+            virtual_source::VirtualSource,
+        },
+        ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+        turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+    },
+};
+
+use crate::{
+    embed_js::next_js_file_path,
+    next_shared::{
+        module_requests::{is_installed_package, module_requests, package_name},
+        transforms::module_rule_match_js_no_url,
+    },
+};
+
+/// Inserts a fallback for module requests which don't resolve. When the
+/// package of the importing module declares the missing module as an optional
+/// peer dependency or optional dependency, like `pg-native` for `pg`, the
+/// import is replaced with a module throwing `MODULE_NOT_FOUND` at runtime.
+/// Packages `require` these in a `try` block, so the build shouldn't fail
+/// because of them. Other requests stay unresolved. The import maps only know
+/// the directory of the importing module, so the imports are reported as
+/// warnings by [get_optional_peer_dependencies_rule] instead, at the import.
+pub(crate) fn insert_optional_peer_dependency_alias(import_map: &mut ImportMap) {
+    import_map.insert_wildcard_alias(
+        "",
+        ImportMapping::Dynamic(Vc::upcast(NextOptionalPeerDependencyReplacer::new())).cell(),
+    );
+}
+
+#[turbo_tasks::value(shared)]
+struct NextOptionalPeerDependencyReplacer;
+
+#[turbo_tasks::value_impl]
+impl NextOptionalPeerDependencyReplacer {
+    #[turbo_tasks::function]
+    fn new() -> Vc<Self> {
+        Self::cell(NextOptionalPeerDependencyReplacer)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextOptionalPeerDependencyReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Request::Module { module, .. } = &*request.await? else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+        if !optional_dependencies(context).await?.contains(module) {
+            return Ok(ImportMapResult::NoEntry.into());
+        }
+
+        let name = module.replace('/', "_");
+        let content = optional_dependency_stub(module)?;
+        let source = VirtualSource::new(
+            next_js_file_path(format!("internal/optional-peer-dependency/{name}.js")),
+            AssetContent::file(FileContent::Content(File::from(content)).cell()),
+        );
+
+        Ok(ImportMapResult::Result(ResolveResult::source(Vc::upcast(source)).into()).into())
+    }
+}
+
+/// Returns the code of the module replacing a missing optional dependency.
+fn optional_dependency_stub(module: &str) -> Result<String> {
+    Ok(formatdoc! {
+        r#"
+            const error = new Error("Cannot find module '" + {module} + "'");
+            error.code = "MODULE_NOT_FOUND";
+            throw error;
+        "#,
+        module = serde_json::to_string(module)?
+    })
+}
+
+/// Returns the optional peer dependencies and optional dependencies declared by
+/// the package containing `context`.
+#[turbo_tasks::function]
+async fn optional_dependencies(context: Vc<FileSystemPath>) -> Result<Vc<Vec<String>>> {
+    let FindContextFileResult::Found(package_json, _) =
+        *find_context_file(context, package_json()).await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+    let FileJsonContent::Content(package) = &*package_json.read_json().await? else {
+        return Ok(Vc::cell(vec![]));
+    };
+    Ok(Vc::cell(declared_optional_dependencies(package)))
+}
+
+fn declared_optional_dependencies(package: &JsonValue) -> Vec<String> {
+    let optional_peers = package["peerDependenciesMeta"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, meta)| meta["optional"].as_bool() == Some(true))
+        .map(|(name, _)| name.clone());
+    let optional = package["optionalDependencies"]
+        .as_object()
+        .into_iter()
+        .flat_map(|dependencies| dependencies.keys().cloned());
+    optional_peers.chain(optional).collect()
+}
+
+/// Returns a rule which reports imports of optional dependencies which aren't
+/// installed as warnings, at the import. They're replaced with a module
+/// throwing at runtime by [insert_optional_peer_dependency_alias].
+pub fn get_optional_peer_dependencies_rule() -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(OptionalPeerDependencies) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+const DESCRIPTION: &str = "The package importing it declares it as optional, so the import is \
+                           replaced with a module throwing when it's evaluated. Install the \
+                           package if the code using it is needed.";
+
+#[derive(Debug)]
+struct OptionalPeerDependencies;
+
+#[async_trait]
+impl CustomTransformer for OptionalPeerDependencies {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let dir = ctx.file_path.parent();
+        let optional_dependencies = optional_dependencies(dir).await?;
+        if optional_dependencies.is_empty() {
+            return Ok(());
+        }
+
+        for (request, span) in module_requests(program) {
+            let Some(package) = package_name(&request) else {
+                continue;
+            };
+            if !optional_dependencies.iter().any(|name| name == package)
+                || is_installed_package(dir, package).await?
+            {
+                continue;
+            }
+
+            let message =
+                format!("The optional dependency '{package}' isn't installed\n{DESCRIPTION}");
+            HANDLER.with(|handler| handler.struct_span_warn(span, &message).emit());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{declared_optional_dependencies, optional_dependency_stub};
+
+    #[test]
+    fn collects_optional_peer_and_optional_dependencies() {
+        let package = json!({
+            "peerDependencies": { "pg-native": "*", "react": "*" },
+            "peerDependenciesMeta": {
+                "pg-native": { "optional": true },
+                "react": { "optional": false },
+            },
+            "optionalDependencies": { "bufferutil": "^4.0.0" },
+            "dependencies": { "ws": "^8.0.0" },
+        });
+        assert_eq!(
+            declared_optional_dependencies(&package),
+            vec!["pg-native".to_string(), "bufferutil".to_string()]
+        );
+        assert!(declared_optional_dependencies(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn stubs_throw_module_not_found() {
+        let stub = optional_dependency_stub("pg-native").unwrap();
+        assert!(stub.contains(r#"new Error("Cannot find module '" + "pg-native" + "'")"#));
+        assert!(stub.contains(r#"error.code = "MODULE_NOT_FOUND";"#));
+        assert!(stub.contains("throw error;"));
+    }
+}
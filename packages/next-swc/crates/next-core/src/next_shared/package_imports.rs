@@ -0,0 +1,235 @@
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileJsonContent, FileSystemPath},
+    turbopack::core::resolve::{
+        find_context_file,
+        options::{ImportMap, ImportMapResult, ImportMapping, ImportMappingReplacement},
+        package_json,
+        parse::Request,
+        pattern::Pattern,
+        FindContextFileResult,
+    },
+};
+
+/// Resolves `#`-prefixed requests with the `imports` field of the
+/// package.json of the importing module, like Node.js does for subpath
+/// imports. Conditional targets are selected with `conditions`, which are
+/// the conditions of the resolve context including the ones the resolver
+/// only implies for the `exports` field, like `browser`.
+pub(crate) fn insert_package_imports_alias(
+    import_map: &mut ImportMap,
+    conditions: Vc<Vec<String>>,
+) {
+    import_map.insert_wildcard_alias(
+        "#",
+        ImportMapping::Dynamic(Vc::upcast(NextPackageImportsReplacer::new(conditions))).cell(),
+    );
+}
+
+/// The conditions for the targets of the package.json `imports` field of a
+/// resolve context with the given custom conditions. Like for the `exports`
+/// field, `browser` contexts match `browser` and `module` contexts `module`.
+/// Requests aren't distinguished by their kind, so both `import` and
+/// `require` match and the first of them in the target wins.
+pub(crate) fn package_imports_conditions(
+    custom_conditions: &[String],
+    browser: bool,
+    module: bool,
+) -> Vc<Vec<String>> {
+    let mut conditions = custom_conditions.to_vec();
+    if browser {
+        conditions.push("browser".to_string());
+    }
+    if module {
+        conditions.push("module".to_string());
+    }
+    conditions.push("import".to_string());
+    conditions.push("require".to_string());
+    conditions.push("default".to_string());
+    Vc::cell(conditions)
+}
+
+#[turbo_tasks::value(shared)]
+struct NextPackageImportsReplacer {
+    conditions: Vc<Vec<String>>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextPackageImportsReplacer {
+    #[turbo_tasks::function]
+    fn new(conditions: Vc<Vec<String>>) -> Vc<Self> {
+        Self::cell(NextPackageImportsReplacer { conditions })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for NextPackageImportsReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Some(specifier) = request.await?.request() else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+        let FindContextFileResult::Found(package_json_path, _) =
+            *find_context_file(context, package_json()).await?
+        else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+        let FileJsonContent::Content(package) = &*package_json_path.read_json().await? else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+        let conditions = self.conditions.await?;
+        let Some(target) = resolve_package_import(&package["imports"], &specifier, &conditions)
+        else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        // Relative targets resolve inside of the package, other targets are
+        // package requests from it.
+        let request = Request::parse(Value::new(Pattern::Constant(target)));
+        Ok(ImportMapResult::Alias(request, Some(package_json_path.parent())).into())
+    }
+}
+
+/// Returns the target of `specifier` in the `imports` field of a
+/// package.json. An exact key takes precedence over patterns with a `*`,
+/// which match with the longest prefix first.
+fn resolve_package_import(
+    imports: &JsonValue,
+    specifier: &str,
+    conditions: &[String],
+) -> Option<String> {
+    let imports = imports.as_object()?;
+    if let Some(target) = imports.get(specifier) {
+        return select_target(target, None, conditions).flatten();
+    }
+
+    let mut best_match: Option<(&str, &str, &JsonValue)> = None;
+    for (key, target) in imports {
+        let Some((prefix, suffix)) = key.split_once('*') else {
+            continue;
+        };
+        if specifier.len() < key.len() || !specifier.starts_with(prefix) {
+            continue;
+        }
+        let Some(capture) = specifier[prefix.len()..].strip_suffix(suffix) else {
+            continue;
+        };
+        if best_match.map_or(true, |(best_prefix, ..)| prefix.len() > best_prefix.len()) {
+            best_match = Some((prefix, capture, target));
+        }
+    }
+    let (_, capture, target) = best_match?;
+    select_target(target, Some(capture), conditions).flatten()
+}
+
+/// Selects the target for the conditions, following nested conditions in
+/// the order of the object, and replaces the `*` of pattern targets with the
+/// capture. Returns `None` when no condition matches and `Some(None)` when
+/// the request is excluded with `null`.
+fn select_target(
+    target: &JsonValue,
+    capture: Option<&str>,
+    conditions: &[String],
+) -> Option<Option<String>> {
+    match target {
+        JsonValue::String(target) => Some(Some(match capture {
+            Some(capture) => target.replace('*', capture),
+            None => target.clone(),
+        })),
+        JsonValue::Null => Some(None),
+        JsonValue::Array(targets) => Some(
+            targets
+                .iter()
+                .find_map(|target| select_target(target, capture, conditions).flatten()),
+        ),
+        // The order of the conditions is kept with serde_json's `preserve_order`.
+        JsonValue::Object(targets) => targets
+            .iter()
+            .filter(|(condition, _)| conditions.contains(condition))
+            .find_map(|(_, target)| select_target(target, capture, conditions)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::resolve_package_import;
+
+    fn conditions(conditions: &[&str]) -> Vec<String> {
+        conditions.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn resolves_exact_and_pattern_imports() {
+        let imports = json!({
+            "#dep": "dep",
+            "#internal/*": "./src/internal/*.js",
+            "#internal/utils/*": "./src/utils/*.js",
+        });
+        let conditions = conditions(&["default"]);
+        assert_eq!(
+            resolve_package_import(&imports, "#dep", &conditions),
+            Some("dep".to_string())
+        );
+        assert_eq!(
+            resolve_package_import(&imports, "#internal/a", &conditions),
+            Some("./src/internal/a.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_import(&imports, "#internal/utils/b", &conditions),
+            Some("./src/utils/b.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_import(&imports, "#other", &conditions),
+            None
+        );
+    }
+
+    #[test]
+    fn selects_conditional_targets() {
+        let imports = json!({
+            "#env": {
+                "react-server": "./env.react-server.js",
+                "edge-light": "./env.edge.js",
+                "browser": "./env.browser.js",
+                "node": { "import": "./env.node.mjs", "default": "./env.node.js" },
+                "default": "./env.js",
+            },
+            "#excluded": { "browser": null, "default": "./excluded.js" },
+        });
+        let browser = conditions(&["development", "browser", "import", "default"]);
+        let node = conditions(&["development", "node", "import", "default"]);
+        let edge = conditions(&["development", "edge-light", "browser", "default"]);
+        let rsc = conditions(&["development", "node", "react-server", "default"]);
+        let other = conditions(&["default"]);
+
+        let resolve = |conditions| resolve_package_import(&imports, "#env", conditions);
+        assert_eq!(resolve(&browser), Some("./env.browser.js".to_string()));
+        assert_eq!(resolve(&node), Some("./env.node.mjs".to_string()));
+        assert_eq!(resolve(&edge), Some("./env.edge.js".to_string()));
+        assert_eq!(resolve(&rsc), Some("./env.react-server.js".to_string()));
+        assert_eq!(resolve(&other), Some("./env.js".to_string()));
+
+        assert_eq!(
+            resolve_package_import(&imports, "#excluded", &browser),
+            None
+        );
+        assert_eq!(
+            resolve_package_import(&imports, "#excluded", &node),
+            Some("./excluded.js".to_string())
+        );
+    }
+}
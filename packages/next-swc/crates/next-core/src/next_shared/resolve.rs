@@ -9,7 +9,7 @@ use turbopack_binding::{
     turbopack::core::{
         diagnostics::DiagnosticExt,
         file_source::FileSource,
-        issue::{unsupported_module::UnsupportedModuleIssue, IssueExt},
+        issue::{unsupported_module::UnsupportedModuleIssue, Issue, IssueExt, IssueSeverity},
         resolve::{
             parse::Request,
             pattern::Pattern,
@@ -25,7 +25,7 @@ lazy_static! {
     static ref UNSUPPORTED_PACKAGES: HashSet<&'static str> = [].into();
     static ref UNSUPPORTED_PACKAGE_PATHS: HashSet<(&'static str, &'static str)> = [].into();
     // Set of the features we want to track, following existing references in webpack/plugins/telemetry-plugin.
-    static ref FEATURE_MODULES: HashMap<&'static str, Vec<&'static str>> = HashMap::from([
+    pub(crate) static ref FEATURE_MODULES: HashMap<&'static str, Vec<&'static str>> = HashMap::from([
         (
             "next",
             vec![
@@ -304,3 +304,133 @@ impl ResolvePlugin for NextSharedRuntimeResolvePlugin {
         )))
     }
 }
+
+/// A resolver plugin which reports copies of React resolved from
+/// `node_modules` in the server graph of the App Router. Its contexts alias
+/// `react` and `react-dom` to the copies vendored by Next.js, so a package
+/// reaching a different copy, like its own nested one, would break hooks with
+/// duplicate React errors at runtime.
+#[turbo_tasks::value]
+pub(crate) struct NextDuplicateReactResolvePlugin {
+    root: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl NextDuplicateReactResolvePlugin {
+    #[turbo_tasks::function]
+    pub fn new(root: Vc<FileSystemPath>) -> Vc<Self> {
+        NextDuplicateReactResolvePlugin { root }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for NextDuplicateReactResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> Vc<ResolvePluginCondition> {
+        ResolvePluginCondition::new(
+            self.root.root(),
+            Glob::new("**/node_modules/{react,react-dom}/**".to_string()),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn after_resolve(
+        &self,
+        fs_path: Vc<FileSystemPath>,
+        context: Vc<FileSystemPath>,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ResolveResultOption>> {
+        let path = &fs_path.await?.path;
+        if let Some(package) = react_package_in_node_modules(path) {
+            DuplicateReactIssue {
+                path: fs_path,
+                context: context.await?.path.clone(),
+                package: package.to_string(),
+            }
+            .cell()
+            .emit();
+        }
+
+        Ok(ResolveResultOption::none())
+    }
+}
+
+/// Returns the name of the React package the path is inside of, if it's a copy
+/// installed in `node_modules` rather than the one vendored by Next.js in
+/// `next/dist/compiled`.
+fn react_package_in_node_modules(path: &str) -> Option<&str> {
+    let (_, package_path) = path.rsplit_once("node_modules/")?;
+    let (package, _) = package_path.split_once('/')?;
+    matches!(package, "react" | "react-dom").then_some(package)
+}
+
+#[turbo_tasks::value(shared)]
+struct DuplicateReactIssue {
+    /// The resolved file of the second copy. Resolve plugins only get the
+    /// directory the request was resolved from, not the importing module.
+    path: Vc<FileSystemPath>,
+    context: String,
+    package: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for DuplicateReactIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "A second copy of {} is included in the server bundle",
+            self.package
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "An import from {} resolves to this copy of `{}`, but the App Router renders with the \
+             React vendored by Next.js. Using two copies of React breaks hooks at runtime. Import \
+             `react` and `react-dom` by their package names so they resolve to the vendored copy.",
+            self.context, self.package
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::react_package_in_node_modules;
+
+    #[test]
+    fn finds_react_copies_in_node_modules() {
+        assert_eq!(
+            react_package_in_node_modules("node_modules/react/index.js"),
+            Some("react")
+        );
+        assert_eq!(
+            react_package_in_node_modules("node_modules/some-lib/node_modules/react-dom/client.js"),
+            Some("react-dom")
+        );
+        assert_eq!(
+            react_package_in_node_modules("node_modules/next/dist/compiled/react/index.js"),
+            None
+        );
+        assert_eq!(
+            react_package_in_node_modules("node_modules/react-is/index.js"),
+            None
+        );
+        assert_eq!(react_package_in_node_modules("app/react/page.js"), None);
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_swc::auto_pure::auto_pure;
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+use crate::mode::NextMode;
+
+/// Returns a rule which annotates calls of known side effect free package
+/// exports, like the icon factories of UI libraries, with `/*#__PURE__*/` in
+/// production builds, like the `auto_pure` pass of the webpack build.
+pub fn get_auto_pure_rule(mode: NextMode) -> Option<ModuleRule> {
+    if mode != NextMode::Build {
+        return None;
+    }
+
+    let transformer = EcmascriptInputTransform::Plugin(Vc::cell(Box::new(AutoPure) as _));
+    Some(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    ))
+}
+
+#[derive(Debug)]
+struct AutoPure;
+
+#[async_trait]
+impl CustomTransformer for AutoPure {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut auto_pure(ctx.comments.clone()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use indexmap::indexmap;
+use next_swc::cjs_optimizer::{cjs_optimizer, Config, PackageConfig};
+use swc_core::{
+    common::{util::take::Take, SyntaxContext},
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which rewrites `require`s of packages with a known heavy
+/// entry, like `next/server`, to `require`s of the subpaths of the used
+/// properties, like the `cjsRequireOptimizer` option of the webpack build.
+pub fn get_cjs_optimizer_rule() -> ModuleRule {
+    let packages = indexmap! {
+        "next/server" => indexmap! {
+            "NextRequest" => "next/dist/server/web/spec-extension/request",
+            "NextResponse" => "next/dist/server/web/spec-extension/response",
+            "ImageResponse" => "next/dist/server/web/spec-extension/image-response",
+            "userAgentFromString" => "next/dist/server/web/spec-extension/user-agent",
+            "userAgent" => "next/dist/server/web/spec-extension/user-agent",
+        },
+    };
+    let config = Config {
+        packages: packages
+            .into_iter()
+            .map(|(package, transforms)| {
+                (
+                    package.to_string(),
+                    PackageConfig {
+                        transforms: transforms
+                            .into_iter()
+                            .map(|(property, subpath)| (property.into(), subpath.into()))
+                            .collect(),
+                    },
+                )
+            })
+            .collect(),
+    };
+
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(CjsOptimizer { config }) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct CjsOptimizer {
+    config: Config,
+}
+
+#[async_trait]
+impl CustomTransformer for CjsOptimizer {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut cjs_optimizer(
+            self.config.clone(),
+            SyntaxContext::empty().apply_mark(ctx.unresolved_mark),
+        ));
+
+        Ok(())
+    }
+}
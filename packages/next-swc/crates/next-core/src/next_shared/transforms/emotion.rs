@@ -2,14 +2,45 @@ use anyhow::Result;
 use turbo_tasks::Vc;
 use turbopack_binding::turbopack::{
     ecmascript::OptionTransformPlugin,
-    ecmascript_plugin::transform::emotion::{EmotionTransformConfig, EmotionTransformer},
+    ecmascript_plugin::transform::emotion::{
+        EmotionLabelKind, EmotionTransformConfig, EmotionTransformer,
+    },
 };
 
-use crate::next_config::{EmotionTransformOptionsOrBoolean, NextConfig};
+use crate::{
+    mode::NextMode,
+    next_config::{EmotionTransformOptions, EmotionTransformOptionsOrBoolean, NextConfig},
+};
+
+/// Resolves the options the same as the webpack build: labels are only added
+/// in development unless `autoLabel` says otherwise, and source maps are
+/// never generated in production.
+fn emotion_transform_config(
+    options: &EmotionTransformOptions,
+    mode: NextMode,
+) -> EmotionTransformConfig {
+    let is_development = matches!(mode, NextMode::Development);
+    let auto_label = match options.auto_label {
+        Some(EmotionLabelKind::Always) => true,
+        Some(EmotionLabelKind::Never) => false,
+        Some(EmotionLabelKind::DevOnly) | None => is_development,
+    };
+    EmotionTransformConfig {
+        sourcemap: Some(is_development && options.source_map.unwrap_or(true)),
+        label_format: options.label_format.clone(),
+        auto_label: Some(if auto_label {
+            EmotionLabelKind::Always
+        } else {
+            EmotionLabelKind::Never
+        }),
+        import_map: options.import_map.clone(),
+    }
+}
 
 #[turbo_tasks::function]
 pub async fn get_emotion_transform_plugin(
     next_config: Vc<NextConfig>,
+    mode: NextMode,
 ) -> Result<Vc<OptionTransformPlugin>> {
     let transform_plugin = next_config
         .await?
@@ -21,15 +52,13 @@ pub async fn get_emotion_transform_plugin(
                 .as_ref()
                 .map(|value| {
                     let transformer = match value {
-                        EmotionTransformOptionsOrBoolean::Boolean(true) => {
-                            EmotionTransformer::new(&EmotionTransformConfig {
-                                ..Default::default()
-                            })
-                        }
+                        EmotionTransformOptionsOrBoolean::Boolean(true) => EmotionTransformer::new(
+                            &emotion_transform_config(&Default::default(), mode),
+                        ),
                         EmotionTransformOptionsOrBoolean::Boolean(false) => None,
 
                         EmotionTransformOptionsOrBoolean::Options(value) => {
-                            EmotionTransformer::new(value)
+                            EmotionTransformer::new(&emotion_transform_config(value, mode))
                         }
                     };
 
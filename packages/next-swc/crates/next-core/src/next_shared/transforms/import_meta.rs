@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use next_swc::import_meta::import_meta;
+use swc_core::{
+    common::{util::take::Take, FileName, SyntaxContext},
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{DiskFileSystem, FileSystemPath},
+    turbopack::{
+        ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+        turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+    },
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which gives `import.meta.url` and `import.meta.env` the same
+/// values as in webpack builds. `import.meta.url` is only replaced on the
+/// server, so browser chunks don't contain paths on disk.
+pub async fn get_import_meta_transform_rule(
+    project_path: Vc<FileSystemPath>,
+    is_server: bool,
+) -> Result<ModuleRule> {
+    let root = match Vc::try_resolve_downcast_type::<DiskFileSystem>(project_path.fs()).await? {
+        Some(disk_fs) => Some(PathBuf::from(&disk_fs.await?.root)),
+        None => None,
+    };
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(
+            Box::new(NextImportMeta { root, is_server }) as _
+        ));
+    Ok(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    ))
+}
+
+#[derive(Debug)]
+struct NextImportMeta {
+    /// The directory on disk the module paths are relative to.
+    root: Option<PathBuf>,
+    is_server: bool,
+}
+
+#[async_trait]
+impl CustomTransformer for NextImportMeta {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let file_name = match &self.root {
+            Some(root) => FileName::Real(root.join(ctx.file_path_str)),
+            None => FileName::Anon,
+        };
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut import_meta(
+            &file_name,
+            SyntaxContext::empty().apply_mark(ctx.unresolved_mark),
+            self.is_server,
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::{bail, Result};
+use serde_json::{Map, Value as JsonValue};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent, FileSystemPath},
+    turbopack::{
+        core::{
+            asset::{Asset, AssetContent},
+            issue::{Issue, IssueExt, IssueSeverity},
+            source::Source,
+            source_transform::SourceTransform,
+            virtual_source::VirtualSource,
+        },
+        turbopack::module_options::{ModuleRule, ModuleRuleCondition, ModuleRuleEffect},
+    },
+};
+
+/// Returns a rule which turns the YAML frontmatter of `.mdx` files into a
+/// `frontmatter` export, like `remark-mdx-frontmatter` does for `@next/mdx`.
+pub fn get_mdx_frontmatter_rule() -> ModuleRule {
+    ModuleRule::new(
+        ModuleRuleCondition::ResourcePathEndsWith(".mdx".to_string()),
+        vec![ModuleRuleEffect::SourceTransforms(Vc::cell(vec![
+            Vc::upcast(MdxFrontmatterTransform::new()),
+        ]))],
+    )
+}
+
+#[turbo_tasks::value]
+struct MdxFrontmatterTransform;
+
+#[turbo_tasks::value_impl]
+impl MdxFrontmatterTransform {
+    #[turbo_tasks::function]
+    fn new() -> Vc<Self> {
+        MdxFrontmatterTransform.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl SourceTransform for MdxFrontmatterTransform {
+    #[turbo_tasks::function]
+    async fn transform(&self, source: Vc<Box<dyn Source>>) -> Result<Vc<Box<dyn Source>>> {
+        let content = source.content().file_content().await?;
+        let FileContent::Content(file) = &*content else {
+            return Ok(source);
+        };
+        let code = file.content().to_str()?;
+        let Some(ExportedFrontmatter { code, error }) = export_frontmatter(&code) else {
+            return Ok(source);
+        };
+        let path = source.ident().path();
+        if let Some(error) = error {
+            MdxFrontmatterIssue { path, error }.cell().emit();
+        }
+        Ok(Vc::upcast(VirtualSource::new(
+            path,
+            AssetContent::file(File::from(code).into()),
+        )))
+    }
+}
+
+/// The code of an MDX file with its frontmatter replaced.
+#[derive(Debug, PartialEq)]
+struct ExportedFrontmatter {
+    code: String,
+    /// Why the frontmatter couldn't be exported, if it couldn't.
+    error: Option<String>,
+}
+
+/// Replaces the frontmatter at the start of `code` with an ESM export of its
+/// values, padded with empty lines so the lines of the content stay the same.
+/// When the frontmatter isn't a valid YAML mapping, it's replaced with empty
+/// lines only. Returns `None` if there's no frontmatter.
+fn export_frontmatter(code: &str) -> Option<ExportedFrontmatter> {
+    let rest = code
+        .strip_prefix("---\n")
+        .or_else(|| code.strip_prefix("---\r\n"))?;
+    let mut yaml = String::new();
+    let mut yaml_lines = 0;
+    let mut lines = rest.split_inclusive('\n');
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == "---" {
+            closed = true;
+            break;
+        }
+        yaml.push_str(line);
+        yaml_lines += 1;
+    }
+    if !closed {
+        return None;
+    }
+
+    let (mut code, error) = match parse_frontmatter(&yaml) {
+        // The export needs to be followed by an empty line to end the ESM block.
+        Ok(frontmatter) => (
+            format!(
+                "export const frontmatter = {}\n{}",
+                JsonValue::Object(frontmatter),
+                "\n".repeat(yaml_lines + 1)
+            ),
+            None,
+        ),
+        Err(err) => ("\n".repeat(yaml_lines + 2), Some(format!("{err:#}"))),
+    };
+    code.extend(lines);
+    Some(ExportedFrontmatter { code, error })
+}
+
+/// Parses the YAML of a frontmatter, which needs to be a mapping. An empty
+/// frontmatter is an empty mapping.
+fn parse_frontmatter(yaml: &str) -> Result<Map<String, JsonValue>> {
+    match serde_yaml::from_str::<Option<JsonValue>>(yaml)? {
+        None => Ok(Map::new()),
+        Some(JsonValue::Object(map)) => Ok(map),
+        Some(_) => bail!("The frontmatter needs to be a mapping of keys to values"),
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct MdxFrontmatterIssue {
+    path: Vc<FileSystemPath>,
+    error: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for MdxFrontmatterIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell("Invalid frontmatter in MDX file".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("parse".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "{}\n\nThe frontmatter is removed from the content, but no `frontmatter` is exported.",
+            self.error
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::export_frontmatter;
+
+    fn exported_value(code: &str) -> serde_json::Value {
+        let result = export_frontmatter(code).unwrap();
+        assert_eq!(result.error, None);
+        // The lines of the content don't move.
+        assert_eq!(result.code.lines().count(), code.lines().count());
+        let export = result.code.lines().next().unwrap();
+        serde_json::from_str(export.strip_prefix("export const frontmatter = ").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn exports_frontmatter() {
+        let code = "---\ntitle: 'Hello, world'\ndate: 2023-10-01\ndraft: false\ntags: [a, \
+                    \"b\"]\nauthors:\n  - Jane\n  - John\n---\n\n# Hello\n";
+        assert_eq!(
+            exported_value(code),
+            json!({
+                "title": "Hello, world",
+                "date": "2023-10-01",
+                "draft": false,
+                "tags": ["a", "b"],
+                "authors": ["Jane", "John"],
+            })
+        );
+        assert!(export_frontmatter(code)
+            .unwrap()
+            .code
+            .ends_with("\n\n# Hello\n"));
+    }
+
+    #[test]
+    fn exports_nested_values() {
+        let code = "---\nauthor:\n  name: Jane\n  links: { github: jane }\nsummary: |\n  Line \
+                    one\n  Line two\nexcerpt: >\n  Folded\n  text\n---\n# Hello\n";
+        assert_eq!(
+            exported_value(code),
+            json!({
+                "author": { "name": "Jane", "links": { "github": "jane" } },
+                "summary": "Line one\nLine two\n",
+                "excerpt": "Folded text\n",
+            })
+        );
+    }
+
+    #[test]
+    fn exports_empty_frontmatter() {
+        assert_eq!(exported_value("---\n---\n# Hello\n"), json!({}));
+    }
+
+    #[test]
+    fn removes_invalid_frontmatter() {
+        let code = "---\ntitle: [unclosed\n---\n# Hello\n";
+        let result = export_frontmatter(code).unwrap();
+        assert!(result.error.is_some());
+        assert_eq!(result.code, "\n\n\n# Hello\n");
+
+        let result = export_frontmatter("---\n- a\n- b\n---\n").unwrap();
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn ignores_files_without_frontmatter() {
+        assert_eq!(export_frontmatter("# Hello\n\n---\n"), None);
+        assert_eq!(export_frontmatter("---\ntitle: Hello\n"), None);
+    }
+}
@@ -1,19 +1,33 @@
+pub(crate) mod auto_pure;
+pub(crate) mod cjs_optimizer;
 pub(crate) mod emotion;
+pub(crate) mod import_meta;
+pub(crate) mod mdx_frontmatter;
 pub(crate) mod modularize_imports;
 pub(crate) mod next_dynamic;
 pub(crate) mod next_font;
 pub(crate) mod next_strip_page_exports;
+pub(crate) mod optimize_package_imports;
+pub(crate) mod react_server_components;
 pub(crate) mod relay;
+pub(crate) mod remove_console;
 pub(crate) mod server_actions;
 pub(crate) mod styled_components;
 pub(crate) mod styled_jsx;
 pub(crate) mod swc_ecma_transform_plugins;
 
+pub use auto_pure::get_auto_pure_rule;
+pub use cjs_optimizer::get_cjs_optimizer_rule;
+pub use import_meta::get_import_meta_transform_rule;
+pub use mdx_frontmatter::get_mdx_frontmatter_rule;
 pub use modularize_imports::{get_next_modularize_imports_rule, ModularizeImportPackageConfig};
 pub use next_dynamic::get_next_dynamic_transform_rule;
 pub use next_font::get_next_font_transform_rule;
 pub use next_strip_page_exports::get_next_pages_transforms_rule;
+pub use optimize_package_imports::get_optimize_package_imports_rule;
+pub use react_server_components::get_react_server_components_transform_rule;
 pub use relay::get_relay_transform_plugin;
+pub use remove_console::get_remove_console_transform_rule;
 pub use server_actions::get_server_actions_transform_rule;
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::turbopack::{
@@ -55,7 +69,8 @@ pub fn get_next_image_rule() -> ModuleRule {
     )
 }
 
-/// Returns a rule which applies the Next.js dynamic transform.
+/// Matches the modules the Next.js-specific ecmascript transforms apply to.
+/// This includes MDX files, which compile to ecmascript.
 pub(crate) fn module_rule_match_js_no_url() -> ModuleRuleCondition {
     ModuleRuleCondition::all(vec![
         ModuleRuleCondition::not(ModuleRuleCondition::ReferenceType(ReferenceType::Url(
@@ -66,6 +81,7 @@ pub(crate) fn module_rule_match_js_no_url() -> ModuleRuleCondition {
             ModuleRuleCondition::ResourcePathEndsWith(".jsx".to_string()),
             ModuleRuleCondition::ResourcePathEndsWith(".ts".to_string()),
             ModuleRuleCondition::ResourcePathEndsWith(".tsx".to_string()),
+            ModuleRuleCondition::ResourcePathEndsWith(".mdx".to_string()),
         ]),
     ])
 }
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_swc::split_named_imports::{split_named_imports, Config};
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which splits the named imports of the
+/// `optimizePackageImports` packages, so that they can be resolved past the
+/// packages' barrel files.
+pub fn get_optimize_package_imports_rule(packages: &[String]) -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(OptimizePackageImportsTransformer {
+            packages: packages.to_vec(),
+        }) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct OptimizePackageImportsTransformer {
+    packages: Vec<String>,
+}
+
+#[async_trait]
+impl CustomTransformer for OptimizePackageImportsTransformer {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut split_named_imports(Config {
+            packages: self.packages.clone(),
+        }));
+
+        Ok(())
+    }
+}
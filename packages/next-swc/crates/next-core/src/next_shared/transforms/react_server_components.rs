@@ -0,0 +1,211 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use next_swc::react_server_components::{server_components, Config, Options};
+use swc_core::{
+    common::{
+        comments::SwcComments,
+        errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+        FileName, MultiSpan,
+    },
+    ecma::{ast::Program, visit::VisitMutWith},
+};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+        turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+    },
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which validates the React Server Components boundaries of
+/// App Router modules, like the `react_server_components` transform does for
+/// webpack: the directives of a module, and the imports and React APIs which
+/// aren't available in the layer it's compiled for.
+///
+/// Unlike for webpack, the module isn't rewritten. Turbopack creates the client
+/// references from the `"use client"` directive itself.
+pub async fn get_react_server_components_transform_rule(
+    is_react_server_layer: bool,
+    app_dir: Vc<FileSystemPath>,
+) -> Result<ModuleRule> {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextReactServerComponents {
+            is_react_server_layer,
+            app_dir: app_dir.await?.path.clone().into(),
+        }) as _));
+    Ok(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    ))
+}
+
+#[derive(Debug)]
+struct NextReactServerComponents {
+    is_react_server_layer: bool,
+    app_dir: PathBuf,
+}
+
+#[async_trait]
+impl CustomTransformer for NextReactServerComponents {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        // Apart from the placement of the directives, the transform doesn't check
+        // modules in `node_modules`, so skip copying them.
+        if ctx
+            .file_path_str
+            .split('/')
+            .any(|segment| segment == "node_modules")
+        {
+            return Ok(());
+        }
+
+        // The transform replaces Client Components in the server layer with a
+        // client reference and removes the directives, which Turbopack still reads
+        // afterwards. So it checks a copy of the program, which is dropped.
+        let mut validated = program.clone();
+        let emitter = CollectingEmitter::default();
+        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+        HANDLER.set(&handler, || {
+            validated.visit_mut_with(&mut server_components(
+                FileName::Real(ctx.file_path_str.into()),
+                Config::WithOptions(Options {
+                    is_react_server_layer: self.is_react_server_layer,
+                }),
+                SwcComments::default(),
+                Some(self.app_dir.clone()),
+            ))
+        });
+
+        let file_name = ctx
+            .file_path_str
+            .rsplit('/')
+            .next()
+            .unwrap_or(ctx.file_path_str);
+        for (message, span) in emitter.0.lock().unwrap().drain(..) {
+            let message = format_rsc_error(&message, file_name);
+            HANDLER.with(|handler| handler.struct_span_err(span, &message).emit());
+        }
+        Ok(())
+    }
+}
+
+/// Collects the errors of the React Server Components transform, which are
+/// reported with the `NEXT_RSC_ERR_*` codes webpack's `parseRSC` formats.
+#[derive(Clone, Default)]
+struct CollectingEmitter(Arc<Mutex<Vec<(String, MultiSpan)>>>);
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((db.message(), db.span.clone()));
+    }
+}
+
+/// Returns the message of an error of the React Server Components transform,
+/// like `formatRSCErrorMessage` in `parseRSC.ts` formats it for App Router
+/// modules. Errors without a code are returned as they are.
+fn format_rsc_error(message: &str, file_name: &str) -> String {
+    const REACT_ESSENTIALS: &str = "https://nextjs.org/docs/getting-started/react-essentials";
+
+    let (code, argument) = message.split_once(": ").unwrap_or((message, ""));
+    match code {
+        "NEXT_RSC_ERR_REACT_API" if argument == "Component" => format!(
+            "You’re importing a class component. It only works in a Client Component but none \
+             of its parents are marked with \"use client\", so they're Server Components by \
+             default.\nLearn more: {REACT_ESSENTIALS}#client-components"
+        ),
+        "NEXT_RSC_ERR_REACT_API" => format!(
+            "You're importing a component that needs {argument}. It only works in a Client \
+             Component but none of its parents are marked with \"use client\", so they're \
+             Server Components by default.\nLearn more: {REACT_ESSENTIALS}"
+        ),
+        "NEXT_RSC_ERR_SERVER_IMPORT" if argument == "react-dom/server" => format!(
+            "You're importing a component that imports react-dom/server. To fix it, render or \
+             return the content directly as a Server Component instead for perf and \
+             security.\nLearn more: {REACT_ESSENTIALS}"
+        ),
+        "NEXT_RSC_ERR_SERVER_IMPORT" if argument == "next/router" => {
+            "You have a Server Component that imports next/router. Use next/navigation \
+             instead.\nLearn more: https://nextjs.org/docs/app/api-reference/functions/use-router"
+                .to_string()
+        }
+        "NEXT_RSC_ERR_SERVER_IMPORT" => format!(
+            "You're importing a component that imports {argument}. It only works in a Client \
+             Component but none of its parents are marked with \"use client\", so they're \
+             Server Components by default.\nLearn more: {REACT_ESSENTIALS}"
+        ),
+        "NEXT_RSC_ERR_CLIENT_IMPORT" => format!(
+            "You're importing a component that needs {argument}. That only works in a Server \
+             Component but one of its parents is marked with \"use client\", so it's a Client \
+             Component.\nLearn more: {REACT_ESSENTIALS}"
+        ),
+        "NEXT_RSC_ERR_CLIENT_DIRECTIVE" => {
+            "The \"use client\" directive must be placed before other expressions. Move it to \
+             the top of the file to resolve this issue."
+                .to_string()
+        }
+        "NEXT_RSC_ERR_CLIENT_DIRECTIVE_PAREN" => format!(
+            "\"use client\" must be a directive, and placed before other expressions. Remove the \
+             parentheses and move it to the top of the file to resolve this issue.\nLearn more: \
+             {REACT_ESSENTIALS}#the-use-client-directive"
+        ),
+        "NEXT_RSC_ERR_INVALID_API" => format!(
+            "\"{argument}\" is not supported in app/. Read more: \
+             https://nextjs.org/docs/app/building-your-application/data-fetching"
+        ),
+        "NEXT_RSC_ERR_ERROR_FILE_SERVER_COMPONENT" => format!(
+            "{file_name} must be a Client Component. Add the \"use client\" directive the top of \
+             the file to resolve this issue.\nLearn more: {REACT_ESSENTIALS}#client-components"
+        ),
+        "NEXT_RSC_ERR_CLIENT_METADATA_EXPORT" => format!(
+            "You are attempting to export \"{argument}\" from a component marked with \"use \
+             client\", which is disallowed. Either remove the export, or the \"use client\" \
+             directive. Read more: {REACT_ESSENTIALS}#the-use-client-directive"
+        ),
+        "NEXT_RSC_ERR_CONFLICT_METADATA_EXPORT" => {
+            "\"metadata\" and \"generateMetadata\" cannot be exported at the same time, please \
+             keep one of them. Read more: \
+             https://nextjs.org/docs/app/api-reference/file-conventions/metadata"
+                .to_string()
+        }
+        _ => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_rsc_error;
+
+    #[test]
+    fn formats_the_error_codes() {
+        assert_eq!(
+            format_rsc_error("NEXT_RSC_ERR_CLIENT_IMPORT: server-only", "page.js"),
+            "You're importing a component that needs server-only. That only works in a Server \
+             Component but one of its parents is marked with \"use client\", so it's a Client \
+             Component.\nLearn more: https://nextjs.org/docs/getting-started/react-essentials"
+        );
+        assert_eq!(
+            format_rsc_error("NEXT_RSC_ERR_ERROR_FILE_SERVER_COMPONENT", "error.js"),
+            "error.js must be a Client Component. Add the \"use client\" directive the top of \
+             the file to resolve this issue.\nLearn more: \
+             https://nextjs.org/docs/getting-started/react-essentials#client-components"
+        );
+        assert_eq!(
+            format_rsc_error(
+                "It's not possible to have both `use client` and `use server` directives in the \
+                 same file.",
+                "page.js"
+            ),
+            "It's not possible to have both `use client` and `use server` directives in the same \
+             file."
+        );
+    }
+}
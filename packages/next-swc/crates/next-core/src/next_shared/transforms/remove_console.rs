@@ -0,0 +1,76 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use remove_console::{remove_console, Config, Options};
+use swc_core::{
+    common::{util::take::Take, SyntaxContext},
+    ecma::{
+        ast::{Module, Program},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+use crate::{
+    mode::NextMode,
+    next_config::{NextConfig, RemoveConsoleConfig},
+};
+
+/// Returns a rule which removes `console.*` calls in production builds, as
+/// configured by `compiler.removeConsole`.
+pub async fn get_remove_console_transform_rule(
+    next_config: Vc<NextConfig>,
+    mode: NextMode,
+) -> Result<Option<ModuleRule>> {
+    if mode != NextMode::Build {
+        return Ok(None);
+    }
+
+    let config = match next_config
+        .await?
+        .compiler
+        .as_ref()
+        .and_then(|compiler| compiler.remove_console.as_ref())
+    {
+        Some(RemoveConsoleConfig::Boolean(true)) => Config::All(true),
+        Some(RemoveConsoleConfig::Config { exclude }) => Config::WithOptions(Options {
+            exclude: exclude
+                .iter()
+                .flatten()
+                .map(|method| method.as_str().into())
+                .collect(),
+        }),
+        Some(RemoveConsoleConfig::Boolean(false)) | None => return Ok(None),
+    };
+
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(RemoveConsole { config }) as _));
+    Ok(Some(ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )))
+}
+
+#[derive(Debug)]
+struct RemoveConsole {
+    config: Config,
+}
+
+#[async_trait]
+impl CustomTransformer for RemoveConsole {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut remove_console(
+            self.config.clone(),
+            SyntaxContext::empty().apply_mark(ctx.unresolved_mark),
+        ));
+
+        Ok(())
+    }
+}
@@ -7,11 +7,44 @@ use turbopack_binding::turbopack::{
     },
 };
 
-use crate::next_config::{NextConfig, StyledComponentsTransformOptionsOrBoolean};
+use crate::{
+    mode::NextMode,
+    next_config::{
+        NextConfig, StyledComponentsTransformOptions, StyledComponentsTransformOptionsOrBoolean,
+    },
+};
+
+/// Fills in the options which aren't set the same as the webpack build:
+/// `displayName` depends on the mode, the others take the defaults of the
+/// transform.
+fn styled_components_transform_config(
+    options: &StyledComponentsTransformOptions,
+    mode: NextMode,
+) -> StyledComponentsTransformConfig {
+    let defaults = StyledComponentsTransformConfig::default();
+    StyledComponentsTransformConfig {
+        display_name: options
+            .display_name
+            .unwrap_or(matches!(mode, NextMode::Development)),
+        ssr: options.ssr.unwrap_or(defaults.ssr),
+        file_name: options.file_name.unwrap_or(defaults.file_name),
+        top_level_import_paths: options
+            .top_level_import_paths
+            .clone()
+            .unwrap_or(defaults.top_level_import_paths),
+        meaningless_file_names: options
+            .meaningless_file_names
+            .clone()
+            .unwrap_or(defaults.meaningless_file_names),
+        css_prop: options.css_prop.unwrap_or(defaults.css_prop),
+        namespace: options.namespace.clone().or(defaults.namespace),
+    }
+}
 
 #[turbo_tasks::function]
 pub async fn get_styled_components_transform_plugin(
     next_config: Vc<NextConfig>,
+    mode: NextMode,
 ) -> Result<Vc<OptionTransformPlugin>> {
     let transform_plugin = next_config
         .await?
@@ -23,14 +56,16 @@ pub async fn get_styled_components_transform_plugin(
                 .as_ref()
                 .map(|value| {
                     let transformer = match value {
-                        StyledComponentsTransformOptionsOrBoolean::Boolean(true) => Some(
-                            StyledComponentsTransformer::new(&StyledComponentsTransformConfig {
-                                ..Default::default()
-                            }),
-                        ),
+                        StyledComponentsTransformOptionsOrBoolean::Boolean(true) => {
+                            Some(StyledComponentsTransformer::new(
+                                &styled_components_transform_config(&Default::default(), mode),
+                            ))
+                        }
                         StyledComponentsTransformOptionsOrBoolean::Boolean(false) => None,
                         StyledComponentsTransformOptionsOrBoolean::Options(value) => {
-                            Some(StyledComponentsTransformer::new(value))
+                            Some(StyledComponentsTransformer::new(
+                                &styled_components_transform_config(value, mode),
+                            ))
                         }
                     };
 
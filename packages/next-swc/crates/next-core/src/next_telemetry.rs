@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 
+use indexmap::IndexMap;
 use turbopack_binding::{
     turbo::tasks::Vc,
-    turbopack::core::diagnostics::{Diagnostic, DiagnosticPayload},
+    turbopack::core::diagnostics::{Diagnostic, DiagnosticPayload, PlainDiagnostic},
 };
 
+use crate::next_shared::resolve::FEATURE_MODULES;
+
+const MODULE_FEATURE_TELEMETRY_CATEGORY: &str = "ModuleFeatureTelemetry_category_tbd";
+
 /// A struct represent telemetry event if certain feature of next.js
 /// is enabled, such as next.config.swcMinify.
 /// This is an equivalent representation of the following code:
@@ -70,7 +75,7 @@ impl ModuleFeatureTelemetry {
 impl Diagnostic for ModuleFeatureTelemetry {
     #[turbo_tasks::function]
     fn category(&self) -> Vc<String> {
-        Vc::cell("ModuleFeatureTelemetry_category_tbd".to_string())
+        Vc::cell(MODULE_FEATURE_TELEMETRY_CATEGORY.to_string())
     }
 
     #[turbo_tasks::function]
@@ -86,3 +91,30 @@ impl Diagnostic for ModuleFeatureTelemetry {
         )]))
     }
 }
+
+/// Sums up the invocation counts of the [ModuleFeatureTelemetry] diagnostics of
+/// a build by feature, like webpack's `TelemetryPlugin` reports them. Features
+/// which are never imported are included with a count of 0.
+pub fn module_feature_usage<'a>(
+    diagnostics: impl IntoIterator<Item = &'a PlainDiagnostic>,
+) -> IndexMap<String, usize> {
+    let mut usage: IndexMap<String, usize> = FEATURE_MODULES
+        .iter()
+        .flat_map(|(module, sub_paths)| {
+            sub_paths
+                .iter()
+                .map(move |sub_path| (format!("{module}{sub_path}"), 0))
+        })
+        .collect();
+    for diagnostic in diagnostics {
+        if diagnostic.category != MODULE_FEATURE_TELEMETRY_CATEGORY {
+            continue;
+        }
+        for (feature_name, invocation_count) in &diagnostic.payload {
+            *usage.entry(feature_name.clone()).or_default() +=
+                invocation_count.parse::<usize>().unwrap_or_default();
+        }
+    }
+    usage.sort_keys();
+    usage
+}
@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde_json::json;
+use turbo_tasks::{
+    graph::{GraphTraversal, NonDeterministic},
+    TryFlatJoinIterExt, Value, Vc,
+};
+use turbo_tasks_fs::{File, FileSystemPath};
+use turbopack_binding::turbopack::{
+    core::{
+        asset::{Asset, AssetContent},
+        compile_time_info::CompileTimeInfo,
+        context::AssetContext,
+        ident::AssetIdent,
+        module::Module,
+        output::OutputAsset,
+        reference::primary_referenced_modules,
+        reference_type::ReferenceType,
+        resolve::{
+            find_context_file, node::node_cjs_resolve_options, package_json, parse::Request,
+            pattern::Pattern, resolve, FindContextFileResult, ModuleResolveResultItem,
+        },
+    },
+    turbopack::{
+        module_options::ModuleOptionsContext, resolve_options_context::ResolveOptionsContext,
+        ModuleAssetContext,
+    },
+};
+
+use crate::emit::all_assets_from_entries;
+
+/// A `.nft.json` trace of a server entry chunk, in the format produced by
+/// `@vercel/nft`. It lists all emitted files the chunk needs at runtime, and
+/// the files in `node_modules` its externals require, so they can be copied
+/// into the standalone output.
+#[turbo_tasks::value(shared)]
+pub struct NftJsonAsset {
+    /// The chunk for which the trace is generated.
+    entry: Vc<Box<dyn OutputAsset>>,
+    /// The module the chunk was generated from, whose externals are traced.
+    entry_module: Vc<Box<dyn Module>>,
+    /// The project directory. It's the root of the output filesystem, which
+    /// the paths of the traced files are made relative to.
+    project_path: Vc<FileSystemPath>,
+    /// Only emitted files inside this directory are part of the trace.
+    output_root: Vc<FileSystemPath>,
+    compile_time_info: Vc<CompileTimeInfo>,
+}
+
+#[turbo_tasks::value_impl]
+impl NftJsonAsset {
+    #[turbo_tasks::function]
+    pub fn new(
+        entry: Vc<Box<dyn OutputAsset>>,
+        entry_module: Vc<Box<dyn Module>>,
+        project_path: Vc<FileSystemPath>,
+        output_root: Vc<FileSystemPath>,
+        compile_time_info: Vc<CompileTimeInfo>,
+    ) -> Vc<Self> {
+        NftJsonAsset {
+            entry,
+            entry_module,
+            project_path,
+            output_root,
+            compile_time_info,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for NftJsonAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        AssetIdent::from_path(self.entry.ident().path().append(".nft.json".to_string()))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for NftJsonAsset {
+    #[turbo_tasks::function]
+    async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        let this = &*self.await?;
+        let entry_path = this.entry.ident().path().await?;
+        let trace_dir = self.ident().path().parent().await?;
+        let output_root = this.output_root.await?;
+
+        let all_assets = all_assets_from_entries(Vc::cell(vec![this.entry])).await?;
+        let mut files: Vec<String> = all_assets
+            .iter()
+            .map(|&asset| {
+                let entry_path = &entry_path;
+                let trace_dir = &trace_dir;
+                let output_root = &output_root;
+                async move {
+                    let path = asset.ident().path().await?;
+                    if *path == **entry_path || output_root.get_path_to(&path).is_none() {
+                        return Ok(None);
+                    }
+                    Ok(trace_dir
+                        .get_relative_path_to(&path)
+                        .map(|path| path.trim_start_matches("./").to_string()))
+                }
+            })
+            .try_flat_join()
+            .await?;
+
+        // The traced files live in the project filesystem, whose root is the
+        // workspace root, so their paths are relative to that and need to go
+        // up to it from the trace.
+        let project_path = this.project_path.await?;
+        let up = "../".repeat(
+            trace_dir.path.split('/').filter(|s| !s.is_empty()).count()
+                + project_path
+                    .path
+                    .split('/')
+                    .filter(|s| !s.is_empty())
+                    .count(),
+        );
+        let traced_files =
+            traced_external_files(this.entry_module, this.project_path, this.compile_time_info)
+                .await?;
+        files.extend(traced_files.iter().map(|path| format!("{up}{path}")));
+
+        files.sort();
+        files.dedup();
+
+        let json = json!({
+            "version": 1,
+            "files": files,
+        });
+
+        Ok(AssetContent::file(File::from(json.to_string()).into()))
+    }
+}
+
+async fn get_referenced_modules(
+    parent: Vc<Box<dyn Module>>,
+) -> Result<impl Iterator<Item = Vc<Box<dyn Module>>> + Send> {
+    primary_referenced_modules(parent)
+        .await
+        .map(|modules| modules.clone_value().into_iter())
+}
+
+/// Returns the requests of all externals referenced from the module graph of
+/// `entry`.
+#[turbo_tasks::function]
+async fn external_requests(entry: Vc<Box<dyn Module>>) -> Result<Vc<Vec<String>>> {
+    let modules = NonDeterministic::new()
+        .skip_duplicates()
+        .visit([entry], get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner();
+
+    let mut requests = HashSet::new();
+    for module in modules {
+        for reference in module.references().await?.iter() {
+            for item in reference.resolve_reference().await?.primary.iter() {
+                if let ModuleResolveResultItem::OriginalReferenceTypeExternal(request) = item {
+                    requests.insert(request.clone());
+                }
+            }
+        }
+    }
+
+    let mut requests: Vec<_> = requests.into_iter().collect();
+    requests.sort();
+    Ok(Vc::cell(requests))
+}
+
+/// The context the files required by externals are traced in. Like
+/// `@vercel/nft`, it follows all requires in `node_modules`, and only leaves
+/// out the Node.js builtins.
+#[turbo_tasks::function]
+async fn trace_context(
+    project_path: Vc<FileSystemPath>,
+    compile_time_info: Vc<CompileTimeInfo>,
+) -> Result<Vc<Box<dyn AssetContext>>> {
+    Ok(Vc::upcast(ModuleAssetContext::new(
+        Vc::cell(Default::default()),
+        compile_time_info,
+        ModuleOptionsContext::default().cell(),
+        ResolveOptionsContext {
+            enable_node_modules: Some(project_path.root().resolve().await?),
+            enable_node_externals: true,
+            enable_node_native_modules: true,
+            custom_conditions: vec!["node".to_string()],
+            ..Default::default()
+        }
+        .cell(),
+        Vc::cell("trace".to_string()),
+    )))
+}
+
+/// Returns the paths, in the project filesystem, of the files the externals of
+/// `entry` require at runtime, including the `package.json` files of their
+/// packages, which Node.js reads to resolve them.
+#[turbo_tasks::function]
+async fn traced_external_files(
+    entry: Vc<Box<dyn Module>>,
+    project_path: Vc<FileSystemPath>,
+    compile_time_info: Vc<CompileTimeInfo>,
+) -> Result<Vc<Vec<String>>> {
+    let context = trace_context(project_path, compile_time_info);
+    let externals = external_requests(entry)
+        .await?
+        .iter()
+        .map(|request| async move {
+            let request = Request::parse(Value::new(Pattern::Constant(request.clone())));
+            let result = resolve(
+                project_path,
+                request,
+                node_cjs_resolve_options(project_path.root()),
+            );
+            Ok(result
+                .primary_sources()
+                .await?
+                .iter()
+                .map(|&source| context.process(source, Value::new(ReferenceType::Undefined)))
+                .collect::<Vec<_>>())
+        })
+        .try_flat_join()
+        .await?;
+
+    let modules = NonDeterministic::new()
+        .skip_duplicates()
+        .visit(externals, get_referenced_modules)
+        .await
+        .completed()?
+        .into_inner();
+
+    let paths = modules
+        .into_iter()
+        .map(|module| async move {
+            let path = module.ident().path();
+            let mut paths = vec![path.await?.path.clone()];
+            if let FindContextFileResult::Found(package_json, _) =
+                *find_context_file(path.parent(), package_json()).await?
+            {
+                paths.push(package_json.await?.path.clone());
+            }
+            Ok(paths)
+        })
+        .try_flat_join()
+        .await?;
+
+    let mut paths: Vec<_> = paths
+        .into_iter()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    paths.sort();
+    Ok(Vc::cell(paths))
+}
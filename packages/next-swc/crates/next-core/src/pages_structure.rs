@@ -376,13 +376,15 @@ async fn get_pages_structure_for_directory(
     .cell())
 }
 
+/// Strips the page extension from `name`. Extensions can contain dots, like
+/// `page.tsx`, so the longest one matching wins, like the regex of webpack
+/// matching the earliest `.` does.
 fn page_basename<'a>(name: &'a str, page_extensions: &'a [String]) -> Option<&'a str> {
-    if let Some((basename, extension)) = name.rsplit_once('.') {
-        if page_extensions.iter().any(|allowed| allowed == extension) {
-            return Some(basename);
-        }
-    }
-    None
+    page_extensions
+        .iter()
+        .filter_map(|extension| name.strip_suffix(extension.as_str())?.strip_suffix('.'))
+        .filter(|basename| !basename.is_empty())
+        .min_by_key(|basename| basename.len())
 }
 
 fn next_router_path_for_basename(
@@ -0,0 +1,311 @@
+//! Detection of routes the router can't tell apart, so that they can be
+//! reported with both paths instead of one of them being picked arbitrarily.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteCollisionKind {
+    /// `/[id]` and `/[slug]`.
+    DifferentSlugNames,
+    /// `/[...slug]` and `/[[...slug]]`.
+    RequiredAndOptionalCatchAll,
+    /// `/docs` and `/docs/[[...slug]]`.
+    SameSpecificityAsOptionalCatchAll,
+    /// `/feed/(..)photo` and `/feed/(...)photo`, which intercept the same
+    /// route from the same segment.
+    AmbiguousInterception,
+}
+
+impl RouteCollisionKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            RouteCollisionKind::DifferentSlugNames => {
+                "You cannot use different slug names for the same dynamic path."
+            }
+            RouteCollisionKind::RequiredAndOptionalCatchAll => {
+                "You cannot use both a required and optional catch-all route at the same level."
+            }
+            RouteCollisionKind::SameSpecificityAsOptionalCatchAll => {
+                "You cannot define a route with the same specificity as an optional catch-all \
+                 route."
+            }
+            RouteCollisionKind::AmbiguousInterception => {
+                "You cannot intercept the same route more than once from the same segment."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteCollision {
+    pub kind: RouteCollisionKind,
+    pub first: String,
+    pub second: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    Static(&'a str),
+    Dynamic(&'a str),
+    CatchAll(&'a str),
+    OptionalCatchAll(&'a str),
+}
+
+impl<'a> Segment<'a> {
+    fn parse(segment: &'a str) -> Self {
+        if let Some(name) = segment
+            .strip_prefix("[[...")
+            .and_then(|s| s.strip_suffix("]]"))
+        {
+            Segment::OptionalCatchAll(name)
+        } else if let Some(name) = segment
+            .strip_prefix("[...")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            Segment::CatchAll(name)
+        } else if let Some(name) = segment.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            Segment::Dynamic(name)
+        } else {
+            Segment::Static(segment)
+        }
+    }
+
+    /// The segment with its slug name erased, as seen by the router.
+    fn shape(&self) -> &'a str {
+        match *self {
+            Segment::Static(segment) => segment,
+            Segment::Dynamic(_) => "[]",
+            Segment::CatchAll(_) => "[...]",
+            Segment::OptionalCatchAll(_) => "[[...]]",
+        }
+    }
+
+    fn slug_name(&self) -> Option<&'a str> {
+        match *self {
+            Segment::Static(_) => None,
+            Segment::Dynamic(name) | Segment::CatchAll(name) | Segment::OptionalCatchAll(name) => {
+                Some(name)
+            }
+        }
+    }
+}
+
+fn shape(segments: &[Segment]) -> String {
+    let mut shape = String::new();
+    for segment in segments {
+        shape.push('/');
+        shape.push_str(segment.shape());
+    }
+    shape
+}
+
+/// Splits an interception marker like `(..)(..)` from a segment, returning the
+/// number of levels it goes up (`None` for the root) and the intercepted
+/// segment.
+fn parse_interception(segment: &str) -> Option<(Option<usize>, &str)> {
+    if let Some(rest) = segment.strip_prefix("(...)") {
+        return Some((None, rest));
+    }
+    if let Some(rest) = segment.strip_prefix("(.)") {
+        return Some((Some(0), rest));
+    }
+    let mut levels = 0;
+    let mut rest = segment;
+    while let Some(next) = rest.strip_prefix("(..)") {
+        levels += 1;
+        rest = next;
+    }
+    (levels > 0).then_some((Some(levels), rest))
+}
+
+/// Returns all pairs of routes in `pathnames` the router can't tell apart.
+pub fn find_route_collisions<'a>(
+    pathnames: impl IntoIterator<Item = &'a str>,
+) -> Vec<RouteCollision> {
+    let routes = pathnames
+        .into_iter()
+        .map(|pathname| {
+            let segments = pathname
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(Segment::parse)
+                .collect::<Vec<_>>();
+            (pathname, segments)
+        })
+        .collect::<Vec<_>>();
+
+    let mut collisions = vec![];
+    let mut collide = |kind, first: &str, second: &str| {
+        let collision = RouteCollision {
+            kind,
+            first: first.to_string(),
+            second: second.to_string(),
+        };
+        if !collisions.contains(&collision) {
+            collisions.push(collision);
+        }
+    };
+
+    let mut shapes = HashMap::new();
+    for (pathname, segments) in &routes {
+        shapes.entry(shape(segments)).or_insert(*pathname);
+    }
+
+    let mut slugs: HashMap<(String, &str), (&str, &str)> = HashMap::new();
+    let mut catch_alls: HashMap<String, (Segment, &str)> = HashMap::new();
+    let mut interceptions: HashMap<(String, String), &str> = HashMap::new();
+
+    for (pathname, segments) in &routes {
+        for (i, segment) in segments.iter().enumerate() {
+            let prefix = shape(&segments[..i]);
+
+            if let Some(slug_name) = segment.slug_name() {
+                match slugs.entry((prefix.clone(), segment.shape())) {
+                    Entry::Occupied(e) => {
+                        let (existing_slug_name, existing) = *e.get();
+                        if existing_slug_name != slug_name {
+                            collide(RouteCollisionKind::DifferentSlugNames, existing, pathname);
+                        }
+                    }
+                    Entry::Vacant(e) => {
+                        e.insert((slug_name, *pathname));
+                    }
+                }
+            }
+
+            if matches!(segment, Segment::CatchAll(_) | Segment::OptionalCatchAll(_)) {
+                match catch_alls.entry(prefix.clone()) {
+                    Entry::Occupied(e) => {
+                        let (existing_segment, existing) = *e.get();
+                        if existing_segment.shape() != segment.shape() {
+                            collide(
+                                RouteCollisionKind::RequiredAndOptionalCatchAll,
+                                existing,
+                                pathname,
+                            );
+                        }
+                    }
+                    Entry::Vacant(e) => {
+                        e.insert((*segment, *pathname));
+                    }
+                }
+            }
+
+            if let (Segment::OptionalCatchAll(_), Some(existing)) = (segment, shapes.get(&prefix)) {
+                collide(
+                    RouteCollisionKind::SameSpecificityAsOptionalCatchAll,
+                    existing,
+                    pathname,
+                );
+            }
+
+            if let Segment::Static(segment) = segment {
+                if let Some((levels, intercepted)) = parse_interception(segment) {
+                    let target_prefix = match levels {
+                        Some(levels) => &segments[..i.saturating_sub(levels)],
+                        None => &[],
+                    };
+                    let target = format!(
+                        "{}/{}{}",
+                        shape(target_prefix),
+                        intercepted,
+                        shape(&segments[i + 1..])
+                    );
+                    match interceptions.entry((prefix.clone(), target)) {
+                        Entry::Occupied(e) => {
+                            collide(RouteCollisionKind::AmbiguousInterception, e.get(), pathname);
+                        }
+                        Entry::Vacant(e) => {
+                            e.insert(*pathname);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_route_collisions, RouteCollision, RouteCollisionKind};
+
+    fn collisions(pathnames: &[&str]) -> Vec<RouteCollision> {
+        find_route_collisions(pathnames.iter().copied())
+    }
+
+    fn collision(kind: RouteCollisionKind, first: &str, second: &str) -> RouteCollision {
+        RouteCollision {
+            kind,
+            first: first.to_string(),
+            second: second.to_string(),
+        }
+    }
+
+    #[test]
+    fn allows_distinct_routes() {
+        assert_eq!(
+            collisions(&[
+                "/",
+                "/blog",
+                "/blog/[slug]",
+                "/blog/[slug]/comments",
+                "/docs/[...path]",
+                "/docs",
+                "/shop/[[...path]]",
+                "/feed/(..)photo/[id]",
+                "/photo/[id]",
+            ]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn reports_different_slug_names() {
+        assert_eq!(
+            collisions(&["/posts/[id]", "/posts/[slug]/edit"]),
+            vec![collision(
+                RouteCollisionKind::DifferentSlugNames,
+                "/posts/[id]",
+                "/posts/[slug]/edit"
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_required_and_optional_catch_all() {
+        assert_eq!(
+            collisions(&["/docs/[...slug]", "/docs/[[...slug]]"]),
+            vec![collision(
+                RouteCollisionKind::RequiredAndOptionalCatchAll,
+                "/docs/[...slug]",
+                "/docs/[[...slug]]"
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_same_specificity_as_optional_catch_all() {
+        assert_eq!(
+            collisions(&["/docs", "/docs/[[...slug]]"]),
+            vec![collision(
+                RouteCollisionKind::SameSpecificityAsOptionalCatchAll,
+                "/docs",
+                "/docs/[[...slug]]"
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_ambiguous_interception() {
+        assert_eq!(
+            collisions(&["/feed/(..)photo/[id]", "/feed/(...)photo/[id]"]),
+            vec![collision(
+                RouteCollisionKind::AmbiguousInterception,
+                "/feed/(..)photo/[id]",
+                "/feed/(...)photo/[id]"
+            )]
+        );
+    }
+}
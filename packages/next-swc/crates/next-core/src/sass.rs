@@ -15,6 +15,26 @@ pub async fn maybe_add_sass_loader(
     let Some(sass_options) = sass_options.as_object() else {
         bail!("sass_options must be an object");
     };
+    // Like in webpack builds, `prependData` and `additionalData` are options of
+    // sass-loader rather than of Sass itself.
+    let mut sass_options = sass_options.clone();
+    let prepend_data = sass_options.remove("prependData");
+    let additional_data = sass_options.remove("additionalData");
+    let additional_data = prepend_data
+        .filter(is_truthy)
+        .or(additional_data.filter(is_truthy));
+    // The optional `fibers` package isn't needed with Node.js 16+.
+    sass_options
+        .entry("fibers")
+        .or_insert(JsonValue::Bool(false));
+    let mut loader_options = serde_json::Map::new();
+    //https://github.com/vercel/turbo/blob/d527eb54be384a4658243304cecd547d09c05c6b/crates/turbopack-node/src/transforms/webpack.rs#L191
+    loader_options.insert("sourceMap".to_string(), JsonValue::Bool(false));
+    loader_options.insert("sassOptions".to_string(), JsonValue::Object(sass_options));
+    if let Some(additional_data) = additional_data {
+        loader_options.insert("additionalData".to_string(), additional_data);
+    }
+
     let mut rules = if let Some(webpack_rules) = webpack_rules {
         webpack_rules.await?.clone_value()
     } else {
@@ -27,16 +47,13 @@ pub async fn maybe_add_sass_loader(
         ("*.sass", ".css"),
     ] {
         let rule = rules.get_mut(pattern);
+        // TODO: sass-loader reports the partials a file `@use`s, `@forward`s or
+        // `@import`s with `this.addDependency`, which Turbopack's loader runner
+        // doesn't track. So in watch mode, editing a partial doesn't recompile the
+        // files using it until they change themselves.
         let loader = WebpackLoaderItem {
             loader: "next/dist/compiled/sass-loader".to_string(),
-            options: serde_json::json!({
-                //https://github.com/vercel/turbo/blob/d527eb54be384a4658243304cecd547d09c05c6b/crates/turbopack-node/src/transforms/webpack.rs#L191
-                "sourceMap": false,
-                "sassOptions": sass_options,
-            })
-            .as_object()
-            .unwrap()
-            .clone(),
+            options: loader_options.clone(),
         };
 
         if let Some(rule) = rule {
@@ -66,3 +83,15 @@ pub async fn maybe_add_sass_loader(
 
     Ok(Vc::cell(Some(Vc::cell(rules))))
 }
+
+/// Whether `value` is truthy in JavaScript, as the options are checked with
+/// `||` in the webpack config.
+fn is_truthy(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(b) => *b,
+        JsonValue::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        JsonValue::String(s) => !s.is_empty(),
+        JsonValue::Array(_) | JsonValue::Object(_) => true,
+    }
+}
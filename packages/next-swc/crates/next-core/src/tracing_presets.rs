@@ -8,6 +8,7 @@ pub static TRACING_NEXT_TARGETS: Lazy<Vec<&str>> = Lazy::new(|| {
         &TRACING_OVERVIEW_TARGETS[..],
         &[
             "next_dev=trace",
+            "next_api=trace",
             "next_core=trace",
             "next_font=trace",
             "turbopack_node=trace",
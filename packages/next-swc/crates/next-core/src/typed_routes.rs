@@ -0,0 +1,262 @@
+//! The route type definitions of `experimental.typedRoutes`, like the ones the
+//! `NextTypesPlugin` of webpack writes to `.next/types/link.d.ts`.
+
+use crate::{
+    next_config::{Redirect, Rewrites},
+    url_node::is_dynamic_route,
+};
+
+/// Returns whether `route` is dynamic, and its member of the `StaticRoutes` or
+/// `DynamicRoutes` union type.
+fn format_route_to_route_type(route: &str) -> (bool, String) {
+    let is_dynamic = is_dynamic_route(route);
+    let route = if is_dynamic {
+        route
+            .split('/')
+            .map(|part| {
+                if !part.starts_with('[') || !part.ends_with(']') {
+                    part
+                } else if part.starts_with("[...") {
+                    "${CatchAllSlug<T>}"
+                } else if part.starts_with("[[...") && part.ends_with("]]") {
+                    "${OptionalCatchAllSlug<T>}"
+                } else {
+                    "${SafeSlug<T>}"
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    } else {
+        route.to_string()
+    };
+    (is_dynamic, format!("\n    | `{route}`"))
+}
+
+/// Converts the `source` of a rewrite or redirect to a route, with its named
+/// parameters as dynamic segments. Sources using other patterns can't be
+/// expressed as a route type and return `None`.
+fn source_to_route(source: &str) -> Option<String> {
+    let mut route = String::new();
+    for segment in source.split('/').skip(1) {
+        route.push('/');
+        let Some(param) = segment.strip_prefix(':') else {
+            if segment.contains(['(', ')', '{', '}', '*', '+', '?', ':']) {
+                return None;
+            }
+            route.push_str(segment);
+            continue;
+        };
+        let name_end = param
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(param.len());
+        let (name, modifier) = param.split_at(name_end);
+        if name.is_empty() {
+            return None;
+        }
+        match modifier {
+            "" => route.push_str(&format!("[{name}]")),
+            "*" | "(.*)" => route.push_str(&format!("[[...{name}]]")),
+            "+" | "(.+)" => route.push_str(&format!("[...{name}]")),
+            _ => return None,
+        }
+    }
+    Some(route)
+}
+
+/// Returns the contents of `link.d.ts` for the given routes and custom routes.
+/// The sources of the rewrites and redirects are possible routes too.
+pub fn route_type_definitions<'a>(
+    routes: impl IntoIterator<Item = &'a str>,
+    rewrites: &Rewrites,
+    redirects: &[Redirect],
+) -> String {
+    let sources = rewrites
+        .before_files
+        .iter()
+        .chain(&rewrites.after_files)
+        .chain(&rewrites.fallback)
+        .map(|rewrite| rewrite.source.as_str())
+        .chain(
+            redirects
+                .iter()
+                .filter(|redirect| redirect.internal.is_none())
+                .map(|redirect| redirect.source.as_str()),
+        )
+        .filter_map(source_to_route);
+
+    let mut static_route_types = String::new();
+    let mut dynamic_route_types = String::new();
+    for route in routes.into_iter().map(str::to_string).chain(sources) {
+        let (is_dynamic, route_type) = format_route_to_route_type(&route);
+        if is_dynamic {
+            dynamic_route_types.push_str(&route_type);
+        } else {
+            static_route_types.push_str(&route_type);
+        }
+    }
+
+    // If both StaticRoutes and DynamicRoutes are empty, fallback to type
+    // 'string'.
+    let route_impl = if static_route_types.is_empty() && dynamic_route_types.is_empty() {
+        "string"
+    } else {
+        ROUTE_IMPL
+    };
+    let or_never = |types: String| {
+        if types.is_empty() {
+            "never".to_string()
+        } else {
+            types
+        }
+    };
+
+    LINK_TYPES
+        .replace("__STATIC_ROUTES__", &or_never(static_route_types))
+        .replace("__DYNAMIC_ROUTES__", &or_never(dynamic_route_types))
+        .replace("__ROUTE_IMPL__", route_impl)
+}
+
+const ROUTE_IMPL: &str = r#"
+    | StaticRoutes
+    | SearchOrHash
+    | WithProtocol
+    | `${StaticRoutes}${SearchOrHash}`
+    | (T extends `${DynamicRoutes<infer _>}${Suffix}` ? T : never)
+    "#;
+
+const LINK_TYPES: &str = r#"// Type definitions for Next.js routes
+
+/**
+ * Internal types used by the Next.js router and Link component.
+ * These types are not meant to be used directly.
+ * @internal
+ */
+declare namespace __next_route_internal_types__ {
+  type SearchOrHash = `?${string}` | `#${string}`
+  type WithProtocol = `${string}:${string}`
+
+  type Suffix = '' | SearchOrHash
+
+  type SafeSlug<S extends string> = S extends `${string}/${string}`
+    ? never
+    : S extends `${string}${SearchOrHash}`
+    ? never
+    : S extends ''
+    ? never
+    : S
+
+  type CatchAllSlug<S extends string> = S extends `${string}${SearchOrHash}`
+    ? never
+    : S extends ''
+    ? never
+    : S
+
+  type OptionalCatchAllSlug<S extends string> =
+    S extends `${string}${SearchOrHash}` ? never : S
+
+  type StaticRoutes = __STATIC_ROUTES__
+  type DynamicRoutes<T extends string = string> = __DYNAMIC_ROUTES__
+
+  type RouteImpl<T> = __ROUTE_IMPL__
+}
+
+declare module 'next' {
+  export { default } from 'next/types/index.js'
+  export * from 'next/types/index.js'
+
+  export type Route<T extends string = string> =
+    __next_route_internal_types__.RouteImpl<T>
+}
+
+declare module 'next/link' {
+  import type { LinkProps as OriginalLinkProps } from 'next/dist/client/link.js'
+  import type { AnchorHTMLAttributes, DetailedHTMLProps } from 'react'
+  import type { UrlObject } from 'url'
+
+  type LinkRestProps = Omit<
+    Omit<
+      DetailedHTMLProps<
+        AnchorHTMLAttributes<HTMLAnchorElement>,
+        HTMLAnchorElement
+      >,
+      keyof OriginalLinkProps
+    > &
+      OriginalLinkProps,
+    'href'
+  >
+
+  export type LinkProps<RouteInferType> = LinkRestProps & {
+    /**
+     * The path or URL to navigate to. This is the only required prop. It can also be an object.
+     * @see https://nextjs.org/docs/api-reference/next/link
+     */
+    href: __next_route_internal_types__.RouteImpl<RouteInferType> | UrlObject
+  }
+
+  export default function Link<RouteType>(props: LinkProps<RouteType>): JSX.Element
+}
+
+declare module 'next/navigation' {
+  export * from 'next/dist/client/components/navigation.js'
+
+  import type { NavigateOptions, AppRouterInstance as OriginalAppRouterInstance } from 'next/dist/shared/lib/app-router-context.shared-runtime.js'
+  interface AppRouterInstance extends OriginalAppRouterInstance {
+    /**
+     * Navigate to the provided href.
+     * Pushes a new history entry.
+     */
+    push<RouteType>(href: __next_route_internal_types__.RouteImpl<RouteType>, options?: NavigateOptions): void
+    /**
+     * Navigate to the provided href.
+     * Replaces the current history entry.
+     */
+    replace<RouteType>(href: __next_route_internal_types__.RouteImpl<RouteType>, options?: NavigateOptions): void
+    /**
+     * Prefetch the provided href.
+     */
+    prefetch<RouteType>(href: __next_route_internal_types__.RouteImpl<RouteType>): void
+  }
+
+  export declare function useRouter(): AppRouterInstance;
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::{format_route_to_route_type, source_to_route};
+
+    #[test]
+    fn formats_route_types() {
+        assert_eq!(
+            format_route_to_route_type("/about"),
+            (false, "\n    | `/about`".to_string())
+        );
+        assert_eq!(
+            format_route_to_route_type("/blog/[slug]/[...rest]/[[...optional]]"),
+            (
+                true,
+                "\n    | `/blog/${SafeSlug<T>}/${CatchAllSlug<T>}/${OptionalCatchAllSlug<T>}`"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn converts_sources_to_routes() {
+        assert_eq!(source_to_route("/"), Some("/".to_string()));
+        assert_eq!(
+            source_to_route("/blog/:slug"),
+            Some("/blog/[slug]".to_string())
+        );
+        assert_eq!(
+            source_to_route("/docs/:path*"),
+            Some("/docs/[[...path]]".to_string())
+        );
+        assert_eq!(
+            source_to_route("/docs/:path+"),
+            Some("/docs/[...path]".to_string())
+        );
+        assert_eq!(source_to_route("/post/:id(\\d+)"), None);
+        assert_eq!(source_to_route("/:locale?/about"), None);
+    }
+}
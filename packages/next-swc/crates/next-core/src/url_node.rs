@@ -334,6 +334,13 @@ impl UrlNode {
     }
 }
 
+/// Whether `route` has a dynamic segment, like `isDynamicRoute`.
+pub fn is_dynamic_route(route: &str) -> bool {
+    route
+        .split('/')
+        .any(|segment| segment.starts_with('[') && segment.ends_with(']'))
+}
+
 pub fn get_sorted_routes(normalized_pages: &[String]) -> Result<Vec<String>, UrlNodeError> {
     // First the UrlNode is created, and every UrlNode can have only 1 dynamic
     // segment Eg you can't have pages/[post]/abc.js and
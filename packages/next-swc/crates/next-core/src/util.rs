@@ -6,7 +6,10 @@ use swc_core::ecma::ast::Program;
 use turbo_tasks::{trace::TraceRawVcs, TaskInput, ValueDefault, ValueToString, Vc};
 use turbo_tasks_fs::{rope::Rope, util::join_path, File};
 use turbopack_binding::{
-    turbo::tasks_fs::{json::parse_json_rope_with_source_context, FileContent, FileSystemPath},
+    turbo::{
+        tasks_fs::{json::parse_json_rope_with_source_context, FileContent, FileSystemPath},
+        tasks_hash::{DeterministicHasher, Xxh3Hash64Hasher},
+    },
     turbopack::{
         core::{
             asset::AssetContent,
@@ -18,7 +21,7 @@ use turbopack_binding::{
             virtual_source::VirtualSource,
         },
         ecmascript::{
-            analyzer::{JsValue, ObjectPart},
+            analyzer::{ConstantValue, JsValue, ObjectPart},
             parse::ParseResult,
             utils::StringifyJs,
             EcmascriptModuleAsset,
@@ -28,7 +31,7 @@ use turbopack_binding::{
 };
 
 use crate::{
-    next_config::{NextConfig, OutputType},
+    next_config::{NextConfig, OutputType, RouteHas},
     next_import_map::get_next_package,
 };
 
@@ -144,13 +147,44 @@ pub enum NextRuntime {
     Edge,
 }
 
+/// The `amp` property of the config export of a page.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, TraceRawVcs, Serialize, Deserialize)]
+pub enum NextSourceAmpConfig {
+    #[default]
+    Disabled,
+    /// `amp: 'hybrid'`, the page is rendered as both HTML and AMP.
+    Hybrid,
+    /// `amp: true`, the page is only rendered as AMP.
+    AmpOnly,
+}
+
+/// A matcher of the config export of middleware, either a source or an object
+/// with the source and its conditions.
+#[derive(Default, PartialEq, Eq, Clone, Debug, TraceRawVcs, Serialize, Deserialize)]
+pub struct MiddlewareMatcherConfig {
+    pub source: String,
+    /// Whether the source is prefixed with the locales, unless `locale: false`.
+    pub locale: bool,
+    pub has: Option<Vec<RouteHas>>,
+    pub missing: Option<Vec<RouteHas>>,
+}
+
 #[turbo_tasks::value]
 #[derive(Default, Clone)]
 pub struct NextSourceConfig {
     pub runtime: NextRuntime,
 
     /// Middleware router matchers
-    pub matcher: Option<Vec<String>>,
+    pub matcher: Option<Vec<MiddlewareMatcherConfig>>,
+
+    /// Globs of modules allowed to evaluate code dynamically in the edge
+    /// runtime
+    pub unstable_allow_dynamic: Option<Vec<String>>,
+
+    /// Preferred regions of an edge function
+    pub regions: Option<Vec<String>>,
+
+    pub amp: NextSourceAmpConfig,
 }
 
 #[turbo_tasks::value_impl]
@@ -166,13 +200,14 @@ impl ValueDefault for NextSourceConfig {
 pub struct NextSourceConfigParsingIssue {
     ident: Vc<AssetIdent>,
     detail: Vc<String>,
+    severity: Vc<IssueSeverity>,
 }
 
 #[turbo_tasks::value_impl]
 impl Issue for NextSourceConfigParsingIssue {
     #[turbo_tasks::function]
     fn severity(&self) -> Vc<IssueSeverity> {
-        IssueSeverity::Warning.into()
+        self.severity
     }
 
     #[turbo_tasks::function]
@@ -216,6 +251,8 @@ pub async fn parse_config_from_source(module: Vc<Box<dyn Module>>) -> Result<Vc<
             ..
         } = &*ecmascript_asset.parse().await?
         {
+            let mut config = None;
+            let mut runtime = None;
             for item in &module_ast.body {
                 if let Some(decl) = item
                     .as_module_decl()
@@ -223,46 +260,205 @@ pub async fn parse_config_from_source(module: Vc<Box<dyn Module>>) -> Result<Vc<
                     .and_then(|export_decl| export_decl.decl.as_var())
                 {
                     for decl in &decl.decls {
-                        if decl
-                            .name
-                            .as_ident()
-                            .map(|ident| &*ident.sym == "config")
-                            .unwrap_or_default()
-                        {
-                            if let Some(init) = decl.init.as_ref() {
-                                let value = eval_context.eval(init);
-                                return Ok(parse_config_from_js_value(module, &value).cell());
-                            } else {
-                                NextSourceConfigParsingIssue {
-                                    ident: module.ident(),
-                                    detail: Vc::cell(
-                                        "The exported config object must contain an variable \
-                                         initializer."
-                                            .to_string(),
-                                    ),
+                        let Some(ident) = decl.name.as_ident() else {
+                            continue;
+                        };
+                        match &*ident.sym {
+                            "config" => {
+                                if let Some(init) = decl.init.as_ref() {
+                                    let value = eval_context.eval(init);
+                                    config = Some(parse_config_from_js_value(module, &value));
+                                } else {
+                                    NextSourceConfigParsingIssue {
+                                        ident: module.ident(),
+                                        detail: Vc::cell(
+                                            "The exported config object must contain an variable \
+                                             initializer."
+                                                .to_string(),
+                                        ),
+                                        severity: IssueSeverity::Warning.into(),
+                                    }
+                                    .cell()
+                                    .emit()
                                 }
-                                .cell()
-                                .emit()
                             }
+                            // Like `export const config = { runtime }`, but takes precedence.
+                            "runtime" => {
+                                if let Some(init) = decl.init.as_ref() {
+                                    let value = eval_context.eval(init);
+                                    runtime = parse_runtime_from_js_value(module, &value);
+                                }
+                            }
+                            _ => {}
                         }
                     }
                 }
             }
+
+            let mut config = config.unwrap_or_default();
+            if let Some(runtime) = runtime {
+                config.runtime = runtime;
+            }
+            return Ok(config.cell());
         }
     }
     Ok(Default::default())
 }
 
+fn emit_config_parsing_issue(
+    module: Vc<Box<dyn Module>>,
+    severity: IssueSeverity,
+    detail: &str,
+    value: &JsValue,
+) {
+    let (explainer, hints) = value.explain(2, 0);
+    NextSourceConfigParsingIssue {
+        ident: module.ident(),
+        detail: Vc::cell(format!("{detail} Got {explainer}.{hints}")),
+        severity: severity.into(),
+    }
+    .cell()
+    .emit()
+}
+
+/// Parses a runtime value. An unknown runtime would silently fall back to
+/// Node.js, so it's reported as an error.
+fn parse_runtime_from_js_value(
+    module: Vc<Box<dyn Module>>,
+    value: &JsValue,
+) -> Option<NextRuntime> {
+    match value.as_str() {
+        Some("edge" | "experimental-edge") => Some(NextRuntime::Edge),
+        Some("nodejs") => Some(NextRuntime::NodeJs),
+        Some(_) => {
+            emit_config_parsing_issue(
+                module,
+                IssueSeverity::Error,
+                "The runtime property must be either \"nodejs\" or \"edge\".",
+                value,
+            );
+            None
+        }
+        None => {
+            emit_config_parsing_issue(
+                module,
+                IssueSeverity::Error,
+                "The runtime property must be a constant string.",
+                value,
+            );
+            None
+        }
+    }
+}
+
+/// Collects a string or an array of strings, calling `invalid` for anything
+/// else.
+fn parse_string_or_array(value: &JsValue, invalid: impl Fn()) -> Vec<String> {
+    let mut strings = vec![];
+    match value {
+        JsValue::Constant(constant) => {
+            if let Some(string) = constant.as_str() {
+                strings.push(string.to_string());
+            } else {
+                invalid();
+            }
+        }
+        JsValue::Array { items, .. } => {
+            for item in items {
+                if let Some(string) = item.as_str() {
+                    strings.push(string.to_string());
+                } else {
+                    invalid();
+                }
+            }
+        }
+        _ => invalid(),
+    }
+    strings
+}
+
+/// Collects the middleware matchers of a string, an object with a `source` or
+/// an array of them, calling `invalid` for anything else.
+fn parse_matchers(value: &JsValue, invalid: impl Fn()) -> Vec<MiddlewareMatcherConfig> {
+    let items = match value {
+        JsValue::Array { items, .. } => &items[..],
+        _ => std::slice::from_ref(value),
+    };
+    let mut matchers = vec![];
+    for item in items {
+        if let Some(source) = item.as_str() {
+            matchers.push(MiddlewareMatcherConfig {
+                source: source.to_string(),
+                locale: true,
+                ..Default::default()
+            });
+        } else if let Some(matcher) = parse_matcher_object(item) {
+            matchers.push(matcher);
+        } else {
+            invalid();
+        }
+    }
+    matchers
+}
+
+/// Parses a `{ source, locale, has, missing }` matcher object.
+fn parse_matcher_object(value: &JsValue) -> Option<MiddlewareMatcherConfig> {
+    let JsValue::Object { parts, .. } = value else {
+        return None;
+    };
+    let mut source = None;
+    let mut matcher = MiddlewareMatcherConfig {
+        locale: true,
+        ..Default::default()
+    };
+    for part in parts {
+        let ObjectPart::KeyValue(key, value) = part else {
+            return None;
+        };
+        match key.as_str()? {
+            "source" => source = Some(value.as_str()?.to_string()),
+            "locale" => match value {
+                JsValue::Constant(ConstantValue::False) => matcher.locale = false,
+                JsValue::Constant(ConstantValue::Undefined) => {}
+                _ => return None,
+            },
+            "has" => matcher.has = Some(parse_route_has(value)?),
+            "missing" => matcher.missing = Some(parse_route_has(value)?),
+            _ => return None,
+        }
+    }
+    matcher.source = source?;
+    Some(matcher)
+}
+
+/// Parses the `has` or `missing` conditions of a matcher, which are objects of
+/// strings.
+fn parse_route_has(value: &JsValue) -> Option<Vec<RouteHas>> {
+    let JsValue::Array { items, .. } = value else {
+        return None;
+    };
+    items
+        .iter()
+        .map(|item| {
+            let JsValue::Object { parts, .. } = item else {
+                return None;
+            };
+            let mut object = serde_json::Map::new();
+            for part in parts {
+                let ObjectPart::KeyValue(key, value) = part else {
+                    return None;
+                };
+                object.insert(key.as_str()?.to_string(), value.as_str()?.into());
+            }
+            serde_json::from_value(JsonValue::Object(object)).ok()
+        })
+        .collect()
+}
+
 fn parse_config_from_js_value(module: Vc<Box<dyn Module>>, value: &JsValue) -> NextSourceConfig {
     let mut config = NextSourceConfig::default();
     let invalid_config = |detail: &str, value: &JsValue| {
-        let (explainer, hints) = value.explain(2, 0);
-        NextSourceConfigParsingIssue {
-            ident: module.ident(),
-            detail: Vc::cell(format!("{detail} Got {explainer}.{hints}")),
-        }
-        .cell()
-        .emit()
+        emit_config_parsing_issue(module, IssueSeverity::Warning, detail, value)
     };
     if let JsValue::Object { parts, .. } = value {
         for part in parts {
@@ -274,64 +470,57 @@ fn parse_config_from_js_value(module: Vc<Box<dyn Module>>, value: &JsValue) -> N
                 ObjectPart::KeyValue(key, value) => {
                     if let Some(key) = key.as_str() {
                         if key == "runtime" {
-                            if let JsValue::Constant(runtime) = value {
-                                if let Some(runtime) = runtime.as_str() {
-                                    match runtime {
-                                        "edge" | "experimental-edge" => {
-                                            config.runtime = NextRuntime::Edge;
-                                        }
-                                        "nodejs" => {
-                                            config.runtime = NextRuntime::NodeJs;
-                                        }
-                                        _ => {
-                                            invalid_config(
-                                                "The runtime property must be either \"nodejs\" \
-                                                 or \"edge\".",
-                                                value,
-                                            );
-                                        }
-                                    }
-                                }
-                            } else {
-                                invalid_config(
-                                    "The runtime property must be a constant string.",
-                                    value,
-                                );
+                            if let Some(runtime) = parse_runtime_from_js_value(module, value) {
+                                config.runtime = runtime;
                             }
                         }
                         if key == "matcher" {
-                            let mut matchers = vec![];
-                            match value {
-                                JsValue::Constant(matcher) => {
-                                    if let Some(matcher) = matcher.as_str() {
-                                        matchers.push(matcher.to_string());
-                                    } else {
-                                        invalid_config(
-                                            "The matcher property must be a string or array of \
-                                             strings",
-                                            value,
-                                        );
-                                    }
+                            config.matcher = Some(parse_matchers(value, || {
+                                invalid_config(
+                                    "The matcher property must be a string, an object with a \
+                                     source or an array of them.",
+                                    value,
+                                )
+                            }));
+                        }
+                        if key == "regions" {
+                            config.regions = Some(parse_string_or_array(value, || {
+                                invalid_config(
+                                    "The regions property must be a string or array of strings.",
+                                    value,
+                                )
+                            }))
+                            .filter(|regions| !regions.is_empty());
+                        }
+                        if key == "amp" {
+                            config.amp = match value {
+                                JsValue::Constant(ConstantValue::True) => {
+                                    NextSourceAmpConfig::AmpOnly
                                 }
-                                JsValue::Array { items, .. } => {
-                                    for item in items {
-                                        if let Some(matcher) = item.as_str() {
-                                            matchers.push(matcher.to_string());
-                                        } else {
-                                            invalid_config(
-                                                "The matcher property must be a string or array \
-                                                 of strings",
-                                                value,
-                                            );
-                                        }
-                                    }
+                                JsValue::Constant(ConstantValue::False) => {
+                                    NextSourceAmpConfig::Disabled
                                 }
-                                _ => invalid_config(
-                                    "The matcher property must be a string or array of strings",
-                                    value,
-                                ),
-                            }
-                            config.matcher = Some(matchers);
+                                _ if value.as_str() == Some("hybrid") => {
+                                    NextSourceAmpConfig::Hybrid
+                                }
+                                _ => {
+                                    invalid_config(
+                                        "The amp property must be true, false or \"hybrid\".",
+                                        value,
+                                    );
+                                    NextSourceAmpConfig::Disabled
+                                }
+                            };
+                        }
+                        if key == "unstable_allowDynamic" {
+                            config.unstable_allow_dynamic =
+                                Some(parse_string_or_array(value, || {
+                                    invalid_config(
+                                        "The unstable_allowDynamic property must be a string or \
+                                         array of strings",
+                                        value,
+                                    )
+                                }));
                         }
                     } else {
                         invalid_config(
@@ -619,6 +808,16 @@ pub async fn file_content_rope(content: Vc<FileContent>) -> Result<Vc<Rope>> {
     Ok(file.content().to_owned().cell())
 }
 
+/// Hashes the content of a file for its emitted name, with xxh3 as a fixed
+/// algorithm. Hashing a slice with `hash_xxh3_hash64` also hashes its length
+/// as a `usize`, which makes the hash depend on the pointer width, so e.g. the
+/// wasm bindings on CI would name files differently than a native build.
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = Xxh3Hash64Hasher::new();
+    hasher.write_bytes(bytes);
+    hasher.finish()
+}
+
 pub fn virtual_next_js_template_path(
     project_path: Vc<FileSystemPath>,
     file: String,
@@ -675,3 +874,15 @@ pub async fn render_data(
     })?;
     Ok(Vc::cell(value))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::content_hash;
+
+    #[test]
+    fn content_hash_is_xxh3() {
+        // The XXH3-64 hash of the empty input with the default seed, which
+        // emitted file names depend on.
+        assert_eq!(content_hash(b""), 0x2d06800538d394c2);
+    }
+}
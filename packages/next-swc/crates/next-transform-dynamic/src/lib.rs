@@ -325,7 +325,7 @@ impl Fold for NextDynamicPatcher {
                             value: generated,
                         })))];
 
-                    let mut has_ssr_false = false;
+                    let mut ssr_false_span = None;
 
                     if expr.args.len() == 2 {
                         if let Expr::Object(ObjectLit {
@@ -343,7 +343,7 @@ impl Fold for NextDynamicPatcher {
                                 } {
                                     if let Some(Ident {
                                         sym,
-                                        span: _,
+                                        span,
                                         optional: _,
                                     }) = match key {
                                         PropName::Ident(ident) => Some(ident),
@@ -355,7 +355,7 @@ impl Fold for NextDynamicPatcher {
                                                 span: _,
                                             })) = value.as_lit()
                                             {
-                                                has_ssr_false = true
+                                                ssr_false_span = Some(*span)
                                             }
                                         }
                                     }
@@ -365,10 +365,31 @@ impl Fold for NextDynamicPatcher {
                         }
                     }
 
+                    // Server Components are only rendered on the server, so they can't
+                    // opt out of server rendering. Webpack mode keeps accepting it, where
+                    // the component is rendered on the server regardless.
+                    if let Some(span) = ssr_false_span.filter(|_| {
+                        self.is_react_server_layer
+                            && matches!(self.state, NextDynamicPatcherState::Turbopack { .. })
+                    }) {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    span,
+                                    "`ssr: false` is not allowed with `next/dynamic` in Server \
+                                     Components. Please move it into a client component.",
+                                )
+                                .emit()
+                        });
+                    }
+
                     // Also don't strip the `loader` argument for server components (both
                     // server/client layers), since they're aliased to a
                     // React.lazy implementation.
-                    if has_ssr_false && self.is_server_compiler && !self.is_react_server_layer {
+                    if ssr_false_span.is_some()
+                        && self.is_server_compiler
+                        && !self.is_react_server_layer
+                    {
                         expr.args[0] = Lit::Null(Null { span: DUMMY_SP }).as_arg();
                     }
 
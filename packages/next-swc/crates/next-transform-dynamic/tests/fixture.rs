@@ -5,7 +5,7 @@ use swc_core::{
     common::FileName,
     ecma::{
         parser::{EsConfig, Syntax},
-        transforms::testing::{test, test_fixture},
+        transforms::testing::{test, test_fixture, FixtureTestConfig},
     },
 };
 use testing::fixture;
@@ -119,6 +119,10 @@ fn next_dynamic_fixture_run(
         },
         input,
         &output,
-        Default::default(),
+        FixtureTestConfig {
+            // `ssr: false` is reported in Server Components.
+            allow_error: is_react_server_layer,
+            ..Default::default()
+        },
     );
 }
@@ -1,13 +1,15 @@
-use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 use anyhow::{Context, Error};
+use fxhash::FxHashSet;
 use js_sys::JsString;
 use next_swc::{custom_before_pass, TransformOptions};
+use serde::Serialize;
 use swc_core::common::Mark;
 use turbopack_binding::swc::core::{
     base::{
         config::{JsMinifyOptions, ParseOptions},
-        try_with_handler, Compiler,
+        try_with_handler, Compiler, TransformOutput,
     },
     common::{
         comments::{Comments, SingleThreadedComments},
@@ -25,6 +27,33 @@ fn convert_err(err: Error) -> JsValue {
     format!("{:?}", err).into()
 }
 
+/// Output of `transform`, in the same shape as the one returned by the native
+/// bindings: `eliminatedPackages` is a JSON-serialized array and only present
+/// when there are eliminated packages.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WasmTransformOutput {
+    code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    map: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eliminated_packages: Option<String>,
+}
+
+impl WasmTransformOutput {
+    fn new(output: TransformOutput, eliminated_packages: FxHashSet<String>) -> Result<Self, Error> {
+        Ok(Self {
+            code: output.code,
+            map: output.map,
+            eliminated_packages: if eliminated_packages.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&eliminated_packages)?)
+            },
+        })
+    }
+}
+
 #[wasm_bindgen(js_name = "minifySync")]
 pub fn minify_sync(s: JsString, opts: JsValue) -> Result<JsValue, JsValue> {
     console_error_panic_hook::set_once();
@@ -68,6 +97,7 @@ pub fn transform_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
 
     let c = compiler();
     let mut opts: TransformOptions = serde_wasm_bindgen::from_value(opts)?;
+    let eliminated_packages: Rc<RefCell<FxHashSet<String>>> = Default::default();
 
     let s = s.dyn_into::<js_sys::JsString>();
     let out = try_with_handler(
@@ -91,6 +121,10 @@ pub fn transform_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
                             },
                             s.into(),
                         );
+                        // Keep in sync with the napi `transform`, so both bindings
+                        // produce identical output for the same input.
+                        let opts = opts.clone().patch(&fm);
+
                         let cm = c.cm.clone();
                         let file = fm.clone();
                         let comments = SingleThreadedComments::default();
@@ -106,7 +140,7 @@ pub fn transform_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
                                     file,
                                     &opts,
                                     comments.clone(),
-                                    Default::default(),
+                                    eliminated_packages.clone(),
                                     unresolved_mark,
                                 )
                             },
@@ -127,6 +161,9 @@ pub fn transform_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
     )
     .map_err(convert_err)?;
 
+    let out = WasmTransformOutput::new(out, eliminated_packages.replace(Default::default()))
+        .map_err(convert_err)?;
+
     Ok(serde_wasm_bindgen::to_value(&out)?)
 }
 
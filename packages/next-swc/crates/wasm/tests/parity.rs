@@ -0,0 +1,83 @@
+//! Runtime parity tests for the wasm bindings. These compile and run on a wasm
+//! runtime, e.g. `wasm-pack test --node packages/next-swc/crates/wasm`, and
+//! assert that the wasm fallback produces the same output as the native
+//! bindings for the same input.
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{JsString, Reflect, JSON};
+use wasm::{minify_sync, transform_sync};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn options(json: &str) -> JsValue {
+    JSON::parse(json).expect("options should be valid JSON")
+}
+
+fn get_string(value: &JsValue, key: &str) -> Option<String> {
+    Reflect::get(value, &JsValue::from_str(key))
+        .ok()
+        .and_then(|v| v.as_string())
+}
+
+#[wasm_bindgen_test]
+fn transform_strips_ssg_exports() {
+    let output = transform_sync(
+        JsValue::from_str(
+            r#"
+import fs from 'fs'
+export async function getStaticProps() {
+  return { props: { data: fs.readFileSync('data.txt', 'utf8') } }
+}
+export default function Page() { return null }
+"#,
+        ),
+        options(
+            r#"{
+                "filename": "/pages/index.js",
+                "isPageFile": true,
+                "isServerCompiler": false,
+                "jsc": { "parser": { "syntax": "ecmascript" }, "target": "es2022" }
+            }"#,
+        ),
+    )
+    .expect("transform should succeed");
+
+    let code = get_string(&output, "code").expect("output should have code");
+    assert!(!code.contains("getStaticProps"));
+    assert!(code.contains("__N_SSG"));
+    assert_eq!(
+        get_string(&output, "eliminatedPackages").as_deref(),
+        Some(r#"["fs"]"#)
+    );
+}
+
+#[wasm_bindgen_test]
+fn transform_enables_commonjs_for_cjs_input() {
+    let output = transform_sync(
+        JsValue::from_str("module.exports = function () { return import('./foo') }"),
+        options(
+            r#"{
+                "filename": "/lib/foo.js",
+                "jsc": { "parser": { "syntax": "ecmascript" }, "target": "es2022" }
+            }"#,
+        ),
+    )
+    .expect("transform should succeed");
+
+    let code = get_string(&output, "code").expect("output should have code");
+    assert!(code.contains("module.exports"));
+    assert!(code.contains("import('./foo')") || code.contains(r#"import("./foo")"#));
+}
+
+#[wasm_bindgen_test]
+fn minify_output() {
+    let output = minify_sync(
+        JsString::from("function foo(bar) { return bar + 1 }\nfoo(1)"),
+        options(r#"{ "compress": true, "mangle": true }"#),
+    )
+    .expect("minify should succeed");
+
+    let code = get_string(&output, "code").expect("output should have code");
+    assert!(!code.contains("bar"));
+    assert!(!code.contains('\n'));
+}
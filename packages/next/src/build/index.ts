@@ -1062,7 +1062,7 @@ export default async function build(
           rewrites.afterFiles.length > 0 ||
           rewrites.fallback.length > 0
 
-        await binding.turbo.nextBuild({
+        const featureUsage = await binding.turbo.nextBuild({
           ...NextBuildContext,
           root,
           distDir: config.distDir,
@@ -1077,10 +1077,15 @@ export default async function build(
             fetchCacheKeyPrefix: config.experimental.fetchCacheKeyPrefix,
             hasRewrites,
             middlewareMatchers: undefined,
-            previewModeId: undefined,
+            previewModeId: previewProps.previewModeId,
           }),
         })
 
+        telemetry.record(eventBuildFeatureUsage(featureUsage))
+
+        // TODO: static generation doesn't run for Turbopack builds yet, so the
+        // 404.html and the app not-found HTML aren't generated from the
+        // /_error and /_not-found entries.
         const [duration] = process.hrtime(turboNextBuildStart)
         return { duration, buildTraceContext: null }
       }
@@ -2800,7 +2805,9 @@ export default async function build(
       )
 
       if (NextBuildContext.telemetryPlugin) {
-        const events = eventBuildFeatureUsage(NextBuildContext.telemetryPlugin)
+        const events = eventBuildFeatureUsage(
+          NextBuildContext.telemetryPlugin.usages()
+        )
         telemetry.record(events)
         telemetry.record(
           eventPackageUsedInGetServerSideProps(NextBuildContext.telemetryPlugin)
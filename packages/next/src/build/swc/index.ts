@@ -162,11 +162,24 @@ export interface Binding {
   }
   minify: any
   minifySync: any
+  transformCss?: any
+  transformCssSync?: any
+  getImageMetadata?(
+    path: string,
+    content: Buffer | undefined,
+    blurPlaceholder: boolean
+  ): Promise<ImageMetadata>
+  calculateFontFallbackMetrics(
+    fontFile: Buffer,
+    fallback: 'Arial' | 'Times New Roman'
+  ): FontFallbackMetrics
   transform: any
   transformSync: any
+  transformCachedSync?: any
   parse: any
   parseSync: any
   getTargetTriple(): string | undefined
+  getBinaryMetadata?(): BinaryMetadata
   initCustomTraceSubscriber?: any
   teardownTraceSubscriber?: any
   initHeapProfiler?: any
@@ -174,6 +187,14 @@ export interface Binding {
   teardownCrashReporter?: any
 }
 
+export interface BinaryMetadata {
+  target: string
+  version: string
+  rustcVersion: string
+  allocator: string
+  features: string[]
+}
+
 export async function loadBindings(
   useWasmBinary: boolean = false
 ): Promise<Binding> {
@@ -203,23 +224,16 @@ export async function loadBindings(
     }
 
     let attempts: any[] = []
-    const disableWasmFallback = process.env.NEXT_DISABLE_SWC_WASM
-    const unsupportedPlatform = triples.some(
-      (triple: any) =>
-        !!triple?.raw && knownDefaultWasmFallbackTriples.includes(triple.raw)
-    )
-    const isWebContainer = process.versions.webcontainer
-    const shouldLoadWasmFallbackFirst =
-      (!disableWasmFallback && unsupportedPlatform && useWasmBinary) ||
-      isWebContainer
+    const unsupportedPlatform = isWasmFallbackPlatform(triples)
+    const loadWasmFirst = shouldLoadWasmFallbackFirst(triples, useWasmBinary)
 
-    if (!unsupportedPlatform && useWasmBinary) {
+    if (!unsupportedPlatform && triples.length > 0 && useWasmBinary) {
       Log.warn(
         `experimental.useWasmBinary is not an option for supported platform ${PlatformName}/${ArchName} and will be ignored.`
       )
     }
 
-    if (shouldLoadWasmFallbackFirst) {
+    if (loadWasmFirst) {
       lastNativeBindingsLoadErrorCode = 'unsupported_target'
       const fallbackBindings = await tryLoadWasmWithFallback(attempts)
       if (fallbackBindings) {
@@ -252,11 +266,56 @@ export async function loadBindings(
       attempts = attempts.concat(a)
     }
 
+    if (!loadWasmFirst && canLoadWasm()) {
+      const fallbackBindings = await tryLoadWasmWithFallback(attempts)
+      if (fallbackBindings) {
+        return resolve(fallbackBindings)
+      }
+    }
+
     logLoadFailure(attempts, true)
   })
   return pendingBindings
 }
 
+/**
+ * Whether the wasm bindings can be used by this process. They need
+ * `WebAssembly` and can be disabled with `NEXT_DISABLE_SWC_WASM`.
+ */
+export function canLoadWasm(): boolean {
+  return !process.env.NEXT_DISABLE_SWC_WASM && typeof WebAssembly === 'object'
+}
+
+function isWasmFallbackPlatform(targetTriples: any[]): boolean {
+  return targetTriples.some(
+    (triple: any) =>
+      !!triple?.raw && knownDefaultWasmFallbackTriples.includes(triple.raw)
+  )
+}
+
+/**
+ * Whether to try the wasm bindings before the native ones. That's the case
+ * for platforms without prebuilt native bindings (`targetTriples` is empty),
+ * for the `knownDefaultWasmFallbackTriples` with `experimental.useWasmBinary`
+ * and for WebContainers, which can't load native addons. Otherwise the wasm
+ * bindings are only tried once the native ones failed to load.
+ */
+export function shouldLoadWasmFallbackFirst(
+  targetTriples: any[],
+  useWasmBinary: boolean
+): boolean {
+  if (process.versions.webcontainer) {
+    return true
+  }
+  if (!canLoadWasm()) {
+    return false
+  }
+  return (
+    targetTriples.length === 0 ||
+    (useWasmBinary && isWasmFallbackPlatform(targetTriples))
+  )
+}
+
 async function tryLoadNativeWithFallback(attempts: Array<string>) {
   const nativeBindingsDirectory = path.join(
     path.dirname(require.resolve('next/package.json')),
@@ -411,6 +470,24 @@ export interface ProjectOptions {
    * The address of the dev server.
    */
   serverAddr: string
+
+  /**
+   * Whether the project is compiled for `next dev` or `next build`.
+   */
+  dev: boolean
+
+  /**
+   * The edge functions whose chunks are rolled into a single file, by page,
+   * e.g. `/api/hello` for pages, `/api/hello/route` for app routes or
+   * `/middleware`.
+   */
+  singleFileEdgeRoutes?: string[]
+
+  /**
+   * The ID of the build, for `next build`. Defaults to the ID returned by
+   * `generateBuildId`, or a random one.
+   */
+  buildId?: string
 }
 
 type RustifiedEnv = { name: string; value: string }[]
@@ -471,6 +548,11 @@ export interface TurboEngineOptions {
    * An upper bound of memory that turbopack will attempt to stay under.
    */
   memoryLimit?: number
+  /**
+   * If set, unused tasks are garbage collected periodically with the given
+   * interval in milliseconds. 0 disables it.
+   */
+  gcInterval?: number
 }
 
 export interface Issue {
@@ -490,6 +572,11 @@ export interface Issue {
   }
   documentationLink: string
   subIssues: Issue[]
+  /**
+   * The chain of modules that led to the issue, starting at the module closest
+   * to it and ending at the entrypoint.
+   */
+  processingPath: Array<{ filePath?: string; description: string }>
 }
 
 export interface Diagnostics {
@@ -498,6 +585,17 @@ export interface Diagnostics {
   payload: unknown
 }
 
+export interface IssueFilter {
+  /**
+   * Only report issues at least as severe as this one.
+   */
+  minSeverity?: string
+  /**
+   * Only report issues whose file path starts with one of these prefixes.
+   */
+  paths?: string[]
+}
+
 export type TurbopackResult<T = {}> = T & {
   issues: Issue[]
   diagnostics: Diagnostics[]
@@ -507,9 +605,16 @@ export interface Middleware {
   endpoint: Endpoint
 }
 
+export interface Instrumentation {
+  nodeJs: Endpoint
+  edge: Endpoint
+}
+
 export interface Entrypoints {
   routes: Map<string, Route>
   middleware?: Middleware
+  instrumentation?: Instrumentation
+  serviceWorkerEndpoint?: Endpoint
   pagesDocumentEndpoint: Endpoint
   pagesAppEndpoint: Endpoint
   pagesErrorEndpoint: Endpoint
@@ -536,6 +641,47 @@ export interface UpdateInfo {
   tasks: number
 }
 
+export interface ImageMetadata {
+  width: number
+  height: number
+  mimeType?: string
+  /**
+   * The EXIF orientation, from 1 to 8. `width` and `height` are the stored
+   * dimensions, which are swapped when displayed with orientations 5 to 8.
+   */
+  orientation?: number
+  blurPlaceholder?: {
+    dataUrl: string
+    width: number
+    height: number
+  }
+}
+
+/**
+ * The overrides of the fallback font of a font file, matching the ones
+ * generated by `next/font/local`.
+ */
+export interface FontFallbackMetrics {
+  ascent: number
+  descent: number
+  lineGap: number
+  sizeAdjust: number
+}
+
+export interface BuildProgress {
+  pathname: string
+  completed: number
+  total: number
+}
+
+export interface BuildResult {
+  /**
+   * How often each feature module, e.g. `next/image`, is imported in the
+   * build, for the feature usage telemetry events.
+   */
+  featureUsage: Array<{ featureName: string; invocationCount: number }>
+}
+
 export interface Project {
   update(options: Partial<ProjectOptions>): Promise<void>
   entrypointsSubscribe(): AsyncIterableIterator<TurbopackResult<Entrypoints>>
@@ -548,6 +694,12 @@ export interface Project {
     stackFrame: TurbopackStackFrame
   ): Promise<TurbopackStackFrame | null>
   updateInfoSubscribe(): AsyncIterableIterator<TurbopackResult<UpdateInfo>>
+  issuesSubscribe(filter?: IssueFilter): AsyncIterableIterator<TurbopackResult>
+  runGc(idle?: boolean): boolean
+  shutdown(): Promise<void>
+  build(
+    onProgress?: (progress: BuildProgress) => void
+  ): Promise<TurbopackResult<BuildResult>>
 }
 
 export type Route =
@@ -630,6 +782,12 @@ export type WrittenEndpoint =
       globalVarName: string
       config: EndpointConfig
     }
+  | {
+      type: 'client'
+      /** The files written for the endpoint, relative to the static output. */
+      files: string[]
+      config: EndpointConfig
+    }
 
 function rustifyEnv(env: Record<string, string>): RustifiedEnv {
   return Object.entries(env)
@@ -744,7 +902,8 @@ function bindingToApi(binding: any, _wasm: boolean) {
     return {
       ...options,
       nextConfig:
-        options.nextConfig && (await serializeNextConfig(options.nextConfig)),
+        options.nextConfig &&
+        (await serializeNextConfig(options.nextConfig, options.projectPath)),
       jsConfig: options.jsConfig && JSON.stringify(options.jsConfig),
       env: options.env && rustifyEnv(options.env),
       defineEnv: options.defineEnv,
@@ -773,6 +932,8 @@ function bindingToApi(binding: any, _wasm: boolean) {
       type NapiEntrypoints = {
         routes: NapiRoute[]
         middleware?: NapiMiddleware
+        instrumentation?: NapiInstrumentation
+        serviceWorkerEndpoint?: NapiEndpoint
         pagesDocumentEndpoint: NapiEndpoint
         pagesAppEndpoint: NapiEndpoint
         pagesErrorEndpoint: NapiEndpoint
@@ -784,6 +945,11 @@ function bindingToApi(binding: any, _wasm: boolean) {
         matcher?: string[]
       }
 
+      type NapiInstrumentation = {
+        nodeJs: NapiEndpoint
+        edge: NapiEndpoint
+      }
+
       type NapiRoute = {
         pathname: string
       } & (
@@ -870,9 +1036,22 @@ function bindingToApi(binding: any, _wasm: boolean) {
           const middleware = entrypoints.middleware
             ? napiMiddlewareToMiddleware(entrypoints.middleware)
             : undefined
+          const napiInstrumentationToInstrumentation = (
+            instrumentation: NapiInstrumentation
+          ) => ({
+            nodeJs: new EndpointImpl(instrumentation.nodeJs),
+            edge: new EndpointImpl(instrumentation.edge),
+          })
+          const instrumentation = entrypoints.instrumentation
+            ? napiInstrumentationToInstrumentation(entrypoints.instrumentation)
+            : undefined
           yield {
             routes,
             middleware,
+            instrumentation,
+            serviceWorkerEndpoint: entrypoints.serviceWorkerEndpoint
+              ? new EndpointImpl(entrypoints.serviceWorkerEndpoint)
+              : undefined,
             pagesDocumentEndpoint: new EndpointImpl(
               entrypoints.pagesDocumentEndpoint
             ),
@@ -923,6 +1102,32 @@ function bindingToApi(binding: any, _wasm: boolean) {
       )
       return subscription
     }
+
+    issuesSubscribe(filter?: IssueFilter) {
+      const subscription = subscribe<TurbopackResult>(false, async (callback) =>
+        binding.projectIssuesSubscribe(this._nativeProject, filter, callback)
+      )
+      return subscription
+    }
+
+    runGc(idle: boolean = false): boolean {
+      return binding.projectRunGc(this._nativeProject, idle)
+    }
+
+    shutdown(): Promise<void> {
+      return binding.projectShutdown(this._nativeProject)
+    }
+
+    build(
+      onProgress?: (progress: BuildProgress) => void
+    ): Promise<TurbopackResult<BuildResult>> {
+      return binding.projectBuild(
+        this._nativeProject,
+        (err: Error | null, progress: BuildProgress) => {
+          if (!err) onProgress?.(progress)
+        }
+      )
+    }
   }
 
   class EndpointImpl implements Endpoint {
@@ -969,7 +1174,8 @@ function bindingToApi(binding: any, _wasm: boolean) {
   }
 
   async function serializeNextConfig(
-    nextConfig: NextConfigComplete
+    nextConfig: NextConfigComplete,
+    projectPath?: string
   ): Promise<string> {
     let nextConfigSerializable = nextConfig as any
 
@@ -1005,6 +1211,25 @@ function bindingToApi(binding: any, _wasm: boolean) {
           )
         : undefined
 
+    // Turbopack resolves the cache handler relative to the project directory.
+    const incrementalCacheHandlerPath =
+      nextConfig.experimental?.incrementalCacheHandlerPath
+    if (
+      projectPath &&
+      incrementalCacheHandlerPath &&
+      path.isAbsolute(incrementalCacheHandlerPath)
+    ) {
+      nextConfigSerializable = {
+        ...nextConfigSerializable,
+        experimental: {
+          ...nextConfigSerializable.experimental,
+          incrementalCacheHandlerPath: path
+            .relative(projectPath, incrementalCacheHandlerPath)
+            .replace(/\\/g, '/'),
+        },
+      }
+    }
+
     return JSON.stringify(nextConfigSerializable, null, 2)
   }
 
@@ -1094,6 +1319,11 @@ async function loadWasm(importPath = '') {
         getTargetTriple() {
           return undefined
         },
+        calculateFontFallbackMetrics(): FontFallbackMetrics {
+          throw new Error(
+            'Wasm binding does not support calculating font fallback metrics yet'
+          )
+        },
         turbo: {
           startTrace: () => {
             Log.error('Wasm binding does not support trace yet')
@@ -1104,6 +1334,7 @@ async function loadWasm(importPath = '') {
               rootDir: string,
               applicationDir: string,
               pageExtensions: string[],
+              dev: boolean,
               callbackFn: (err: Error, entrypoints: any) => void
             ) => {
               return bindings.streamEntrypoints(
@@ -1111,6 +1342,7 @@ async function loadWasm(importPath = '') {
                 rootDir,
                 applicationDir,
                 pageExtensions,
+                dev,
                 callbackFn
               )
             },
@@ -1118,13 +1350,15 @@ async function loadWasm(importPath = '') {
               turboTasks: any,
               rootDir: string,
               applicationDir: string,
-              pageExtensions: string[]
+              pageExtensions: string[],
+              dev: boolean
             ) => {
               return bindings.getEntrypoints(
                 turboTasks,
                 rootDir,
                 applicationDir,
-                pageExtensions
+                pageExtensions,
+                dev
               )
             },
           },
@@ -1258,6 +1492,23 @@ function loadNative(importPath?: string) {
         )
       },
 
+      /**
+       * Transforms a version of a file, reusing the output cached in the
+       * `cache` returned for a previous call if the source is one of its
+       * recent versions. `options` can be omitted when a cache is passed.
+       */
+      transformCachedSync(cache: any, src: string, options?: any) {
+        if (options?.jsc?.parser) {
+          options.jsc.parser.syntax = options.jsc.parser.syntax ?? 'ecmascript'
+        }
+
+        return bindings.transformCachedSync(
+          cache,
+          src,
+          options ? toBuffer(options) : undefined
+        )
+      },
+
       minify(src: string, options: any) {
         return bindings.minify(toBuffer(src), toBuffer(options ?? {}))
       },
@@ -1266,11 +1517,35 @@ function loadNative(importPath?: string) {
         return bindings.minifySync(toBuffer(src), toBuffer(options ?? {}))
       },
 
+      calculateFontFallbackMetrics(
+        fontFile: Buffer,
+        fallback: 'Arial' | 'Times New Roman'
+      ): FontFallbackMetrics {
+        return bindings.calculateFontFallbackMetrics(fontFile, fallback)
+      },
+
+      getImageMetadata(
+        path: string,
+        content: Buffer | undefined,
+        blurPlaceholder: boolean
+      ): Promise<ImageMetadata> {
+        return bindings.getImageMetadata(path, content, blurPlaceholder)
+      },
+
+      transformCss(src: string, options: any) {
+        return bindings.transformCss(src, toBuffer(options ?? {}))
+      },
+
+      transformCssSync(src: string, options: any) {
+        return bindings.transformCssSync(src, toBuffer(options ?? {}))
+      },
+
       parse(src: string, options: any) {
         return bindings.parse(src, toBuffer(options ?? {}))
       },
 
       getTargetTriple: bindings.getTargetTriple,
+      getBinaryMetadata: bindings.getBinaryMetadata,
       initCustomTraceSubscriber: bindings.initCustomTraceSubscriber,
       teardownTraceSubscriber: bindings.teardownTraceSubscriber,
       initHeapProfiler: bindings.initHeapProfiler,
@@ -1299,6 +1574,7 @@ function loadNative(importPath?: string) {
             rootDir: string,
             applicationDir: string,
             pageExtensions: string[],
+            dev: boolean,
             fn: (entrypoints: any) => void
           ) => {
             return (customBindings ?? bindings).streamEntrypoints(
@@ -1306,6 +1582,7 @@ function loadNative(importPath?: string) {
               rootDir,
               applicationDir,
               pageExtensions,
+              dev,
               fn
             )
           },
@@ -1313,13 +1590,15 @@ function loadNative(importPath?: string) {
             turboTasks: any,
             rootDir: string,
             applicationDir: string,
-            pageExtensions: string[]
+            pageExtensions: string[],
+            dev: boolean
           ) => {
             return (customBindings ?? bindings).getEntrypoints(
               turboTasks,
               rootDir,
               applicationDir,
-              pageExtensions
+              pageExtensions,
+              dev
             )
           },
         },
@@ -1401,6 +1680,7 @@ export function getBinaryMetadata() {
 
   return {
     target: bindings?.getTargetTriple?.(),
+    ...bindings?.getBinaryMetadata?.(),
   }
 }
 
@@ -0,0 +1,90 @@
+/**
+ * Runs the React Compiler (`babel-plugin-react-compiler`) on the components
+ * and hooks of a module. Turbopack applies this loader to the client and SSR
+ * modules of the project when `experimental.reactCompiler` is enabled.
+ *
+ * A module or a function opts out with a `'use no memo'` directive. The
+ * diagnostics of the compiler, e.g. for components it couldn't memoize, are
+ * emitted as warnings, which are reported as issues.
+ */
+
+import type webpack from 'webpack'
+import { transformAsync } from 'next/dist/compiled/babel/core'
+
+export type NextReactCompilerLoaderOptions = {
+  compilerOptions: Record<string, unknown>
+}
+
+type LoggerEvent = {
+  kind: string
+  detail?: any
+  data?: string
+}
+
+function formatEvent(event: LoggerEvent): string {
+  if (event.kind === 'PipelineError') {
+    return `React Compiler failed: ${event.data}`
+  }
+
+  const detail = event.detail?.options ?? event.detail ?? {}
+  const loc = detail.loc?.start
+  const location = loc ? ` (${loc.line}:${loc.column})` : ''
+  const description = detail.description ? `\n${detail.description}` : ''
+  return `React Compiler couldn't optimize this code${location}: ${detail.reason}${description}`
+}
+
+const NextReactCompilerLoader: webpack.LoaderDefinitionFunction<NextReactCompilerLoaderOptions> =
+  function (source, inputSourceMap) {
+    this.cacheable && this.cacheable()
+    const callback = this.async()
+    const { compilerOptions } = this.getOptions()
+    const filename = this.resourcePath
+
+    // `'use no memo'` is handled by the compiler as well, but skipping the
+    // module avoids parsing it.
+    if (/^\s*['"]use no memo['"]/.test(source)) {
+      callback(null, source, inputSourceMap)
+      return
+    }
+
+    const logger = {
+      logEvent: (_filename: string | null, event: LoggerEvent) => {
+        if (
+          event.kind === 'CompileError' ||
+          event.kind === 'CompileDiagnostic' ||
+          event.kind === 'PipelineError'
+        ) {
+          this.emitWarning(new Error(formatEvent(event)))
+        }
+      },
+    }
+
+    const isTypeScript = /\.(ts|tsx|mts|cts)$/.test(filename)
+    const isJSX = !/\.(ts|mts|cts)$/.test(filename)
+
+    transformAsync(source, {
+      filename,
+      babelrc: false,
+      configFile: false,
+      sourceMaps: true,
+      inputSourceMap: (inputSourceMap as any) || undefined,
+      parserOpts: {
+        plugins: [
+          ...(isTypeScript ? ['typescript' as const] : []),
+          ...(isJSX ? ['jsx' as const] : []),
+        ],
+      },
+      plugins: [['babel-plugin-react-compiler', { ...compilerOptions, logger }]],
+    }).then(
+      (result) => {
+        if (!result?.code) {
+          callback(null, source, inputSourceMap)
+          return
+        }
+        callback(null, result.code, (result.map as any) ?? undefined)
+      },
+      (err) => callback(err)
+    )
+  }
+
+export default NextReactCompilerLoader
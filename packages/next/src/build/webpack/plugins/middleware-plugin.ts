@@ -48,6 +48,17 @@ export interface MiddlewareManifest {
   sortedMiddleware: string[]
   middleware: { [page: string]: EdgeFunctionDefinition }
   functions: { [page: string]: EdgeFunctionDefinition }
+  /**
+   * The edge chunks of the instrumentation hook. Only set in the partial
+   * manifests written by Turbopack, which are merged into every edge function.
+   */
+  instrumentation?: InstrumentationDefinition
+}
+
+export interface InstrumentationDefinition {
+  files: string[]
+  name: string
+  wasm?: AssetBinding[]
 }
 
 interface EntryMetadata {
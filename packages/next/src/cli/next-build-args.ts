@@ -10,6 +10,7 @@ export const validArgs: arg.Spec = {
   '--experimental-app-only': Boolean,
   '--experimental-turbo': Boolean,
   '--experimental-turbo-root': String,
+  '--experimental-turbo-profile': Boolean,
   '--build-mode': String,
   // Aliases
   '-h': '--help',
@@ -28,6 +28,9 @@ const nextBuild: CliCommand = (args) => {
       --no-mangling            Disable mangling
       --experimental-app-only  Only build 'app' routes
       --experimental-turbo     Enable experimental turbo mode
+      --experimental-turbo-profile
+                               Write the time spent in Turbopack tasks to
+                               <distDir>/trace.folded, for a flamegraph
       --help, -h               Displays this message
     `,
       0
@@ -55,6 +58,23 @@ const nextBuild: CliCommand = (args) => {
     process.env.TURBOPACK = '1'
   }
 
+  if (args['--experimental-turbo-profile']) {
+    if (!process.env.TURBOPACK) {
+      printAndExit(
+        '> `--experimental-turbo-profile` requires `--experimental-turbo`'
+      )
+    }
+    // The self time of the turbo-tasks spans by stack. It's derived from the
+    // trace spans rather than sampled.
+    if (!process.env.NEXT_TURBOPACK_TRACING) {
+      process.env.NEXT_TURBOPACK_TRACING = 'turbo-tasks'
+    }
+    process.env.NEXT_TURBOPACK_TRACE_FORMAT = 'flamegraph'
+    Log.warn(
+      'Turbopack profiling is enabled. Note: This may affect performance'
+    )
+  }
+
   return build(
     dir,
     args['--profile'],
@@ -66,6 +66,8 @@ const supportedTurbopackNextConfigOptions = [
   'experimental.useDeploymentId',
   'experimental.useDeploymentIdServerActions',
   'experimental.deploymentId',
+  'experimental.fallbackNodePolyfills',
+  'experimental.instrumentationHook',
 
   // Experimental options that don't affect compilation
   'experimental.ppr',
@@ -104,7 +106,6 @@ const supportedTurbopackNextConfigOptions = [
   // 'compiler.removeConsole',
   // 'compiler.styledComponents',
   // 'experimental.fetchCacheKeyPrefix',
-  // 'experimental.instrumentationHook',
 
   // Left to be implemented
   'excludeDefaultMomentLocales',
@@ -118,7 +119,6 @@ const supportedTurbopackNextConfigOptions = [
   // 'experimental.allowedRevalidateHeaderKeys',
   // 'experimental.bundlePagesExternals',
   // 'experimental.extensionAlias',
-  // 'experimental.fallbackNodePolyfills',
 
   // 'experimental.sri.algorithm',
   // 'experimental.swcTraceProfiling',
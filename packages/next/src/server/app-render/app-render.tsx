@@ -111,6 +111,11 @@ export type AppRenderContext = AppRenderBaseContext & {
   serverComponentsErrorHandler: ErrorHandler
   isNotFoundPath: boolean
   res: ServerResponse
+  /**
+   * The nonce from the request's Content-Security-Policy header, applied to
+   * every script and stylesheet tag emitted for the rendered segments.
+   */
+  nonce: string | undefined
 }
 
 function createNotFoundLoaderTree(loaderTree: LoaderTree): LoaderTree {
@@ -584,6 +589,13 @@ async function renderToHTMLOrFlightImpl(
     providedFlightRouterState
   )
 
+  // Get the nonce from the incoming request if it has one.
+  const csp = req.headers['content-security-policy']
+  let nonce: string | undefined
+  if (csp && typeof csp === 'string') {
+    nonce = getScriptNonceFromHeader(csp)
+  }
+
   const ctx: AppRenderContext = {
     ...baseCtx,
     getDynamicParamFromSegment,
@@ -603,6 +615,7 @@ async function renderToHTMLOrFlightImpl(
     serverComponentsErrorHandler,
     isNotFoundPath,
     res,
+    nonce,
   }
 
   if (isRSCRequest && !isStaticGeneration) {
@@ -617,13 +630,6 @@ async function renderToHTMLOrFlightImpl(
         .catch(() => null)
     : Promise.resolve(null)
 
-  // Get the nonce from the incoming request if it has one.
-  const csp = req.headers['content-security-policy']
-  let nonce: string | undefined
-  if (csp && typeof csp === 'string') {
-    nonce = getScriptNonceFromHeader(csp)
-  }
-
   const serverComponentsRenderOpts: ServerComponentRendererOptions = {
     inlinedDataTransformStream: new TransformStream<Uint8Array, Uint8Array>(),
     clientReferenceManifest,
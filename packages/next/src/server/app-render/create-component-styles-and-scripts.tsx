@@ -53,6 +53,7 @@ export async function createComponentStylesAndScripts({
             // @ts-ignore
             precedence={precedence}
             crossOrigin={ctx.renderOpts.crossOrigin}
+            nonce={ctx.nonce}
             key={index}
           />
         )
@@ -61,7 +62,11 @@ export async function createComponentStylesAndScripts({
 
   const scripts = jsHrefs
     ? jsHrefs.map((href) => (
-        <script src={`${ctx.assetPrefix}/_next/${href}`} async={true} />
+        <script
+          src={`${ctx.assetPrefix}/_next/${href}`}
+          async={true}
+          nonce={ctx.nonce}
+        />
       ))
     : null
 
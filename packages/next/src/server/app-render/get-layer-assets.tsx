@@ -87,6 +87,7 @@ export function getLayerAssets({
             // @ts-ignore
             precedence={precedence}
             crossOrigin={ctx.renderOpts.crossOrigin}
+            nonce={ctx.nonce}
             key={index}
           />
         )
@@ -97,7 +98,14 @@ export function getLayerAssets({
     ? scriptTags.map((href, index) => {
         const fullSrc = `${ctx.assetPrefix}/_next/${href}`
 
-        return <script src={fullSrc} async={true} key={index} />
+        return (
+          <script
+            src={fullSrc}
+            async={true}
+            nonce={ctx.nonce}
+            key={index}
+          />
+        )
       })
     : []
 
@@ -317,6 +317,9 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
           )
           .optional(),
         mdxRs: z.boolean().optional(),
+        reactCompiler: z
+          .union([z.boolean(), z.record(z.string(), z.any())])
+          .optional(),
         typedRoutes: z.boolean().optional(),
         webpackBuildWorker: z.boolean().optional(),
         turbo: z
@@ -336,11 +339,14 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
                 ])
               )
               .optional(),
+            resolveExtensions: z.array(z.string()).optional(),
+            sourceMaps: z.enum(['full', 'none']).optional(),
           })
           .optional(),
         optimizePackageImports: z.array(z.string()).optional(),
         optimizeServerReact: z.boolean().optional(),
         instrumentationHook: z.boolean().optional(),
+        serviceWorker: z.boolean().optional(),
         turbotrace: z
           .object({
             logLevel: z
@@ -364,6 +370,7 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
           .optional(),
         serverMinification: z.boolean().optional(),
         serverSourceMaps: z.boolean().optional(),
+        edgeFunctionSizeLimit: z.number().int().positive().optional(),
         bundlePagesExternals: z.boolean().optional(),
         staticWorkerRequestDeduping: z.boolean().optional(),
         useWasmBinary: z.boolean().optional(),
@@ -111,6 +111,14 @@ export interface ExperimentalTurboOptions {
     string | string[] | Record<string, string | string[]>
   >
 
+  /**
+   * (`next --turbo` only) The extensions to try when resolving a request, in
+   * order. Replaces the default list.
+   *
+   * @see [Resolve Extensions](https://nextjs.org/docs/app/api-reference/next-config-js/turbo#resolve-extensions)
+   */
+  resolveExtensions?: string[]
+
   /**
    * (`next --turbo` only) A list of webpack loaders to apply when running with Turbopack.
    *
@@ -120,10 +128,22 @@ export interface ExperimentalTurboOptions {
 
   /**
    * (`next --turbo` only) A list of webpack loaders to apply when running with Turbopack.
+   * Loaders can be given a query, like `'svgr-loader?icon'`, and `as` decides the
+   * module type of their output, like `'*.js'`. Only a subset of the loader API is
+   * available: `this.emitFile` and `this.resolve` aren't supported yet.
    *
    * @see [Turbopack Loaders](https://nextjs.org/docs/app/api-reference/next-config-js/turbo#webpack-loaders)
    */
   rules?: Record<string, TurboRule>
+
+  /**
+   * (`next --turbo` only) The source maps generated for client chunks in development.
+   * `'none'` skips them, which makes rebuilds of large projects faster. There's
+   * no line-only mode like webpack's `cheap-*` devtools yet.
+   *
+   * @default 'full'
+   */
+  sourceMaps?: 'full' | 'none'
 }
 
 export interface WebpackConfigContext {
@@ -275,6 +295,13 @@ export interface ExperimentalConfig {
    */
   mdxRs?: boolean
 
+  /**
+   * Compile the components and hooks of the client and SSR modules with the
+   * React Compiler. Requires `babel-plugin-react-compiler` to be installed, and
+   * is only supported with Turbopack. The options are passed to the plugin.
+   */
+  reactCompiler?: boolean | Record<string, unknown>
+
   /**
    * Generate Route types and enable type checking for Link and Router.push, etc.
    * @see https://nextjs.org/docs/app/api-reference/next-config-js/typedRoutes
@@ -291,6 +318,13 @@ export interface ExperimentalConfig {
    */
   instrumentationHook?: boolean
 
+  /**
+   * Bundle `sw.{ext}` in the project root (or `src/`) as a service worker and
+   * serve it at `/sw.js`, so it can be registered with the scope of the whole
+   * site. Only supported by Turbopack.
+   */
+  serviceWorker?: boolean
+
   /**
    * Using this feature will enable the `react@experimental` for the `app` directory.
    */
@@ -327,6 +361,14 @@ export interface ExperimentalConfig {
    */
   serverSourceMaps?: boolean
 
+  /**
+   * (`next --turbo` only) The gzipped size in bytes an edge function must not
+   * exceed in `next build`.
+   *
+   * @default 1048576
+   */
+  edgeFunctionSizeLimit?: number
+
   /**
    * @internal Used by the Next.js internals only.
    */
@@ -789,6 +831,7 @@ export const defaultConfig: NextConfig = {
     turbotrace: undefined,
     typedRoutes: false,
     instrumentationHook: false,
+    serviceWorker: false,
     bundlePagesExternals: false,
     ppr: false,
   },
@@ -12,6 +12,13 @@ export type BuildManifest = {
     [page: string]: readonly string[]
   }
   ampFirstPages: readonly string[]
+  /**
+   * External `beforeInteractive` scripts found at build time, by page. Only
+   * emitted by Turbopack.
+   */
+  beforeInteractiveScripts?: {
+    [page: string]: readonly string[]
+  }
 }
 
 export function getPageFiles(
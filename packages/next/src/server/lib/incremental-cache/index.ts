@@ -112,6 +112,11 @@ export class IncrementalCache implements IncrementalCacheType {
     experimental: { ppr: boolean }
   }) {
     const debug = !!process.env.NEXT_PRIVATE_DEBUG_CACHE
+    if (CurCacheHandler && typeof CurCacheHandler !== 'function') {
+      throw new Error(
+        'The cache handler configured with `experimental.incrementalCacheHandlerPath` must default export a class'
+      )
+    }
     if (!CurCacheHandler) {
       if (fs && serverDistDir) {
         if (debug) {
@@ -594,7 +594,14 @@ export async function initialize(opts: {
       }
 
       if (parsedUrl.protocol) {
-        return await proxyRequest(req, socket as any, parsedUrl, head)
+        return await proxyRequest(
+          req,
+          socket as any,
+          parsedUrl,
+          head,
+          undefined,
+          config.experimental.proxyTimeout
+        )
       }
 
       // If there's no matched output, we don't handle the request as user's
@@ -59,6 +59,8 @@ export type FsOutput = {
 
 const debug = setupDebug('next:router-server:filesystem')
 
+const SERVICE_WORKER_PATH = '/sw.js'
+
 export type FilesystemDynamicRoute = ManifestRoute & {
   /**
    * The path matcher that can be used to match paths against this route.
@@ -451,6 +453,25 @@ export async function setupFsCheck(opts: {
         }
       }
 
+      // A service worker bundled from `sw.{ext}` (`experimental.serviceWorker`)
+      // is emitted into the static folder, but served from the root so its
+      // scope covers the whole site.
+      if (itemPath === SERVICE_WORKER_PATH) {
+        const fsPath = path.join(nextStaticFolderPath, 'sw.js')
+        if (
+          opts.dev
+            ? await fileExists(fsPath, FileType.File)
+            : nextStaticFolderItems.has(`/_next/static${SERVICE_WORKER_PATH}`)
+        ) {
+          return {
+            type: 'nextStaticFolder',
+            fsPath,
+            itemsRoot: nextStaticFolderPath,
+            itemPath,
+          }
+        }
+      }
+
       const itemsToCheck: Array<[Set<string>, FsOutput['type']]> = [
         [this.devVirtualFsItems, 'devVirtualFsItem'],
         [nextStaticFolderItems, 'nextStaticFolder'],
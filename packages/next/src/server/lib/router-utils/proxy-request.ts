@@ -25,9 +25,12 @@ export async function proxyRequest(
     changeOrigin: true,
     ignorePath: true,
     ws: true,
-    // we limit proxy requests to 30s by default, in development
-    // we don't time out WebSocket requests to allow proxying
-    proxyTimeout: proxyTimeout === null ? undefined : proxyTimeout || 30_000,
+    // we limit proxy requests to 30s by default, WebSocket requests are
+    // long-lived so they are only timed out when configured explicitly
+    proxyTimeout:
+      proxyTimeout === null
+        ? undefined
+        : proxyTimeout || (upgradeHead ? undefined : 30_000),
     headers: {
       'x-forwarded-host': req.headers.host || '',
     },
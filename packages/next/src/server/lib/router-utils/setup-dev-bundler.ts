@@ -17,7 +17,10 @@ import type { BuildManifest } from '../../get-page-files'
 import type { PagesManifest } from '../../../build/webpack/plugins/pages-manifest-plugin'
 import type { AppBuildManifest } from '../../../build/webpack/plugins/app-build-manifest-plugin'
 import type { PropagateToWorkersField } from './types'
-import type { MiddlewareManifest } from '../../../build/webpack/plugins/middleware-plugin'
+import type {
+  InstrumentationDefinition,
+  MiddlewareManifest,
+} from '../../../build/webpack/plugins/middleware-plugin'
 import type {
   HMR_ACTION_TYPES,
   NextJsHotReloaderInterface,
@@ -113,6 +116,7 @@ import {
 import { devPageFiles } from '../../../build/webpack/plugins/next-types-plugin/shared'
 import type { LazyRenderServerInstance } from '../router-server'
 import { pathToRegexp } from 'next/dist/compiled/path-to-regexp'
+import { getMiddlewareMatchers } from '../../../build/analysis/get-page-static-info'
 import { HMR_ACTIONS_SENT_TO_BROWSER } from '../../dev/hot-reloader-types'
 import type { Update as TurbopackUpdate } from '../../../build/swc'
 import { debounce } from '../../utils'
@@ -251,6 +255,7 @@ async function startWatcher(opts: SetupOpts) {
         previewModeId: undefined,
       }),
       serverAddr: `127.0.0.1:${opts.port}`,
+      dev: true,
     })
     const iter = project.entrypointsSubscribe()
     const curEntries: Map<string, Route> = new Map()
@@ -325,6 +330,14 @@ async function startWatcher(opts: SetupOpts) {
       return message
     }
 
+    // The modules importing the module of the issue, up to the entrypoint, in
+    // the shape of webpack's `moduleTrace` that the error overlay renders.
+    function formatModuleTrace(issue: Issue) {
+      return issue.processingPath
+        .filter(({ filePath }) => filePath)
+        .map(({ filePath }) => ({ moduleName: filePath! }))
+    }
+
     class ModuleBuildError extends Error {}
 
     function processIssues(
@@ -345,7 +358,15 @@ async function startWatcher(opts: SetupOpts) {
         const key = issueKey(issue)
         const formatted = formatIssue(issue)
         if (!oldSet.has(key) && !newSet.has(key)) {
-          console.error(`  ⚠ ${displayName} ${key} ${formatted}\n\n`)
+          const moduleTrace = formatModuleTrace(issue)
+          const importTrace = moduleTrace.length
+            ? `\n\nImport trace for requested module:\n${moduleTrace
+                .map(({ moduleName }) => moduleName)
+                .join('\n')}`
+            : ''
+          console.error(
+            `  ⚠ ${displayName} ${key} ${formatted}${importTrace}\n\n`
+          )
         }
         newSet.set(key, issue)
         relevantIssues.add(formatted)
@@ -478,6 +499,7 @@ async function startWatcher(opts: SetupOpts) {
           errors.set(key, {
             message,
             details: issue.detail,
+            moduleTrace: formatModuleTrace(issue),
           })
         }
       }
@@ -534,13 +556,18 @@ async function startWatcher(opts: SetupOpts) {
     async function loadPartialManifest<T>(
       name: string,
       pageName: string,
-      type: 'pages' | 'app' | 'app-route' | 'middleware' = 'pages'
+      type:
+        | 'pages'
+        | 'app'
+        | 'app-route'
+        | 'middleware'
+        | 'instrumentation' = 'pages'
     ): Promise<T> {
       const manifestPath = path.posix.join(
         distDir,
         `server`,
         type === 'app-route' ? 'app' : type,
-        type === 'middleware'
+        type === 'middleware' || type === 'instrumentation'
           ? ''
           : pageName === '/'
           ? 'index'
@@ -570,7 +597,7 @@ async function startWatcher(opts: SetupOpts) {
 
     async function loadMiddlewareManifest(
       pageName: string,
-      type: 'pages' | 'app' | 'app-route' | 'middleware'
+      type: 'pages' | 'app' | 'app-route' | 'middleware' | 'instrumentation'
     ): Promise<void> {
       middlewareManifests.set(
         pageName,
@@ -689,6 +716,15 @@ async function startWatcher(opts: SetupOpts) {
       for (const m of manifests) {
         Object.assign(manifest.pages, m.pages)
         if (m.rootMainFiles.length) manifest.rootMainFiles = m.rootMainFiles
+        if (m.ampFirstPages?.length) {
+          manifest.ampFirstPages!.push(...m.ampFirstPages)
+        }
+        if (m.beforeInteractiveScripts) {
+          manifest.beforeInteractiveScripts = Object.assign(
+            manifest.beforeInteractiveScripts ?? {},
+            m.beforeInteractiveScripts
+          )
+        }
       }
       return manifest
     }
@@ -720,13 +756,28 @@ async function startWatcher(opts: SetupOpts) {
         sortedMiddleware: [],
         functions: {},
       }
+      let instrumentation: InstrumentationDefinition | undefined
       for (const m of manifests) {
         Object.assign(manifest.functions, m.functions)
         Object.assign(manifest.middleware, m.middleware)
+        if (m.instrumentation) {
+          instrumentation = m.instrumentation
+        }
       }
-      for (const fun of Object.values(manifest.functions).concat(
-        Object.values(manifest.middleware)
-      )) {
+      // Like with webpack, the edge chunks of the instrumentation hook are
+      // loaded before the files of every edge function.
+      if (instrumentation) {
+        for (const fun of [
+          ...Object.values(manifest.functions),
+          ...Object.values(manifest.middleware),
+        ]) {
+          fun.files = [...instrumentation.files, ...fun.files]
+          if (instrumentation.wasm?.length) {
+            fun.wasm = [...instrumentation.wasm, ...(fun.wasm ?? [])]
+          }
+        }
+      }
+      for (const fun of Object.values(manifest.functions)) {
         for (const matcher of fun.matchers) {
           if (!matcher.regexp) {
             matcher.regexp = pathToRegexp(matcher.originalSource, [], {
@@ -737,6 +788,20 @@ async function startWatcher(opts: SetupOpts) {
           }
         }
       }
+      // Middleware matchers from the config are prefixed with the locale and
+      // base path like in webpack builds, keeping their conditions.
+      for (const fun of Object.values(manifest.middleware)) {
+        fun.matchers = fun.matchers.map((matcher) => {
+          if (matcher.regexp) {
+            return matcher
+          }
+          const { originalSource, ...conditions } = matcher
+          return getMiddlewareMatchers(
+            { source: originalSource, ...conditions },
+            nextConfig
+          )[0]
+        })
+      }
       manifest.sortedMiddleware = Object.keys(manifest.middleware)
       return manifest
     }
@@ -1070,6 +1135,33 @@ async function startWatcher(opts: SetupOpts) {
             }
           }
 
+          const { instrumentation } = entrypoints
+          if (instrumentation) {
+            // The hook only runs once when the server starts, so its output is
+            // written upfront, before the dev server requires it.
+            const writtenNodeJsEndpoint = await processResult(
+              'instrumentation',
+              await instrumentation.nodeJs.writeToDisk()
+            )
+            processIssues(
+              'instrumentation',
+              'instrumentation',
+              writtenNodeJsEndpoint
+            )
+            const writtenEdgeEndpoint = await processResult(
+              'edge-instrumentation',
+              await instrumentation.edge.writeToDisk()
+            )
+            processIssues(
+              'edge-instrumentation',
+              'edge-instrumentation',
+              writtenEdgeEndpoint
+            )
+            await loadMiddlewareManifest('instrumentation', 'instrumentation')
+          } else {
+            middlewareManifests.delete('instrumentation')
+          }
+
           const { middleware } = entrypoints
           // We check for explicit true/false, since it's initialized to
           // undefined during the first loop (middlewareChanges event is
@@ -1140,6 +1232,30 @@ async function startWatcher(opts: SetupOpts) {
           )
           await propagateServerField('middleware', serverFields.middleware)
 
+          const { serviceWorkerEndpoint } = entrypoints
+          if (serviceWorkerEndpoint) {
+            const processServiceWorker = async () => {
+              processIssues(
+                'service-worker',
+                'service-worker',
+                await serviceWorkerEndpoint.writeToDisk()
+              )
+            }
+            await processServiceWorker()
+
+            // Browsers pick up the rewritten worker when they check for
+            // updates, so there is nothing to send over HMR.
+            changeSubscription(
+              'service-worker',
+              'client',
+              false,
+              serviceWorkerEndpoint,
+              processServiceWorker
+            )
+          } else {
+            await clearChangeSubscription('service-worker', 'client')
+          }
+
           currentEntriesHandlingResolve!()
           currentEntriesHandlingResolve = undefined
         }
@@ -1390,6 +1506,8 @@ async function startWatcher(opts: SetupOpts) {
           if (page === '/_app') return
           if (page === '/_document') return
           if (page === '/middleware') return
+          // The instrumentation hook is written with the entrypoints.
+          if (isInstrumentationHookFile(page)) return
 
           throw new PageNotFoundError(`route not found ${page}`)
         }
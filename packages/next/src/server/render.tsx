@@ -39,6 +39,7 @@ import type { ComponentsEnhancer } from '../shared/lib/utils'
 import type { NextParsedUrlQuery } from './request-meta'
 import type { Revalidate } from './lib/revalidate'
 import type { COMPILER_NAMES } from '../shared/lib/constants'
+import type { ScriptProps } from '../client/script'
 
 import React from 'react'
 import ReactDOMServer from 'react-dom/server.browser'
@@ -1428,6 +1429,22 @@ export async function renderToHTMLImpl(
   const hybridAmp = ampState.hybrid
   const docComponentsRendered: DocumentProps['docComponentsRendered'] = {}
 
+  // Turbopack records `beforeInteractive` scripts at build time so they are
+  // part of the initial HTML even when they weren't rendered on the server.
+  const buildBeforeInteractiveScripts =
+    buildManifest.beforeInteractiveScripts?.[pathname]
+  if (buildBeforeInteractiveScripts?.length) {
+    const beforeInteractive: ScriptProps[] = [
+      ...(scriptLoader.beforeInteractive || []),
+    ]
+    for (const src of buildBeforeInteractiveScripts) {
+      if (!beforeInteractive.some((script) => script.src === src)) {
+        beforeInteractive.push({ src, strategy: 'beforeInteractive' })
+      }
+    }
+    scriptLoader = { ...scriptLoader, beforeInteractive }
+  }
+
   const {
     assetPrefix,
     buildId,
@@ -1,7 +1,9 @@
 let runtimeConfig: any
 
 export default () => {
-  return runtimeConfig
+  // Turbopack inlines the runtime config at compile time, which keeps
+  // `getConfig()` working when this module isn't shared with the server.
+  return runtimeConfig ?? process.env.__NEXT_RUNTIME_CONFIG
 }
 
 export function setConfig(configValue: any): void {
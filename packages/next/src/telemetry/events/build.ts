@@ -175,9 +175,9 @@ export type EventBuildFeatureUsage = {
   invocationCount: number
 }
 export function eventBuildFeatureUsage(
-  telemetryPlugin: TelemetryPlugin
+  usages: ReturnType<TelemetryPlugin['usages']>
 ): Array<{ eventName: string; payload: EventBuildFeatureUsage }> {
-  return telemetryPlugin.usages().map(({ featureName, invocationCount }) => ({
+  return usages.map(({ featureName, invocationCount }) => ({
     eventName: EVENT_BUILD_FEATURE_USAGE,
     payload: {
       featureName,
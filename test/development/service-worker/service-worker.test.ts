@@ -0,0 +1,68 @@
+import { createNextDescribe } from 'e2e-utils'
+import { check } from 'next-test-utils'
+
+const files = {
+  'pages/index.js': `
+    export default function Page() {
+      return <p>hello world</p>
+    }
+  `,
+  'sw.js': `
+    import { message } from './lib/message'
+
+    self.addEventListener('install', () => {
+      console.log(message)
+    })
+  `,
+  'lib/message.js': `
+    export const message = 'service-worker-installed'
+  `,
+}
+
+// The service worker entry is only bundled by Turbopack.
+;(process.env.TURBOPACK ? describe : describe.skip)('service-worker', () => {
+  createNextDescribe(
+    'with experimental.serviceWorker',
+    {
+      files,
+      nextConfig: {
+        experimental: {
+          serviceWorker: true,
+        },
+      },
+    },
+    ({ next }) => {
+      it('should serve a bootstrap at /sw.js which loads the bundled worker', async () => {
+        await check(async () => (await next.fetch('/sw.js')).status, 200)
+
+        const bootstrap = await (await next.fetch('/sw.js')).text()
+        const [, chunks] = bootstrap.match(/^importScripts\((.*)\);$/m)
+        const urls: string[] = JSON.parse(`[${chunks}]`)
+        expect(urls.length).toBeGreaterThan(0)
+
+        const code = await Promise.all(
+          urls.map(async (url) => {
+            const res = await next.fetch(url)
+            expect(res.status).toBe(200)
+            return res.text()
+          })
+        )
+        expect(code.join('\n')).toContain('service-worker-installed')
+      })
+    }
+  )
+
+  createNextDescribe(
+    'without experimental.serviceWorker',
+    {
+      files,
+    },
+    ({ next }) => {
+      it('should not bundle sw.js', async () => {
+        // Wait for the entrypoints to be written.
+        expect(await next.render('/')).toContain('hello world')
+        expect((await next.fetch('/sw.js')).status).toBe(404)
+      })
+    }
+  )
+})
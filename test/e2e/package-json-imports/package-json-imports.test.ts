@@ -0,0 +1,67 @@
+import { createNextDescribe } from 'e2e-utils'
+import { shouldRunTurboDevTest } from '../../lib/next-test-utils'
+
+createNextDescribe(
+  'package.json imports',
+  {
+    files: __dirname,
+    packageJson: {
+      imports: {
+        '#env': {
+          'edge-light': './lib/env/edge.js',
+          browser: './lib/env/browser.js',
+          node: './lib/env/node.js',
+          default: './lib/env/default.js',
+        },
+        '#internal/*': './lib/internal/*.js',
+      },
+      scripts: {
+        setup: 'cp -r ./node_modules_bak/* ./node_modules',
+        build: 'yarn setup && next build',
+        dev: `yarn setup && next ${
+          shouldRunTurboDevTest() ? 'dev --turbo' : 'dev'
+        }`,
+        start: 'next start',
+      },
+    },
+    installCommand: 'yarn',
+    startCommand: (global as any).isNextDev ? 'yarn dev' : 'yarn start',
+    buildCommand: 'yarn build',
+    skipDeployment: true,
+  },
+  ({ next }) => {
+    it('resolves the node targets on the server', async () => {
+      const $ = await next.render$('/')
+      expect(JSON.parse($('#server').text())).toEqual({
+        env: 'node',
+        greeting: 'hello from #internal',
+        dependency: 'default',
+      })
+    })
+
+    it('resolves the browser targets on the client', async () => {
+      const browser = await next.browser('/')
+      const text = await browser.waitForElementByCss('#client').text()
+      expect(JSON.parse(text)).toEqual({
+        env: 'browser',
+        dependency: 'browser',
+      })
+    })
+
+    it('resolves the edge targets in edge pages', async () => {
+      const $ = await next.render$('/edge')
+      expect(JSON.parse($('#server').text())).toEqual({
+        env: 'edge',
+        dependency: 'browser',
+      })
+    })
+
+    it('resolves the edge targets in edge api routes', async () => {
+      const res = await next.fetch('/api/edge')
+      expect(await res.json()).toEqual({
+        env: 'edge',
+        greeting: 'hello from #internal',
+      })
+    })
+  }
+)
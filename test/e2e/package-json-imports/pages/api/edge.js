@@ -0,0 +1,9 @@
+import env from '#env'
+import greeting from '#internal/greeting'
+import { NextResponse } from 'next/server'
+
+export const config = { runtime: 'edge' }
+
+export default function handler() {
+  return NextResponse.json({ env, greeting })
+}
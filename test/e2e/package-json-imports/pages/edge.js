@@ -0,0 +1,8 @@
+import env from '#env'
+import dependency from 'imports-package'
+
+export const config = { runtime: 'experimental-edge' }
+
+export default function Edge() {
+  return <pre id="server">{JSON.stringify({ env, dependency })}</pre>
+}
@@ -0,0 +1,18 @@
+import { useEffect, useState } from 'react'
+import env from '#env'
+import greeting from '#internal/greeting'
+import dependency from 'imports-package'
+
+export default function Index() {
+  const [client, setClient] = useState(null)
+  useEffect(() => {
+    setClient({ env, dependency })
+  }, [])
+
+  return (
+    <>
+      <pre id="server">{JSON.stringify({ env, greeting, dependency })}</pre>
+      {client && <pre id="client">{JSON.stringify(client)}</pre>}
+    </>
+  )
+}
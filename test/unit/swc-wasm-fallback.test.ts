@@ -0,0 +1,37 @@
+/* eslint-env jest */
+import { shouldLoadWasmFallbackFirst } from 'next/dist/build/swc'
+
+const freebsd = { raw: 'x86_64-unknown-freebsd' }
+const linuxGnu = { raw: 'x86_64-unknown-linux-gnu' }
+
+describe('shouldLoadWasmFallbackFirst', () => {
+  const originalEnv = process.env.NEXT_DISABLE_SWC_WASM
+
+  afterEach(() => {
+    if (originalEnv === undefined) {
+      delete process.env.NEXT_DISABLE_SWC_WASM
+    } else {
+      process.env.NEXT_DISABLE_SWC_WASM = originalEnv
+    }
+  })
+
+  it('should load wasm first on platforms without native bindings', () => {
+    expect(shouldLoadWasmFallbackFirst([], false)).toBe(true)
+  })
+
+  it('should load wasm first for wasm fallback targets with useWasmBinary', () => {
+    expect(shouldLoadWasmFallbackFirst([freebsd], true)).toBe(true)
+    expect(shouldLoadWasmFallbackFirst([freebsd], false)).toBe(false)
+  })
+
+  it('should load native bindings first on supported platforms', () => {
+    expect(shouldLoadWasmFallbackFirst([linuxGnu], false)).toBe(false)
+    expect(shouldLoadWasmFallbackFirst([linuxGnu], true)).toBe(false)
+  })
+
+  it('should not load wasm first when it is disabled', () => {
+    process.env.NEXT_DISABLE_SWC_WASM = '1'
+    expect(shouldLoadWasmFallbackFirst([], false)).toBe(false)
+    expect(shouldLoadWasmFallbackFirst([freebsd], true)).toBe(false)
+  })
+})